@@ -0,0 +1,141 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! Regenerates the golden expectations in `tests/golden/to_radix_str.txt` by
+//! converting a corpus of values with a JavaScript engine.
+//!
+//! Usage: `cargo run --example generate_golden [engine]`, where `engine` is
+//! `node` (the default) or a path to `node` or `d8`.
+//!
+//! Each line of the output is the value's bit pattern in hex, the base and the
+//! engine's `toString(base)` result, separated by spaces. Base 10 is skipped,
+//! since engines format it with the shortest decimal algorithm rather than the
+//! radix one.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+use radix_ecmascript::double::{next_float, prev_float};
+
+/// Reads bit patterns and bases from standard input and prints the results.
+/// Works in both node and d8.
+const SCRIPT: &str = r#"
+const input = typeof require === "function"
+    ? require("fs").readFileSync(0, "utf8")
+    : (() => { let s = "", line; while ((line = readline()) != null) s += line + "\n"; return s; })();
+const bits = new BigUint64Array(1);
+const value = new Float64Array(bits.buffer);
+const output = [];
+for (const line of input.split("\n")) {
+    if (!line) continue;
+    const [hex, base] = line.split(" ");
+    bits[0] = BigInt("0x" + hex);
+    output.push(hex + " " + base + " " + value[0].toString(Number(base)));
+}
+const text = output.join("\n") + "\n";
+typeof process === "object" ? process.stdout.write(text) : print(text.slice(0, -1));
+"#;
+
+/// Returns the corpus of values, grouped by class.
+fn corpus() -> Vec<f64> {
+    let mut values = vec![
+        // Specials.
+        0.0, -0.0, f64::NAN, f64::INFINITY, f64::NEG_INFINITY,
+        // Common values.
+        0.05217266072382676, 0.123, -26.5, 0.1, 0.2, 0.3, 1.0 / 3.0, 255.5, 1e21, 123.456e30,
+        // Extremes and subnormals.
+        f64::MAX, f64::MIN_POSITIVE, 5e-324, -1e-310, prev_float(f64::MIN_POSITIVE),
+        // Huge magnitudes.
+        1e300, -1.7976931348623e308, 9007199254740993e250,
+    ];
+
+    // Powers of two and their neighbours.
+    for exponent in [-1074, -1022, -100, -1, 0, 52, 53, 64, 1023] {
+        let value = 2f64.powi(exponent);
+        values.extend([value, prev_float(value), next_float(value)]);
+    }
+
+    // Values near ties.
+    for value in [0.5, 2.5, 1.5e-5, 4503599627370495.5] {
+        values.extend([prev_float(value), value, next_float(value)]);
+    }
+
+    // Seeded random bit patterns (SplitMix64).
+    let mut state = 0x5EED_u64;
+    while values.len() < 80 {
+        state = state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        let value = f64::from_bits(z ^ (z >> 31));
+        if value.is_finite() {
+            values.push(value);
+        }
+    }
+    values
+}
+
+fn main() {
+    let engine = std::env::args().nth(1).unwrap_or_else(|| "node".into());
+    let script = std::env::temp_dir().join("radix_ecmascript_golden.js");
+    std::fs::write(&script, SCRIPT).expect("failed to write script");
+
+    let mut child = Command::new(&engine)
+        .arg(&script)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap_or_else(|e| panic!("failed to run {}: {}", engine, e));
+
+    let mut input = String::new();
+    for value in corpus() {
+        for base in (2..=36).filter(|base| *base != 10) {
+            input.push_str(&format!("{:016x} {}\n", value.to_bits(), base));
+        }
+    }
+    child.stdin.take().unwrap().write_all(input.as_bytes()).expect("failed to write input");
+
+    let output = child.wait_with_output().expect("failed to read output");
+    assert!(output.status.success(), "{} exited with {}", engine, output.status);
+
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/to_radix_str.txt");
+    std::fs::write(path, &output.stdout).expect("failed to write golden file");
+    println!("wrote {} lines to {}", input.lines().count(), path);
+}
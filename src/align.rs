@@ -0,0 +1,136 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! Aligning radix strings in columns.
+
+use crate::{radix_str, Base, InvalidBaseError, MAX_BASE, MIN_BASE};
+
+/// Radix strings padded so their radix points line up, returned by
+/// [align_radix_column].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlignedColumn {
+    rows: Vec<String>,
+    integer_width: usize,
+    fraction_width: usize,
+}
+
+impl AlignedColumn {
+    /// Returns the padded rows, all [AlignedColumn::width] characters long.
+    pub fn rows(&self) -> &[String] {
+        &self.rows
+    }
+
+    /// Returns the width of the part before the radix point, including the
+    /// sign. `NaN` and infinities are aligned as if they were integers.
+    pub fn integer_width(&self) -> usize {
+        self.integer_width
+    }
+
+    /// Returns the width of the part after the radix point, which is 0 if no
+    /// row has fraction digits.
+    pub fn fraction_width(&self) -> usize {
+        self.fraction_width
+    }
+
+    /// Returns the width of every row, including the radix point if any row
+    /// has one.
+    pub fn width(&self) -> usize {
+        match self.fraction_width {
+            0 => self.integer_width,
+            width => self.integer_width + 1 + width,
+        }
+    }
+
+    /// Returns the padded rows, dropping the widths.
+    pub fn into_rows(self) -> Vec<String> {
+        self.rows
+    }
+}
+
+/// Converts the given values to radix strings in the given [Base] and pads
+/// them with spaces so their radix points line up: integer parts are padded
+/// on the left and fraction parts on the right. Rows without fraction digits
+/// have a space in place of the radix point.
+///
+/// Returns [InvalidBaseError] if the given [Base] is out of range of
+/// [MIN_BASE] and [MAX_BASE] (inclusive).
+///
+/// Example:
+/// ```rust
+/// use radix_ecmascript::align_radix_column;
+///
+/// let column = align_radix_column([255.5, -1.0, 0.00390625], 16).unwrap();
+/// assert_eq!(column.rows(), ["ff.8 ", "-1   ", " 0.01"]);
+/// assert_eq!((column.integer_width(), column.fraction_width()), (2, 2));
+/// ```
+pub fn align_radix_column<T: Into<f64>>(
+    values: impl IntoIterator<Item = T>,
+    base: Base,
+) -> Result<AlignedColumn, InvalidBaseError> {
+    if !(MIN_BASE..=MAX_BASE).contains(&base) {
+        return Err(InvalidBaseError(base));
+    }
+
+    let strings = values
+        .into_iter()
+        .map(|value| radix_str(value.into(), base))
+        .collect::<Result<Vec<_>, _>>()?;
+    let split = |s: &str| s.split_once('.').map_or((s.len(), 0), |(integer, fraction)| (integer.len(), fraction.len()));
+    let (integer_width, fraction_width) = strings
+        .iter()
+        .map(|s| split(s))
+        .fold((0, 0), |(i, f), (integer, fraction)| (i.max(integer), f.max(fraction)));
+
+    let rows = strings
+        .iter()
+        .map(|s| {
+            let (integer, fraction) = split(s);
+            let mut row = " ".repeat(integer_width - integer);
+            row.push_str(s);
+            if fraction_width > 0 {
+                let point = if fraction == 0 { 1 } else { 0 };
+                row.extend(std::iter::repeat_n(' ', point + fraction_width - fraction));
+            }
+            row
+        })
+        .collect();
+    Ok(AlignedColumn { rows, integer_width, fraction_width })
+}
@@ -0,0 +1,77 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! Conversion into buffers from caller-supplied allocators, enabled by the
+//! `allocator_api` feature, which requires a nightly compiler.
+
+use std::alloc::Allocator;
+use crate::pool::with_buffer;
+use crate::{write_radix, Base, InvalidBaseError, MAX_BASE, MIN_BASE};
+
+/// Returns the radix string representation of the given value in the given
+/// [Base] as ASCII bytes allocated in the given allocator, keeping the output
+/// off the global heap. There is no allocator-aware `String`, so the bytes
+/// are returned as a `Vec`; they are always valid UTF-8.
+///
+/// Returns [InvalidBaseError] if the given [Base] is out of range of
+/// [MIN_BASE] and [MAX_BASE] (inclusive).
+///
+/// Example:
+/// ```rust
+/// #![feature(allocator_api)]
+/// use std::alloc::Global;
+/// use radix_ecmascript::to_radix_vec_in;
+///
+/// assert_eq!(to_radix_vec_in(255.5, 16, Global).unwrap(), b"ff.8");
+/// ```
+pub fn to_radix_vec_in<A: Allocator>(value: impl Into<f64>, base: Base, alloc: A) -> Result<Vec<u8, A>, InvalidBaseError> {
+    if !(MIN_BASE..=MAX_BASE).contains(&base) {
+        return Err(InvalidBaseError(base));
+    }
+
+    let value = value.into();
+    Ok(with_buffer(|buf| {
+        let range = write_radix(value, base, buf);
+        let mut bytes = Vec::with_capacity_in(range.len(), alloc);
+        bytes.extend_from_slice(&buf[range]);
+        bytes
+    }))
+}
@@ -0,0 +1,161 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! Mapping between digit values and digit characters.
+
+use crate::Base;
+
+/// Lowercase digit characters, as written by
+/// [ToRadixStr::to_radix_str](crate::ToRadixStr::to_radix_str).
+const LOWER: &str = "0123456789abcdefghijklmnopqrstuvwxyz";
+
+/// Uppercase digit characters.
+const UPPER: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// The case of the letter digits `a-z`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Case {
+    /// Digits `a-z`, as written by
+    /// [ToRadixStr::to_radix_str](crate::ToRadixStr::to_radix_str).
+    #[default]
+    Lower,
+    /// Digits `A-Z`.
+    Upper,
+}
+
+/// Returns the character for the given digit value (`0..36`) in the given
+/// [Case], or [None] if the digit is 36 or more.
+///
+/// Example:
+/// ```rust
+/// use radix_ecmascript::{digit_to_char, Case};
+///
+/// assert_eq!(digit_to_char(15, Case::Lower), Some('f'));
+/// assert_eq!(digit_to_char(35, Case::Upper), Some('Z'));
+/// assert_eq!(digit_to_char(36, Case::Lower), None);
+/// ```
+pub fn digit_to_char(digit: u8, case: Case) -> Option<char> {
+    let chars = match case {
+        Case::Lower => LOWER,
+        Case::Upper => UPPER,
+    };
+    chars.as_bytes().get(digit as usize).map(|&c| c as char)
+}
+
+/// Returns the digit value (`0..36`) of the given character, accepting
+/// `0-9`, `a-z` and `A-Z` like the crate's parsers, or [None] for any other
+/// character. Check the value against the [Base] being parsed.
+///
+/// Example:
+/// ```rust
+/// use radix_ecmascript::char_to_digit;
+///
+/// assert_eq!(char_to_digit('f'), Some(15));
+/// assert_eq!(char_to_digit('F'), Some(15));
+/// assert_eq!(char_to_digit('.'), None);
+/// ```
+pub fn char_to_digit(c: char) -> Option<u8> {
+    c.to_digit(36).map(|digit| digit as u8)
+}
+
+/// A custom digit alphabet, with the digit value of each character being its
+/// position in the alphabet.
+///
+/// Example:
+/// ```rust
+/// use radix_ecmascript::Alphabet;
+///
+/// let alphabet = Alphabet::new("01234567890abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ");
+/// assert!(alphabet.is_none(), "'0' is repeated");
+///
+/// let alphabet = Alphabet::new("0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ").unwrap();
+/// assert_eq!(alphabet.base(), 62);
+/// assert_eq!(alphabet.digit_to_char(36), Some('A'));
+/// assert_eq!(alphabet.char_to_digit('Z'), Some(61));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alphabet {
+    chars: Vec<char>,
+}
+
+impl Alphabet {
+    /// Returns the alphabet made of the given characters, or [None] if it has
+    /// fewer than two or more than 255 characters, or repeats a character.
+    pub fn new(chars: &str) -> Option<Self> {
+        let chars: Vec<char> = chars.chars().collect();
+        if !(2..=Base::MAX as usize).contains(&chars.len()) {
+            return None;
+        }
+        if chars.iter().enumerate().any(|(i, c)| chars[..i].contains(c)) {
+            return None;
+        }
+
+        Some(Self { chars })
+    }
+
+    /// Returns the crate's own digits `0-9` and `a-z` or `A-Z`, which is base
+    /// 36. Unlike [char_to_digit], it only accepts letters in the given
+    /// [Case].
+    pub fn standard(case: Case) -> Self {
+        let chars = match case {
+            Case::Lower => LOWER,
+            Case::Upper => UPPER,
+        };
+        Self { chars: chars.chars().collect() }
+    }
+
+    /// Returns the [Base] of the alphabet, which is its number of characters.
+    pub fn base(&self) -> Base {
+        self.chars.len() as Base
+    }
+
+    /// Returns the character for the given digit value, or [None] if the digit
+    /// is at least [Alphabet::base].
+    pub fn digit_to_char(&self, digit: u8) -> Option<char> {
+        self.chars.get(digit as usize).copied()
+    }
+
+    /// Returns the digit value of the given character, or [None] if it isn't
+    /// in the alphabet.
+    pub fn char_to_digit(&self, c: char) -> Option<u8> {
+        self.chars.iter().position(|&d| d == c).map(|digit| digit as u8)
+    }
+}
@@ -0,0 +1,167 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! [quickcheck](https://docs.rs/quickcheck) generators and properties, enabled by
+//! the `quickcheck` feature.
+//!
+//! Example:
+//! ```rust
+//! # #[cfg(feature = "f64")] {
+//! use quickcheck::{quickcheck, TestResult};
+//! use radix_ecmascript::arbitrary::{RadixStrInput, ValidBase};
+//! use radix_ecmascript::FromRadixStr;
+//!
+//! fn prop_parses(input: RadixStrInput) -> bool {
+//!     f64::from_radix_str(&input.text, input.base).is_ok()
+//! }
+//!
+//! quickcheck(prop_parses as fn(RadixStrInput) -> bool);
+//! quickcheck(radix_ecmascript::arbitrary::exact_round_trip as fn(f64, ValidBase) -> TestResult);
+//! # }
+//! ```
+
+use quickcheck::{Arbitrary, Gen, TestResult};
+use crate::parse::parse_f64;
+use crate::{to_radix_str_compat, Base, Compat, RadixFormat, MAX_BASE, MIN_BASE};
+
+/// A [Base] from [MIN_BASE] to [MAX_BASE] (inclusive). Shrinks towards
+/// [MIN_BASE].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ValidBase(pub Base);
+
+impl Arbitrary for ValidBase {
+    fn arbitrary(g: &mut Gen) -> Self {
+        ValidBase(MIN_BASE + u8::arbitrary(g) % (MAX_BASE - MIN_BASE + 1))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        Box::new((MIN_BASE..self.0).map(ValidBase))
+    }
+}
+
+/// A finite radix string in the syntax accepted by
+/// [FromRadixStr::from_radix_str], with a random sign, digit case and number
+/// of digits, and the [Base] it is written in.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RadixStrInput {
+    /// The base of the digits.
+    pub base: Base,
+    /// The radix string.
+    pub text: String,
+}
+
+impl Arbitrary for RadixStrInput {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let base = ValidBase::arbitrary(g).0;
+        let digits = |g: &mut Gen| {
+            let len = 1 + usize::arbitrary(g) % g.size().max(1);
+            (0..len)
+                .map(|_| {
+                    let digit = char::from_digit((u8::arbitrary(g) % base) as u32, base as u32).unwrap();
+                    if bool::arbitrary(g) { digit.to_ascii_uppercase() } else { digit }
+                })
+                .collect::<String>()
+        };
+
+        let mut text = g.choose(&["", "-", "+"]).unwrap().to_string();
+        text.push_str(&digits(g));
+        if bool::arbitrary(g) {
+            text.push('.');
+            text.push_str(&digits(g));
+        }
+        RadixStrInput { base, text }
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        // Drop the fraction, then trailing digits, keeping the syntax valid.
+        let base = self.base;
+        let candidates: Vec<String> = match self.text.split_once('.') {
+            Some((integer, _)) => vec![integer.to_string()],
+            None => {
+                let unsigned = self.text.trim_start_matches(['-', '+']);
+                let sign = &self.text[..self.text.len() - unsigned.len()];
+                let mut candidates = Vec::new();
+                if !sign.is_empty() {
+                    candidates.push(unsigned.to_string());
+                }
+                if unsigned.len() > 1 {
+                    candidates.push(self.text[..self.text.len() - 1].to_string());
+                }
+                candidates
+            }
+        };
+        Box::new(candidates.into_iter().map(move |text| RadixStrInput { base, text }))
+    }
+}
+
+impl Arbitrary for Compat {
+    fn arbitrary(g: &mut Gen) -> Self {
+        *g.choose(&[Compat::V8, Compat::V8Legacy, Compat::SpiderMonkey]).unwrap()
+    }
+}
+
+/// Generates every option except [RadixFormat::truncate], which loses digits.
+impl Arbitrary for RadixFormat {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let mut format = RadixFormat::new(ValidBase::arbitrary(g).0)
+            .unwrap()
+            .always_fraction(bool::arbitrary(g))
+            .leading_zero(bool::arbitrary(g))
+            .radix_point(*g.choose(&['.', ',', '·']).unwrap())
+            .nan_payload(bool::arbitrary(g));
+        if bool::arbitrary(g) {
+            format = format.group(1 + usize::arbitrary(g) % 8, *g.choose(&['_', ' ']).unwrap());
+        }
+        format
+    }
+}
+
+/// Property: the exact digits of a finite value, as written by
+/// [Compat::SpiderMonkey], parse back to the same value. Non-finite values are
+/// discarded.
+pub fn exact_round_trip(value: f64, base: ValidBase) -> TestResult {
+    if !value.is_finite() {
+        return TestResult::discard();
+    }
+
+    let s = to_radix_str_compat(value, base.0, Compat::SpiderMonkey).unwrap();
+    TestResult::from_bool(parse_f64(&s, base.0).is_ok_and(|parsed| parsed == value))
+}
@@ -0,0 +1,232 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! Exact arithmetic on radix strings.
+//!
+//! Numbers are kept as digit strings throughout, so results are exact no matter
+//! how many digits the operands have.
+
+use std::cmp::Ordering;
+use crate::{Base, InvalidBaseError, ParseRadixError, MAX_BASE, MIN_BASE};
+use crate::parse::{split_digits, Digits};
+
+/// Returns the exact sum of two radix strings in the given [Base].
+///
+/// The operands use the syntax accepted by
+/// [FromRadixStr::from_radix_str](crate::FromRadixStr::from_radix_str),
+/// except that `NaN`, `Infinity` and exponents are rejected. The result has no leading
+/// integer zeros, no trailing fraction zeros and lowercase digits, and zero is
+/// written as `0`.
+///
+/// Returns [ParseRadixError] if the given [Base] is out of range of
+/// [MIN_BASE] and [MAX_BASE] (inclusive) or either operand is malformed.
+pub fn add_radix_str(a: &str, b: &str, base: Base) -> Result<String, ParseRadixError> {
+    let (a, b) = operands(a, b, base)?;
+    Ok(signed_sum(a, b, base))
+}
+
+/// Returns the exact difference `a - b` of two radix strings in the given [Base].
+///
+/// See [add_radix_str] for the accepted syntax and result format.
+pub fn sub_radix_str(a: &str, b: &str, base: Base) -> Result<String, ParseRadixError> {
+    let (a, mut b) = operands(a, b, base)?;
+    b.negative = !b.negative;
+    Ok(signed_sum(a, b, base))
+}
+
+/// Converts a radix string from one [Base] to another exactly, however many
+/// digits it has, returning the converted string and whether it is exact.
+///
+/// The integer part always converts exactly. The fraction part only
+/// terminates in the target [Base] if every prime factor of the source [Base]
+/// that it needs also divides the target, so it is cut after at most
+/// `max_fraction_digits` digits, truncating towards zero. See [add_radix_str]
+/// for the accepted syntax and result format.
+///
+/// Returns [ParseRadixError] if either [Base] is out of range of [MIN_BASE]
+/// and [MAX_BASE] (inclusive) or the string is malformed.
+///
+/// Example:
+/// ```rust
+/// use radix_ecmascript::rebase_radix_str;
+///
+/// assert_eq!(rebase_radix_str("ff.8", 16, 10, 10).unwrap(), ("255.5".to_string(), true));
+/// assert_eq!(rebase_radix_str("0.1", 10, 2, 8).unwrap(), ("0.00011001".to_string(), false));
+/// ```
+pub fn rebase_radix_str(
+    s: &str,
+    from: Base,
+    to: Base,
+    max_fraction_digits: usize,
+) -> Result<(String, bool), ParseRadixError> {
+    if !(MIN_BASE..=MAX_BASE).contains(&to) {
+        return Err(InvalidBaseError(to).into());
+    }
+    if !(MIN_BASE..=MAX_BASE).contains(&from) {
+        return Err(InvalidBaseError(from).into());
+    }
+    let digits = split_digits(s, from)?;
+    let (from, to) = (from as u32, to as u32);
+
+    // Horner's method on the integer digits, kept least significant first.
+    let mut integer: Vec<u8> = Vec::with_capacity(digits.integer().len());
+    for &digit in digits.integer() {
+        let mut carry = digit as u32;
+        for d in integer.iter_mut() {
+            let n = *d as u32 * from + carry;
+            *d = (n % to) as u8;
+            carry = n / to;
+        }
+        while carry > 0 {
+            integer.push((carry % to) as u8);
+            carry /= to;
+        }
+    }
+    integer.reverse();
+
+    // Multiplying the fraction by the target base shifts the next target digit
+    // out as the carry.
+    let mut fraction = digits.fraction().to_vec();
+    let mut result_fraction = Vec::new();
+    loop {
+        while fraction.last() == Some(&0) {
+            fraction.pop();
+        }
+        if fraction.is_empty() || result_fraction.len() == max_fraction_digits {
+            break;
+        }
+
+        let mut carry = 0;
+        for d in fraction.iter_mut().rev() {
+            let n = *d as u32 * to + carry;
+            *d = (n % from) as u8;
+            carry = n / from;
+        }
+        result_fraction.push(carry as u8);
+    }
+
+    let point = integer.len();
+    integer.extend(result_fraction);
+    Ok((format_digits(digits.negative, &integer, point, to as Base), fraction.is_empty()))
+}
+
+/// Validates the [Base] and splits both operands into digits.
+fn operands(a: &str, b: &str, base: Base) -> Result<(Digits, Digits), ParseRadixError> {
+    if !(MIN_BASE..=MAX_BASE).contains(&base) {
+        return Err(InvalidBaseError(base).into());
+    }
+
+    Ok((split_digits(a, base)?, split_digits(b, base)?))
+}
+
+/// Returns the sum of two signed digit strings, formatted.
+fn signed_sum(mut a: Digits, mut b: Digits, base: Base) -> String {
+    // Align both operands to the same number of integer and fraction digits.
+    let integer_len = a.point.max(b.point);
+    let fraction_len = a.fraction().len().max(b.fraction().len());
+    for digits in [&mut a, &mut b] {
+        let fraction_pad = fraction_len - digits.fraction().len();
+        let integer_pad = integer_len - digits.point;
+        digits.digits.splice(0..0, std::iter::repeat_n(0, integer_pad));
+        digits.digits.extend(std::iter::repeat_n(0, fraction_pad));
+        digits.point = integer_len;
+    }
+
+    let (negative, magnitude) = if a.negative == b.negative {
+        (a.negative, add_magnitudes(&a.digits, &b.digits, base))
+    } else {
+        match a.digits.cmp(&b.digits) {
+            Ordering::Less => (b.negative, sub_magnitudes(&b.digits, &a.digits, base)),
+            _ => (a.negative, sub_magnitudes(&a.digits, &b.digits, base)),
+        }
+    };
+
+    format_digits(negative, &magnitude, magnitude.len() - fraction_len, base)
+}
+
+/// Adds two aligned magnitudes, returning one more digit than the operands.
+fn add_magnitudes(a: &[u8], b: &[u8], base: Base) -> Vec<u8> {
+    let mut result = vec![0; a.len() + 1];
+    let mut carry = 0;
+    for i in (0..a.len()).rev() {
+        let sum = a[i] + b[i] + carry;
+        carry = (sum >= base) as u8;
+        result[i + 1] = sum - carry * base;
+    }
+    result[0] = carry;
+    result
+}
+
+/// Subtracts two aligned magnitudes, where a is not less than b.
+fn sub_magnitudes(a: &[u8], b: &[u8], base: Base) -> Vec<u8> {
+    let mut result = vec![0; a.len()];
+    let mut borrow = 0;
+    for i in (0..a.len()).rev() {
+        let subtrahend = b[i] + borrow;
+        borrow = (a[i] < subtrahend) as u8;
+        result[i] = a[i] + borrow * base - subtrahend;
+    }
+    result
+}
+
+/// Formats the given digits without redundant zeros.
+fn format_digits(negative: bool, digits: &[u8], point: usize, base: Base) -> String {
+    let leading = digits[..point].iter().take_while(|d| **d == 0).count();
+    let trailing = digits[point..].iter().rev().take_while(|d| **d == 0).count();
+    let integer = &digits[leading..point];
+    let fraction = &digits[point..digits.len() - trailing];
+
+    let mut result = String::with_capacity(integer.len() + fraction.len() + 3);
+    if negative && !(integer.is_empty() && fraction.is_empty()) {
+        result.push('-');
+    }
+    if integer.is_empty() {
+        result.push('0');
+    }
+
+    let to_char = |d: &u8| char::from_digit(*d as u32, base as u32).unwrap();
+    result.extend(integer.iter().map(to_char));
+    if !fraction.is_empty() {
+        result.push('.');
+        result.extend(fraction.iter().map(to_char));
+    }
+    result
+}
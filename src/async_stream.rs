@@ -0,0 +1,175 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+
+//! Converting [Stream]s of numbers, enabled by the `futures` feature.
+
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use futures_core::{FusedStream, Stream};
+use crate::pool::with_buffer;
+use crate::{write_radix, Base, InvalidBaseError, MAX_BASE, MIN_BASE};
+
+/// The default number of values converted at once by a [RadixStream].
+const DEFAULT_BATCH_SIZE: usize = 64;
+
+/// A [Stream] of the radix string representations of the values of another
+/// stream.
+///
+/// Whatever values the inner stream has ready, up to the batch size, are
+/// converted together, so the conversion buffer is set up once per batch
+/// rather than once per value.
+///
+/// Example:
+/// ```rust
+/// use futures::executor::block_on;
+/// use futures::stream::{self, StreamExt};
+/// use radix_ecmascript::RadixStream;
+///
+/// let values = stream::iter([0.5, -255.5, 1e21]);
+/// let strings: Vec<String> = block_on(RadixStream::new(values, 16).unwrap().collect());
+/// assert_eq!(strings, ["0.8", "-ff.8", "3635c9adc5dea00000"]);
+/// ```
+#[derive(Debug)]
+pub struct RadixStream<S> {
+    inner: S,
+    base: Base,
+    batch_size: usize,
+    values: Vec<f64>,
+    ready: VecDeque<String>,
+    done: bool,
+}
+
+impl<S> RadixStream<S> {
+    /// Creates a stream converting the values of inner to the given [Base].
+    /// Streams that aren't [Unpin] can be pinned with `Box::pin` first.
+    ///
+    /// Returns [InvalidBaseError] if the given [Base] is out of range of
+    /// [MIN_BASE] and [MAX_BASE] (inclusive).
+    pub fn new(inner: S, base: Base) -> Result<Self, InvalidBaseError> {
+        if !(MIN_BASE..=MAX_BASE).contains(&base) {
+            return Err(InvalidBaseError(base));
+        }
+
+        Ok(RadixStream {
+            inner,
+            base,
+            batch_size: DEFAULT_BATCH_SIZE,
+            values: Vec::new(),
+            ready: VecDeque::new(),
+            done: false,
+        })
+    }
+
+    /// Sets the most values converted at once, which is at least 1.
+    /// Defaults to 64.
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Returns the inner stream. Values it has already yielded that haven't
+    /// been yielded as strings are dropped.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S, T> Stream for RadixStream<S>
+where
+    S: Stream<Item = T> + Unpin,
+    T: Into<f64>,
+{
+    type Item = String;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<String>> {
+        let this = self.get_mut();
+
+        if this.ready.is_empty() && !this.done {
+            // Take everything the inner stream has ready, up to a batch.
+            while this.values.len() < this.batch_size {
+                match Pin::new(&mut this.inner).poll_next(cx) {
+                    Poll::Ready(Some(value)) => this.values.push(value.into()),
+                    Poll::Ready(None) => {
+                        this.done = true;
+                        break;
+                    }
+                    Poll::Pending => break,
+                }
+            }
+
+            let (values, ready, base) = (&mut this.values, &mut this.ready, this.base);
+            with_buffer(|buf| {
+                for value in values.drain(..) {
+                    let range = write_radix(value, base, buf);
+                    ready.push_back(buf[range].iter().map(|c| *c as char).collect());
+                }
+            });
+        }
+
+        match this.ready.pop_front() {
+            Some(s) => Poll::Ready(Some(s)),
+            None if this.done => Poll::Ready(None),
+            // The inner stream returned Pending, so it will wake the task.
+            None => Poll::Pending,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (self.ready.len(), Some(self.ready.len()));
+        }
+
+        let (lower, upper) = self.inner.size_hint();
+        (lower.saturating_add(self.ready.len()), upper.and_then(|upper| upper.checked_add(self.ready.len())))
+    }
+}
+
+impl<S, T> FusedStream for RadixStream<S>
+where
+    S: Stream<Item = T> + Unpin,
+    T: Into<f64>,
+{
+    fn is_terminated(&self) -> bool {
+        self.done && self.ready.is_empty()
+    }
+}
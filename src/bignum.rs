@@ -0,0 +1,427 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! A small fixed-size unsigned big integer, modelled after V8's `Bignum`.
+//!
+//! It has no dependencies and never allocates, and holds up to 4096 bits,
+//! enough for any f64 scaled to an integer. The crate uses it for exact
+//! formatting and correctly rounded parsing.
+//!
+//! Example:
+//! ```rust
+//! use radix_ecmascript::bignum::{ratio_to_f64, Bignum};
+//!
+//! let mut n = Bignum::pow(10, 30);
+//! n.add_small(7);
+//! assert_eq!(n.to_string(), "1000000000000000000000000000007");
+//! assert_eq!(ratio_to_f64(&Bignum::from_u64(1), &Bignum::from_u64(3)), 1.0 / 3.0);
+//! ```
+//!
+//! [V8 Source Code](https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/bignum.h)
+
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display, Formatter};
+use crate::double::from_diy_fp;
+
+/// Number of bits in a single bigit.
+pub const BIGIT_SIZE: u32 = 32;
+
+/// Maximum number of bigits a [Bignum] can hold (4096 bits), which is
+/// enough to hold any f64 value scaled to an integer with room to spare.
+pub const BIGIT_CAPACITY: usize = 128;
+
+/// A fixed-capacity unsigned big integer.
+///
+/// Holds up to [BIGIT_CAPACITY] bigits of [BIGIT_SIZE] bits. Operations panic
+/// if the result does not fit.
+#[derive(Clone)]
+pub struct Bignum {
+    /// Bigits, least significant first.
+    bigits: [u32; BIGIT_CAPACITY],
+    /// Number of used bigits. The most significant used bigit is never zero.
+    used: usize,
+}
+
+impl Bignum {
+    /// Returns a [Bignum] with the value zero.
+    pub const fn zero() -> Self {
+        Self { bigits: [0; BIGIT_CAPACITY], used: 0 }
+    }
+
+    /// Returns a [Bignum] with the given value.
+    pub fn from_u64(value: u64) -> Self {
+        let mut result = Self::zero();
+        result.bigits[0] = value as u32;
+        result.bigits[1] = (value >> BIGIT_SIZE) as u32;
+        result.used = 2;
+        result.trim();
+        result
+    }
+
+    /// Returns `base` raised to the power of `exponent`.
+    pub fn pow(base: u32, exponent: u32) -> Self {
+        let mut result = Self::from_u64(1);
+        for _ in 0..exponent {
+            result.mul_small(base);
+        }
+        result
+    }
+
+    /// Reports if self is zero.
+    pub fn is_zero(&self) -> bool {
+        self.used == 0
+    }
+
+    /// Returns self as a u64 if it fits.
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.used {
+            0 => Some(0),
+            1 => Some(self.bigits[0] as u64),
+            2 => Some(self.bigits[0] as u64 | (self.bigits[1] as u64) << BIGIT_SIZE),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of significant bits in self.
+    pub fn bit_len(&self) -> u32 {
+        if self.used == 0 {
+            return 0;
+        }
+
+        let top = self.bigits[self.used - 1];
+        (self.used as u32 - 1) * BIGIT_SIZE + (BIGIT_SIZE - top.leading_zeros())
+    }
+
+    /// Reports if the given bit is set.
+    pub fn bit(&self, index: u32) -> bool {
+        let bigit = (index / BIGIT_SIZE) as usize;
+        bigit < self.used && self.bigits[bigit] >> (index % BIGIT_SIZE) & 1 == 1
+    }
+
+    /// Multiplies self by the given factor.
+    pub fn mul_small(&mut self, factor: u32) {
+        let mut carry = 0u64;
+        for bigit in &mut self.bigits[..self.used] {
+            let product = *bigit as u64 * factor as u64 + carry;
+            *bigit = product as u32;
+            carry = product >> BIGIT_SIZE;
+        }
+        self.push_carry(carry);
+        self.trim();
+    }
+
+    /// Adds the given value to self.
+    pub fn add_small(&mut self, value: u32) {
+        let mut carry = value as u64;
+        for bigit in &mut self.bigits[..self.used] {
+            if carry == 0 {
+                break;
+            }
+            let sum = *bigit as u64 + carry;
+            *bigit = sum as u32;
+            carry = sum >> BIGIT_SIZE;
+        }
+        self.push_carry(carry);
+    }
+
+    /// Subtracts other from self.
+    ///
+    /// Panics if other is greater than self.
+    pub fn sub(&mut self, other: &Bignum) {
+        assert!(*self >= *other, "bignum subtraction underflow");
+        let mut borrow = 0i64;
+        for i in 0..self.used {
+            let difference = self.bigits[i] as i64 - other.bigits[i] as i64 - borrow;
+            self.bigits[i] = difference as u32;
+            borrow = (difference < 0) as i64;
+        }
+        self.trim();
+    }
+
+    /// Shifts self to the left by the given number of bits.
+    pub fn shl(&mut self, bits: u32) {
+        if self.used == 0 {
+            return;
+        }
+
+        let bigit_shift = (bits / BIGIT_SIZE) as usize;
+        let bit_shift = bits % BIGIT_SIZE;
+        assert!(self.used + bigit_shift < BIGIT_CAPACITY, "bignum capacity exceeded");
+
+        // Shift whole bigits, writing from the top down so nothing is overwritten early.
+        let mut i = self.used + bigit_shift + 1;
+        while i > 0 {
+            i -= 1;
+            let high = if i >= bigit_shift && i - bigit_shift < self.used {
+                self.bigits[i - bigit_shift]
+            } else {
+                0
+            };
+            let low = if bit_shift != 0 && i > bigit_shift && i - bigit_shift - 1 < self.used {
+                self.bigits[i - bigit_shift - 1] >> (BIGIT_SIZE - bit_shift)
+            } else {
+                0
+            };
+            self.bigits[i] = if bit_shift == 0 { high } else { high << bit_shift | low };
+        }
+        self.used += bigit_shift + 1;
+        self.trim();
+    }
+
+    /// Divides self by the given divisor, returning the remainder.
+    ///
+    /// Panics if divisor is zero.
+    pub fn div_small(&mut self, divisor: u32) -> u32 {
+        assert_ne!(divisor, 0, "bignum division by zero");
+        let mut remainder = 0u64;
+        for bigit in self.bigits[..self.used].iter_mut().rev() {
+            let dividend = remainder << BIGIT_SIZE | *bigit as u64;
+            *bigit = (dividend / divisor as u64) as u32;
+            remainder = dividend % divisor as u64;
+        }
+        self.trim();
+        remainder as u32
+    }
+
+    /// Divides self by the given divisor, returning the quotient and leaving
+    /// the remainder in self.
+    ///
+    /// Panics if divisor is zero.
+    pub fn div_rem(&mut self, divisor: &Bignum) -> Bignum {
+        assert!(!divisor.is_zero(), "bignum division by zero");
+        let mut quotient = Bignum::zero();
+        if *self < *divisor {
+            return quotient;
+        }
+
+        // Schoolbook binary long division.
+        let shift = self.bit_len() - divisor.bit_len();
+        let mut shifted = divisor.clone();
+        shifted.shl(shift);
+        for bit in (0..=shift).rev() {
+            if *self >= shifted {
+                self.sub(&shifted);
+                quotient.set_bit(bit);
+            }
+            shifted.shr1();
+        }
+        quotient
+    }
+
+    /// Returns the digits of self in the given base, most significant first.
+    /// Zero is represented by a single zero digit.
+    ///
+    /// Panics if base is less than 2 or greater than 256.
+    pub fn to_digits(&self, base: u32) -> Vec<u8> {
+        assert!((2..=256).contains(&base), "invalid bignum digit base");
+        let mut value = self.clone();
+        let mut digits = Vec::new();
+        loop {
+            digits.push(value.div_small(base) as u8);
+            if value.is_zero() {
+                break;
+            }
+        }
+        digits.reverse();
+        digits
+    }
+
+    /// Sets the given bit.
+    fn set_bit(&mut self, index: u32) {
+        let bigit = (index / BIGIT_SIZE) as usize;
+        assert!(bigit < BIGIT_CAPACITY, "bignum capacity exceeded");
+        self.bigits[bigit] |= 1 << (index % BIGIT_SIZE);
+        self.used = self.used.max(bigit + 1);
+    }
+
+    /// Shifts self to the right by one bit.
+    fn shr1(&mut self) {
+        let mut carry = 0;
+        for bigit in self.bigits[..self.used].iter_mut().rev() {
+            let next = *bigit & 1;
+            *bigit = *bigit >> 1 | carry << (BIGIT_SIZE - 1);
+            carry = next;
+        }
+        self.trim();
+    }
+
+    /// Appends the given carry as new bigits.
+    fn push_carry(&mut self, mut carry: u64) {
+        while carry != 0 {
+            assert!(self.used < BIGIT_CAPACITY, "bignum capacity exceeded");
+            self.bigits[self.used] = carry as u32;
+            self.used += 1;
+            carry >>= BIGIT_SIZE;
+        }
+    }
+
+    /// Removes leading zero bigits.
+    fn trim(&mut self) {
+        while self.used > 0 && self.bigits[self.used - 1] == 0 {
+            self.used -= 1;
+        }
+    }
+}
+
+impl Default for Bignum {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl Display for Bignum {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let digits: String = self.to_digits(10).iter().map(|d| (b'0' + d) as char).collect();
+        f.pad_integral(true, "", &digits)
+    }
+}
+
+impl Debug for Bignum {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Bignum({})", self)
+    }
+}
+
+impl PartialEq for Bignum {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Bignum {}
+
+impl PartialOrd for Bignum {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Bignum {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.used.cmp(&other.used).then_with(|| {
+            self.bigits[..self.used].iter().rev()
+                .cmp(other.bigits[..other.used].iter().rev())
+        })
+    }
+}
+
+/// The significand width and exponent range of a binary float, which
+/// [round_ratio] rounds to.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BinaryFormat {
+    /// The number of significand bits, including the hidden bit.
+    pub significand_bits: i32,
+    /// The exponent of the smallest subnormal's significand.
+    pub denormal_exponent: i32,
+    /// The exponent from which `significand * 2^exponent` is infinite.
+    pub max_exponent: i32,
+}
+
+/// The [BinaryFormat] of f64.
+pub(crate) const F64_FORMAT: BinaryFormat =
+    BinaryFormat { significand_bits: 53, denormal_exponent: -1074, max_exponent: 972 };
+
+/// The [BinaryFormat] of f32.
+#[cfg(feature = "f32")]
+pub(crate) const F32_FORMAT: BinaryFormat =
+    BinaryFormat { significand_bits: 24, denormal_exponent: -149, max_exponent: 105 };
+
+/// Returns the f64 nearest to `numerator / denominator` (ties to even).
+pub fn ratio_to_f64(numerator: &Bignum, denominator: &Bignum) -> f64 {
+    match round_ratio(numerator, denominator, F64_FORMAT) {
+        Some((significand, exponent)) => from_diy_fp(significand, exponent),
+        None => f64::INFINITY,
+    }
+}
+
+/// Returns the f32 nearest to `numerator / denominator` (ties to even),
+/// rounding once from the exact value.
+#[cfg(feature = "f32")]
+pub fn ratio_to_f32(numerator: &Bignum, denominator: &Bignum) -> f32 {
+    match round_ratio(numerator, denominator, F32_FORMAT) {
+        // The rounded value is an f32 (or beyond f32::MAX), so the f64 holds
+        // it exactly and narrowing doesn't round again.
+        Some((significand, exponent)) => from_diy_fp(significand, exponent) as f32,
+        None => f32::INFINITY,
+    }
+}
+
+/// Rounds `numerator / denominator` to `significand * 2^exponent` in the given
+/// [BinaryFormat] (ties to even). Returns [None] if the exponent would reach
+/// the format's maximum, which is infinite.
+pub(crate) fn round_ratio(
+    numerator: &Bignum,
+    denominator: &Bignum,
+    format: BinaryFormat,
+) -> Option<(u64, i32)> {
+    let BinaryFormat { significand_bits, denormal_exponent, max_exponent } = format;
+
+    // Choose the binary exponent so the quotient has the significand's bits.
+    let mut exponent = numerator.bit_len() as i32 - denominator.bit_len() as i32 - significand_bits;
+    loop {
+        let exponent_clamped = exponent.max(denormal_exponent);
+        if exponent_clamped >= max_exponent {
+            return None;
+        }
+
+        let mut remainder = numerator.clone();
+        let mut divisor = denominator.clone();
+        if exponent_clamped >= 0 {
+            divisor.shl(exponent_clamped as u32);
+        } else {
+            remainder.shl(-exponent_clamped as u32);
+        }
+        let quotient = remainder.div_rem(&divisor).to_u64().unwrap_or(u64::MAX);
+        if quotient >> significand_bits != 0 {
+            exponent += 1;
+            continue;
+        }
+
+        // Round half to even.
+        remainder.shl(1);
+        let round_up = match remainder.cmp(&divisor) {
+            Ordering::Greater => true,
+            Ordering::Equal => quotient & 1 == 1,
+            Ordering::Less => false,
+        };
+        return Some((quotient + round_up as u64, exponent_clamped));
+    }
+}
@@ -0,0 +1,105 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! Bijective numeration of unsigned integers.
+//!
+//! Bijective base-k has the digits `1..=k` and no zero, so every integer has
+//! exactly one representation, with zero written as the empty string. With
+//! [SPREADSHEET_ALPHABET] it numbers spreadsheet columns: `A` to `Z`, then
+//! `AA`, `AB` and so on.
+
+#[cfg(feature = "parse")]
+use crate::ParseRadixError;
+
+/// The digits of bijective base-26 as used for spreadsheet columns.
+pub const SPREADSHEET_ALPHABET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Returns the given integer in the bijective base given by the number of
+/// characters in the alphabet, whose characters are the digits `1..=k` in
+/// order.
+///
+/// Panics if the alphabet has fewer than two characters.
+///
+/// Example:
+/// ```rust
+/// use radix_ecmascript::{to_bijective, SPREADSHEET_ALPHABET};
+///
+/// assert_eq!(to_bijective(1, SPREADSHEET_ALPHABET), "A");
+/// assert_eq!(to_bijective(28, SPREADSHEET_ALPHABET), "AB");
+/// ```
+pub fn to_bijective(mut n: u64, alphabet: &str) -> String {
+    let digits: Vec<char> = alphabet.chars().collect();
+    let base = digits.len() as u64;
+    assert!(base >= 2, "bijective alphabet must have at least two characters");
+
+    let mut result = Vec::new();
+    while n > 0 {
+        // Digits are 1..=base, so take one off before dividing.
+        n -= 1;
+        result.push(digits[(n % base) as usize]);
+        n /= base;
+    }
+    result.iter().rev().collect()
+}
+
+/// Returns the integer written in the bijective base given by the number of
+/// characters in the alphabet, as produced by [to_bijective]. The empty string
+/// is zero. Digits must match the alphabet exactly, including case.
+///
+/// Returns [ParseRadixError] if a character isn't in the alphabet or the value
+/// doesn't fit in a u64.
+///
+/// Panics if the alphabet has fewer than two characters.
+#[cfg(feature = "parse")]
+pub fn from_bijective(s: &str, alphabet: &str) -> Result<u64, ParseRadixError> {
+    let base = alphabet.chars().count() as u64;
+    assert!(base >= 2, "bijective alphabet must have at least two characters");
+
+    let mut n: u64 = 0;
+    for (i, c) in s.char_indices() {
+        let digit = alphabet.chars().position(|d| d == c).ok_or(ParseRadixError::InvalidDigit(i))? as u64 + 1;
+        n = n
+            .checked_mul(base)
+            .and_then(|n| n.checked_add(digit))
+            .ok_or(ParseRadixError::Overflow)?;
+    }
+    Ok(n)
+}
@@ -0,0 +1,133 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! Rebases numbers read line by line from standard input, for shell
+//! pipelines over large files.
+//!
+//! Usage: `rebase FROM TO [--csv COLUMN | --jsonl FIELD]`, installed with
+//! `cargo install radix-ecmascript --features serde_json`.
+//!
+//! Each line is a number in base `FROM`, written back in base `TO`. With
+//! `--csv`, only the zero-based `COLUMN` of comma-separated lines is
+//! converted (quoted fields aren't supported). With `--jsonl`, lines are JSON
+//! objects whose `FIELD` is converted, read from a string in base `FROM` or a
+//! JSON number, and written as a string. Output is flushed per line, and the
+//! first line that fails to convert stops the program.
+
+use std::io::{BufRead, BufWriter, Write};
+use std::process::exit;
+use radix_ecmascript::{Base, FromRadixStr, ToRadixStr};
+
+/// Which part of each line holds the number.
+enum Mode {
+    Line,
+    Csv(usize),
+    Jsonl(String),
+}
+
+/// Converts the given number from base `from` to base `to`.
+fn rebase(s: &str, from: Base, to: Base) -> Result<String, String> {
+    let value = f64::from_radix_str(s.trim(), from).map_err(|e| format!("{:?}: {}", s, e))?;
+    Ok(value.to_radix_str(to).unwrap())
+}
+
+/// Converts the number in the given line.
+fn convert(line: &str, mode: &Mode, from: Base, to: Base) -> Result<String, String> {
+    match mode {
+        Mode::Line => rebase(line, from, to),
+        Mode::Csv(column) => {
+            let mut fields: Vec<&str> = line.split(',').collect();
+            let field = fields.get_mut(*column).ok_or_else(|| format!("no column {}", column))?;
+            let converted = rebase(field, from, to)?;
+            *field = &converted;
+            Ok(fields.join(","))
+        }
+        Mode::Jsonl(name) => {
+            let mut object: serde_json::Value = serde_json::from_str(line).map_err(|e| e.to_string())?;
+            let field = object.get_mut(name).ok_or_else(|| format!("no field {:?}", name))?;
+            let converted = match field {
+                serde_json::Value::String(s) => rebase(s, from, to)?,
+                serde_json::Value::Number(n) => n.as_f64().unwrap().to_radix_str(to).unwrap(),
+                _ => return Err(format!("field {:?} isn't a string or number", name)),
+            };
+            *field = converted.into();
+            Ok(object.to_string())
+        }
+    }
+}
+
+/// Prints the given message and exits with a failure status.
+fn fail(message: &str) -> ! {
+    eprintln!("rebase: {}", message);
+    exit(1);
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let base = |arg: Option<&String>| -> Base {
+        match arg.and_then(|arg| arg.parse().ok()) {
+            Some(base) if (radix_ecmascript::MIN_BASE..=radix_ecmascript::MAX_BASE).contains(&base) => base,
+            _ => fail("usage: rebase FROM TO [--csv COLUMN | --jsonl FIELD], with bases 2 to 36"),
+        }
+    };
+    let (from, to) = (base(args.first()), base(args.get(1)));
+    let mode = match (args.get(2).map(String::as_str), args.get(3)) {
+        (None, _) => Mode::Line,
+        (Some("--csv"), Some(column)) => Mode::Csv(column.parse().unwrap_or_else(|_| fail("invalid column"))),
+        (Some("--jsonl"), Some(field)) => Mode::Jsonl(field.clone()),
+        _ => fail("usage: rebase FROM TO [--csv COLUMN | --jsonl FIELD]"),
+    };
+
+    let stdin = std::io::stdin().lock();
+    let mut stdout = BufWriter::new(std::io::stdout().lock());
+    for (i, line) in stdin.lines().enumerate() {
+        let line = line.unwrap_or_else(|e| fail(&e.to_string()));
+        match convert(&line, &mode, from, to) {
+            Ok(converted) => {
+                // Stop quietly when the reader goes away, as in `| head`.
+                if writeln!(stdout, "{}", converted).and_then(|_| stdout.flush()).is_err() {
+                    return;
+                }
+            }
+            Err(e) => fail(&format!("line {}: {}", i + 1, e)),
+        }
+    }
+}
@@ -0,0 +1,127 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! Lossless encoding of float bit patterns as radix strings.
+//!
+//! Unlike [ToRadixStr](crate::ToRadixStr), these encode the raw
+//! [f64::to_bits] as an unsigned integer, so every value, including the sign of
+//! zero and NaN payloads, survives a round-trip.
+
+use crate::{Base, InvalidBaseError, MAX_BASE, MIN_BASE};
+#[cfg(feature = "parse")]
+use crate::{lut::digit_value, ParseRadixError};
+
+/// Returns the bit pattern of the given value as an unsigned integer in the
+/// given [Base], such as `3ff0000000000000` for `1.0` in base 16.
+///
+/// Returns [InvalidBaseError] if the given [Base] is out of range of
+/// [MIN_BASE] and [MAX_BASE] (inclusive).
+#[cfg(feature = "f64")]
+pub fn bits_to_radix_str(value: f64, base: Base) -> Result<String, InvalidBaseError> {
+    integer_to_radix_str(value.to_bits(), base)
+}
+
+/// Returns the bit pattern of the given f32 as an unsigned integer in the
+/// given [Base].
+///
+/// See [bits_to_radix_str].
+#[cfg(feature = "f32")]
+pub fn bits_to_radix_str_f32(value: f32, base: Base) -> Result<String, InvalidBaseError> {
+    integer_to_radix_str(value.to_bits() as u64, base)
+}
+
+/// Returns the f64 with the bit pattern encoded by [bits_to_radix_str].
+///
+/// Digits are case-insensitive and signs are rejected. Returns
+/// [ParseRadixError] if the given [Base] is out of range of [MIN_BASE] and
+/// [MAX_BASE] (inclusive), the string is malformed, or the pattern is wider
+/// than 64 bits.
+#[cfg(all(feature = "parse", feature = "f64"))]
+pub fn bits_from_radix_str(s: &str, base: Base) -> Result<f64, ParseRadixError> {
+    integer_from_radix_str(s, base, u64::MAX).map(f64::from_bits)
+}
+
+/// Returns the f32 with the bit pattern encoded by [bits_to_radix_str_f32].
+///
+/// See [bits_from_radix_str].
+#[cfg(all(feature = "parse", feature = "f32"))]
+pub fn bits_from_radix_str_f32(s: &str, base: Base) -> Result<f32, ParseRadixError> {
+    integer_from_radix_str(s, base, u32::MAX as u64).map(|bits| f32::from_bits(bits as u32))
+}
+
+/// Formats an unsigned integer in the given [Base].
+fn integer_to_radix_str(mut n: u64, base: Base) -> Result<String, InvalidBaseError> {
+    if !(MIN_BASE..=MAX_BASE).contains(&base) {
+        return Err(InvalidBaseError(base));
+    }
+
+    let mut digits = Vec::with_capacity(64);
+    loop {
+        digits.push(char::from_digit((n % base as u64) as u32, base as u32).unwrap());
+        n /= base as u64;
+        if n == 0 {
+            break;
+        }
+    }
+    Ok(digits.iter().rev().collect())
+}
+
+/// Parses an unsigned integer in the given [Base] that is at most max.
+#[cfg(feature = "parse")]
+fn integer_from_radix_str(s: &str, base: Base, max: u64) -> Result<u64, ParseRadixError> {
+    if !(MIN_BASE..=MAX_BASE).contains(&base) {
+        return Err(InvalidBaseError(base).into());
+    }
+    if s.is_empty() {
+        return Err(ParseRadixError::Empty);
+    }
+
+    let mut n: u64 = 0;
+    for (i, &c) in s.as_bytes().iter().enumerate() {
+        let digit = digit_value(c, base).ok_or(ParseRadixError::InvalidDigit(i))?;
+        n = n
+            .checked_mul(base as u64)
+            .and_then(|n| n.checked_add(digit as u64))
+            .filter(|n| *n <= max)
+            .ok_or(ParseRadixError::Overflow)?;
+    }
+    Ok(n)
+}
@@ -0,0 +1,99 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! Worst-case output lengths of [ToRadixStr::to_radix_str](crate::ToRadixStr::to_radix_str).
+//!
+//! A radix string is either dominated by integer digits (large values, which
+//! have no fraction part) or by fraction digits (small values). The fraction
+//! loop stops once the remaining fraction is below `delta`, which starts at no
+//! less than `2^-1074` for f64 (`2^-202` for f32 values, whose half-ULP as an
+//! f64 is at least that) and grows by a factor of the base per digit. So the
+//! length is bounded by one sign character plus the larger of:
+//! * the number of integer digits of the largest finite value, and
+//! * `0.` followed by `ceil(1074 / log2(base))` (or `ceil(202 / log2(base))`) digits.
+//!
+//! Values with both integer and fraction digits are well below these bounds.
+
+#[cfg(any(feature = "f32", feature = "f64"))]
+use crate::Base;
+
+/// Worst-case lengths of f64 radix strings, indexed by base.
+#[cfg(feature = "f64")]
+const MAX_LEN_F64: [usize; 37] = [
+    0, 0, 1077, 681, 540, 466, 419, 386, 361, 342, 327, 314, 303, 294, 286, 278, 272,
+    266, 261, 256, 252, 248, 244, 241, 238, 235, 232, 229, 227, 225, 222, 220, 218,
+    216, 215, 213, 211,
+];
+
+/// Worst-case lengths of f32 radix strings, indexed by base.
+#[cfg(feature = "f32")]
+const MAX_LEN_F32: [usize; 37] = [
+    0, 0, 205, 131, 104, 90, 82, 75, 71, 67, 64, 62, 60, 58, 57, 55, 54, 53, 52, 51,
+    50, 49, 49, 48, 48, 47, 46, 46, 46, 45, 45, 44, 44, 44, 43, 43, 43,
+];
+
+/// The worst-case length of an f64 radix string in any [Base], that of base 2.
+pub const MAX_LEN: usize = 1077;
+
+#[cfg(feature = "f64")]
+const _: () = assert!(MAX_LEN == MAX_LEN_F64[2]);
+
+/// Returns the worst-case length in bytes of an f64 radix string in the given
+/// [Base], or 0 if the [Base] is out of range of [MIN_BASE](crate::MIN_BASE)
+/// and [MAX_BASE](crate::MAX_BASE).
+#[cfg(feature = "f64")]
+pub const fn max_len(base: Base) -> usize {
+    if base as usize >= MAX_LEN_F64.len() {
+        return 0;
+    }
+    MAX_LEN_F64[base as usize]
+}
+
+/// Returns the worst-case length in bytes of an f32 radix string in the given
+/// [Base], or 0 if the [Base] is out of range of [MIN_BASE](crate::MIN_BASE)
+/// and [MAX_BASE](crate::MAX_BASE).
+#[cfg(feature = "f32")]
+pub const fn max_len_f32(base: Base) -> usize {
+    if base as usize >= MAX_LEN_F32.len() {
+        return 0;
+    }
+    MAX_LEN_F32[base as usize]
+}
@@ -0,0 +1,117 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! Number printing for JavaScript code generators.
+//!
+//! Code generators can take a [NumberPrinter] to choose how numeric literals
+//! are emitted, with output byte-compatible with V8.
+
+use std::fmt::Write;
+use crate::literal::to_decimal_str;
+use crate::shortest_js_literal;
+#[cfg(feature = "format")]
+use crate::RadixFormat;
+
+/// Prints numbers into generated code.
+///
+/// Implemented by [DefaultToString], [ShortestLiteral], [RadixFormat] and any
+/// `Fn(f64) -> String`.
+///
+/// Example:
+/// ```rust
+/// use radix_ecmascript::{DefaultToString, NumberPrinter, ShortestLiteral};
+///
+/// fn emit(printer: &dyn NumberPrinter, value: f64) -> String {
+///     let mut code = String::from("x = ");
+///     printer.print_number(value, &mut code).unwrap();
+///     code
+/// }
+///
+/// assert_eq!(emit(&DefaultToString, 0.5), "x = 0.5");
+/// assert_eq!(emit(&ShortestLiteral, 0.5), "x = .5");
+/// ```
+pub trait NumberPrinter {
+    /// Writes the given value to the given output.
+    fn print_number(&self, value: f64, out: &mut dyn Write) -> std::fmt::Result;
+}
+
+/// Prints numbers as ECMAScript's Number::toString does with no radix, such
+/// as `1e+21` and `0.5`. Negative zero is printed as `0`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DefaultToString;
+
+impl NumberPrinter for DefaultToString {
+    fn print_number(&self, value: f64, out: &mut dyn Write) -> std::fmt::Result {
+        if value.is_nan() {
+            return out.write_str("NaN");
+        }
+        if value < 0.0 {
+            out.write_char('-')?;
+        }
+        if value.is_infinite() {
+            return out.write_str("Infinity");
+        }
+        out.write_str(&to_decimal_str(value.abs()))
+    }
+}
+
+/// Prints numbers with [shortest_js_literal], such as `.5` and `1e3`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ShortestLiteral;
+
+impl NumberPrinter for ShortestLiteral {
+    fn print_number(&self, value: f64, out: &mut dyn Write) -> std::fmt::Result {
+        out.write_str(&shortest_js_literal(value))
+    }
+}
+
+#[cfg(feature = "format")]
+impl NumberPrinter for RadixFormat {
+    fn print_number(&self, value: f64, out: &mut dyn Write) -> std::fmt::Result {
+        out.write_str(&self.format(value))
+    }
+}
+
+impl<F: Fn(f64) -> String> NumberPrinter for F {
+    fn print_number(&self, value: f64, out: &mut dyn Write) -> std::fmt::Result {
+        out.write_str(&self(value))
+    }
+}
@@ -0,0 +1,96 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! [nom](https://docs.rs/nom) parser combinators, enabled by the `nom` feature.
+//!
+//! The combinators parse `&str` input and compose with any nom parser.
+//!
+//! Example:
+//! ```rust
+//! use nom::sequence::separated_pair;
+//! use nom::character::complete::char;
+//! use radix_ecmascript::combinators::radix_float;
+//!
+//! let mut point = separated_pair(radix_float::<nom::error::Error<&str>>(16), char(','), radix_float(16));
+//! assert_eq!(point("1a.8,-ff;"), Ok((";", (26.5, -255.0))));
+//! ```
+
+use nom::error::{ErrorKind, ParseError};
+use nom::{Err, IResult};
+use crate::{scan_js_literal, scan_js_numeric_literal, scan_radix_literal, Base, JsLiteral};
+
+/// Returns a parser for radix strings in the given [Base], with the syntax of
+/// [scan_radix_literal].
+///
+/// The parser fails with [ErrorKind::Float] if the input doesn't start with a
+/// numeral, and fails unrecoverably with [ErrorKind::Verify] if the [Base] is
+/// invalid.
+pub fn radix_float<'a, E: ParseError<&'a str>>(base: Base) -> impl Fn(&'a str) -> IResult<&'a str, f64, E> {
+    move |input| match scan_radix_literal(input, base) {
+        Ok(Some((len, value))) => Ok((&input[len..], value)),
+        Ok(None) => Err(Err::Error(E::from_error_kind(input, ErrorKind::Float))),
+        Err(_) => Err(Err::Failure(E::from_error_kind(input, ErrorKind::Verify))),
+    }
+}
+
+/// Returns a parser for JavaScript numeric literals, with the syntax of
+/// [scan_js_numeric_literal].
+///
+/// The parser fails with [ErrorKind::Float] if the input doesn't start with a
+/// literal.
+pub fn js_numeric_literal<'a, E: ParseError<&'a str>>() -> impl Fn(&'a str) -> IResult<&'a str, f64, E> {
+    move |input| match scan_js_numeric_literal(input) {
+        Some((len, value)) => Ok((&input[len..], value)),
+        None => Err(Err::Error(E::from_error_kind(input, ErrorKind::Float))),
+    }
+}
+
+/// Returns a parser for JavaScript numeric literals including BigInt
+/// literals, with the syntax of [scan_js_literal].
+///
+/// The parser fails with [ErrorKind::Float] if the input doesn't start with a
+/// literal or a BigInt literal is out of range of i128.
+pub fn js_literal<'a, E: ParseError<&'a str>>() -> impl Fn(&'a str) -> IResult<&'a str, JsLiteral, E> {
+    move |input| match scan_js_literal(input) {
+        Some((len, literal)) => Ok((&input[len..], literal)),
+        None => Err(Err::Error(E::from_error_kind(input, ErrorKind::Float))),
+    }
+}
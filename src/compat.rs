@@ -0,0 +1,141 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! Conversion compatible with specific engines and engine revisions.
+
+use crate::{radix_str, Base, InvalidBaseError, MAX_BASE, MIN_BASE};
+use crate::spidermonkey::to_radix_str_spidermonkey;
+
+/// The engine algorithm (and revision) to match when converting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum Compat {
+    /// V8's current `DoubleToRadixCString`, which stops generating fraction
+    /// digits once they uniquely identify the value and fills integer digits
+    /// beyond the value's precision with zeros. This is what
+    /// [ToRadixStr::to_radix_str] implements.
+    #[default]
+    V8,
+    /// V8's `DoubleToRadixCString` before its delta-based rewrite, which
+    /// generates fraction digits until the fraction reaches zero or 1098
+    /// digits have been written, and computes every integer digit.
+    V8Legacy,
+    /// SpiderMonkey's `js_dtobasestr`, as used by Firefox, which computes
+    /// integer digits exactly and generates the shortest fraction digits that
+    /// round back to the value.
+    SpiderMonkey,
+}
+
+/// Returns the radix string representation of the given value as produced by
+/// the given [Compat] revision.
+///
+/// Returns [InvalidBaseError] if the given [Base] is out of range of
+/// [MIN_BASE] and [MAX_BASE] (inclusive).
+pub fn to_radix_str_compat(value: impl Into<f64>, base: Base, compat: Compat) -> Result<String, InvalidBaseError> {
+    let value = value.into();
+    match compat {
+        Compat::V8 => radix_str(value, base),
+        Compat::V8Legacy | Compat::SpiderMonkey => {
+            if !(MIN_BASE..=MAX_BASE).contains(&base) {
+                return Err(InvalidBaseError(base));
+            }
+
+            // Special values are handled by Number.prototype.toString before
+            // reaching the conversion algorithm, and are the same everywhere.
+            if !value.is_finite() || value == 0.0 {
+                return radix_str(value, base);
+            }
+
+            Ok(match compat {
+                Compat::SpiderMonkey => to_radix_str_spidermonkey(value, base),
+                _ => to_radix_str_v8_legacy(value, base),
+            })
+        }
+    }
+}
+
+/// Converts a finite, non-zero value using V8's legacy algorithm.
+fn to_radix_str_v8_legacy(value: f64, base: Base) -> String {
+    // Fraction digits are never generated beyond this limit.
+    const MAX_FRACTION_DIGITS: usize = 1098;
+
+    let base_f64 = base as f64;
+    let to_char = |digit: f64| char::from_digit(digit as u32, base as u32).unwrap();
+
+    let negative = value < 0.0;
+    let value = value.abs();
+    let mut integer = value.floor();
+    let mut fraction = value - integer;
+
+    // Convert the integer part starting from the back. Always generate at
+    // least one digit.
+    let mut integer_digits = Vec::new();
+    loop {
+        let remainder = integer % base_f64;
+        integer_digits.push(to_char(remainder));
+        integer -= remainder;
+        integer /= base_f64;
+
+        if integer < 1.0 {
+            break;
+        }
+    }
+
+    let mut result = String::with_capacity(integer_digits.len() + 2);
+    if negative {
+        result.push('-');
+    }
+    result.extend(integer_digits.iter().rev());
+
+    // Convert the fraction part by repeatedly multiplying by the base.
+    if fraction > 0.0 {
+        result.push('.');
+    }
+    let mut fraction_digits = 0;
+    while fraction > 0.0 && fraction_digits < MAX_FRACTION_DIGITS {
+        fraction *= base_f64;
+        result.push(to_char(fraction.floor()));
+        fraction -= fraction.floor();
+        fraction_digits += 1;
+    }
+
+    result
+}
@@ -0,0 +1,153 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! Continued-fraction expansion of floating-point values.
+//!
+//! Every finite f64 is a rational number, so its continued fraction is finite
+//! and can be computed exactly with the Euclidean algorithm.
+
+use std::fmt::{Display, Formatter};
+use crate::{Base, InvalidBaseError, MAX_BASE, MIN_BASE};
+use crate::bignum::Bignum;
+use crate::double::{exponent, significand};
+
+/// The continued-fraction expansion `[a0; a1, a2, ...]` of a finite f64.
+///
+/// The first term `a0` is `floor(value)` and may be negative; all other terms
+/// are positive.
+#[derive(Clone)]
+pub struct ContinuedFraction {
+    negative: bool,
+    terms: Vec<Bignum>,
+}
+
+impl ContinuedFraction {
+    /// Returns the number of terms, which is always at least one.
+    pub fn num_terms(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// Reports if the first term is negative.
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// Returns the magnitude of the term at the given index, or `None` if the
+    /// index is out of range or the term doesn't fit in a u64.
+    pub fn term_u64(&self, index: usize) -> Option<u64> {
+        self.terms.get(index)?.to_u64()
+    }
+
+    /// Returns the expansion in `[a0; a1, a2, ...]` notation with each term
+    /// written in the given base.
+    ///
+    /// Returns [InvalidBaseError] if the given [Base] is out of range of
+    /// [MIN_BASE] and [MAX_BASE] (inclusive).
+    pub fn to_radix_str(&self, base: Base) -> Result<String, InvalidBaseError> {
+        if !(MIN_BASE..=MAX_BASE).contains(&base) {
+            return Err(InvalidBaseError(base));
+        }
+
+        let mut result = String::from("[");
+        for (i, term) in self.terms.iter().enumerate() {
+            match i {
+                0 => {
+                    if self.negative {
+                        result.push('-');
+                    }
+                }
+                1 => result.push_str("; "),
+                _ => result.push_str(", "),
+            }
+
+            for digit in term.to_digits(base as u32) {
+                result.push(char::from_digit(digit as u32, base as u32).unwrap());
+            }
+        }
+        result.push(']');
+        Ok(result)
+    }
+}
+
+impl Display for ContinuedFraction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_radix_str(10).unwrap())
+    }
+}
+
+/// Returns the exact continued-fraction expansion of the given value, or
+/// `None` if the value is NaN or infinite.
+pub fn continued_fraction(value: f64) -> Option<ContinuedFraction> {
+    if !value.is_finite() {
+        return None;
+    }
+
+    // Write |value| as the fraction p/q, where q is a power of two.
+    let exp = exponent(value);
+    let mut p = Bignum::from_u64(significand(value));
+    let mut q = Bignum::from_u64(1);
+    if exp >= 0 {
+        p.shl(exp as u32);
+    } else {
+        q.shl(-exp as u32);
+    }
+
+    // The first term is floor(value), which rounds away from zero for negative values.
+    let negative = value < 0.0;
+    let mut a0 = p.div_rem(&q);
+    if negative && !p.is_zero() {
+        a0.add_small(1);
+        let mut remainder = q.clone();
+        remainder.sub(&p);
+        p = remainder;
+    }
+
+    let mut terms = vec![a0];
+    while !p.is_zero() {
+        std::mem::swap(&mut p, &mut q);
+        terms.push(p.div_rem(&q));
+    }
+
+    Some(ContinuedFraction {
+        negative: negative && !terms[0].is_zero(),
+        terms,
+    })
+}
@@ -0,0 +1,196 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! Curated test vectors, enabled by the `corpus` feature, so integrations can
+//! be validated against the data this crate is tested with.
+//!
+//! Example:
+//! ```rust
+//! # #[cfg(feature = "f64")] {
+//! use radix_ecmascript::ToRadixStr;
+//! use radix_ecmascript::corpus::all;
+//!
+//! for vector in all() {
+//!     assert_eq!(vector.value.to_radix_str(vector.base).unwrap(), vector.expected);
+//! }
+//! # }
+//! ```
+
+use crate::Base;
+
+/// A value, a [Base] and the radix string V8's `toString(base)` and
+/// [ToRadixStr::to_radix_str](crate::ToRadixStr::to_radix_str) return for it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Vector {
+    /// The value to convert.
+    pub value: f64,
+    /// The base to convert in.
+    pub base: Base,
+    /// The expected radix string.
+    pub expected: &'static str,
+}
+
+/// A value and [Base] for which V8 and SpiderMonkey return different radix
+/// strings, as [Compat::V8](crate::Compat::V8) and
+/// [Compat::SpiderMonkey](crate::Compat::SpiderMonkey) reproduce.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Divergence {
+    /// The value to convert.
+    pub value: f64,
+    /// The base to convert in.
+    pub base: Base,
+    /// V8's radix string.
+    pub v8: &'static str,
+    /// SpiderMonkey's radix string, the shortest that rounds back to the value.
+    pub spidermonkey: &'static str,
+}
+
+/// NaN, infinities and signed zeros.
+pub static SPECIALS: &[Vector] = &[
+    Vector { value: 0.0, base: 2, expected: "0" },
+    Vector { value: -0.0, base: 36, expected: "0" },
+    Vector { value: f64::NAN, base: 16, expected: "NaN" },
+    Vector { value: f64::INFINITY, base: 2, expected: "Infinity" },
+    Vector { value: f64::NEG_INFINITY, base: 36, expected: "-Infinity" },
+];
+
+/// The smallest subnormal and the values either side of the smallest normal.
+pub static SUBNORMALS: &[Vector] = &[
+    Vector {
+        value: 5e-324,
+        base: 36,
+        expected: "0.0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003",
+    },
+    Vector {
+        value: -5e-324,
+        base: 16,
+        expected: "-0.00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004",
+    },
+    Vector {
+        value: 2.225073858507201e-308,
+        base: 32,
+        expected: "0.0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007vvvvvvvvvu",
+    },
+    Vector {
+        value: 2.2250738585072014e-308,
+        base: 36,
+        expected: "0.0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000034lmua2oev",
+    },
+];
+
+/// Values whose last fraction digit rounds up and carries into the digits
+/// before it, such as `0.13bb...` becoming `0.14` in base 12.
+pub static CARRIES: &[Vector] = &[
+    Vector { value: 0.1111111111111111, base: 12, expected: "0.14" },
+    Vector { value: 0.2222222222222222, base: 6, expected: "0.12" },
+    Vector { value: 0.4444444444444444, base: 6, expected: "0.24" },
+    Vector { value: 1.3333333333333333, base: 3, expected: "1.1" },
+    Vector { value: 1.3333333333333333, base: 9, expected: "1.3" },
+    Vector { value: 1.6666666666666665, base: 33, expected: "1.m" },
+    Vector { value: 0.12244897959183673, base: 28, expected: "0.3c" },
+    Vector { value: 0.2716049382716049, base: 3, expected: "0.0211" },
+    Vector { value: 0.49382716049382713, base: 3, expected: "0.1111" },
+];
+
+/// Ties, neighbours of powers of two and integers beyond 2^53, whose low
+/// digits are zero-filled.
+pub static EDGES: &[Vector] = &[
+    Vector { value: 0.5, base: 2, expected: "0.1" },
+    Vector { value: 0.49999999999999994, base: 2, expected: "0.011111111111111111111111111111111111111111111111111111" },
+    Vector { value: 0.5000000000000001, base: 3, expected: "0.1111111111111111111111111111111121" },
+    Vector { value: 2.5, base: 5, expected: "2.2222222222222222222222" },
+    Vector { value: 4503599627370495.5, base: 2, expected: "1111111111111111111111111111111111111111111111111111.1" },
+    Vector { value: 9007199254740992.0, base: 36, expected: "2gosa7pa2gw" },
+    Vector { value: 1e21, base: 36, expected: "5v1j4f4ds7c000" },
+    Vector { value: -255.5, base: 16, expected: "-ff.8" },
+    Vector { value: 0.123, base: 16, expected: "0.1f7ced916872b" },
+    Vector { value: 0.1, base: 3, expected: "0.0022002200220022002200220022002201" },
+];
+
+/// Values V8 and SpiderMonkey convert differently: V8 stops once the digits
+/// identify the value to within half an ulp and zero-fills integer digits
+/// beyond 2^53, while SpiderMonkey writes the shortest digits that round
+/// back to the value.
+pub static DIVERGENCES: &[Divergence] = &[
+    Divergence {
+        value: 3.5,
+        base: 3,
+        v8: "10.111111111111111111111111111111112",
+        spidermonkey: "10.111111111111111111111111111111111",
+    },
+    Divergence { value: 1e21, base: 7, v8: "5135235413265003022600000", spidermonkey: "5135235413265003022550266" },
+    Divergence {
+        value: 0.3333333333333333,
+        base: 5,
+        v8: "0.13131313131313131313131",
+        spidermonkey: "0.131313131313131313131312",
+    },
+    Divergence {
+        value: 1.1529215046068472e18,
+        base: 3,
+        v8: "21200101122222021102111220121120110000",
+        spidermonkey: "21200101122222021102111220121120012212",
+    },
+    Divergence { value: 0.3, base: 6, v8: "0.144444444444444444444", spidermonkey: "0.144444444444444444445" },
+];
+
+/// The golden expectations recorded from V8 by the `generate_golden` example,
+/// with each line holding the value's bit pattern in hex, the base and the
+/// radix string.
+const GOLDEN: &str = include_str!("../tests/golden/to_radix_str.txt");
+
+/// Returns the golden vectors: every base except 10 for each of a corpus of
+/// values, with the expected strings recorded from V8.
+pub fn golden() -> impl Iterator<Item = Vector> {
+    GOLDEN.lines().map(|line| {
+        let mut fields = line.split(' ');
+        let bits = u64::from_str_radix(fields.next().unwrap(), 16).unwrap();
+        let base = fields.next().unwrap().parse().unwrap();
+        let expected = fields.next().unwrap();
+        Vector { value: f64::from_bits(bits), base, expected }
+    })
+}
+
+/// Returns every [Vector]: [SPECIALS], [SUBNORMALS], [CARRIES], [EDGES] and
+/// then [golden].
+pub fn all() -> impl Iterator<Item = Vector> {
+    [SPECIALS, SUBNORMALS, CARRIES, EDGES].into_iter().flatten().copied().chain(golden())
+}
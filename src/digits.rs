@@ -0,0 +1,269 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! Digits of values in a base, as numeric values and as counts computed
+//! exactly without formatting.
+
+use crate::bignum::Bignum;
+use crate::double::{exponent, significand};
+use crate::pool::with_buffer;
+use crate::{write_radix, Base, InvalidBaseError, MAX_BASE, MIN_BASE};
+
+/// A part of a radix string, passed to the callback of [for_each_digit] in
+/// the order the parts are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DigitEvent {
+    /// The value is negative. Comes first, if at all.
+    Sign,
+    /// A digit before the radix point, most significant first.
+    IntegerDigit(u8),
+    /// The radix point, which only precedes fraction digits.
+    Point,
+    /// A digit after the radix point.
+    FractionDigit(u8),
+}
+
+/// The digits of a radix string as numeric values, returned by
+/// [to_radix_digits].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RadixDigits {
+    negative: bool,
+    digits: Vec<u8>,
+    point: usize,
+}
+
+impl RadixDigits {
+    /// Reports if the value is negative. Like
+    /// [ToRadixStr::to_radix_str](crate::ToRadixStr::to_radix_str), `-0` has
+    /// no sign.
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// Returns the digit values (`0..base`), most significant first.
+    pub fn digits(&self) -> &[u8] {
+        &self.digits
+    }
+
+    /// Returns the position of the radix point in [RadixDigits::digits], which
+    /// is the number of integer digits.
+    pub fn point(&self) -> usize {
+        self.point
+    }
+
+    /// Returns the digits before the radix point.
+    pub fn integer(&self) -> &[u8] {
+        &self.digits[..self.point]
+    }
+
+    /// Returns the digits after the radix point.
+    pub fn fraction(&self) -> &[u8] {
+        &self.digits[self.point..]
+    }
+
+    /// Returns the digit values, dropping the sign and point position.
+    pub fn into_digits(self) -> Vec<u8> {
+        self.digits
+    }
+}
+
+/// Returns the digits of the radix string representation of the given value
+/// in the given [Base] as numeric values rather than characters, or [None] for
+/// NaN and infinities.
+///
+/// Returns [InvalidBaseError] if the given [Base] is out of range of
+/// [MIN_BASE] and [MAX_BASE] (inclusive).
+///
+/// Example:
+/// ```rust
+/// use radix_ecmascript::to_radix_digits;
+///
+/// let digits = to_radix_digits(-26.5, 16).unwrap().unwrap();
+/// assert!(digits.is_negative());
+/// assert_eq!(digits.integer(), [1, 10]);
+/// assert_eq!(digits.fraction(), [8]);
+/// ```
+pub fn to_radix_digits(value: impl Into<f64>, base: Base) -> Result<Option<RadixDigits>, InvalidBaseError> {
+    let mut negative = false;
+    let mut digits = Vec::new();
+    let mut point = None;
+    let finite = for_each_digit(value, base, |event| match event {
+        DigitEvent::Sign => negative = true,
+        DigitEvent::IntegerDigit(digit) | DigitEvent::FractionDigit(digit) => digits.push(digit),
+        DigitEvent::Point => point = Some(digits.len()),
+    })?;
+
+    Ok(finite.then(|| RadixDigits {
+        negative,
+        point: point.unwrap_or(digits.len()),
+        digits,
+    }))
+}
+
+/// Calls f with each part of the radix string representation of the given
+/// value in the given [Base], digits as numeric values, without allocating.
+/// The other digit APIs can be built on this.
+///
+/// Returns `false` without calling f for NaN and infinities, which have no
+/// digits, and `true` otherwise.
+///
+/// Returns [InvalidBaseError] if the given [Base] is out of range of
+/// [MIN_BASE] and [MAX_BASE] (inclusive).
+///
+/// Example:
+/// ```rust
+/// use radix_ecmascript::{for_each_digit, DigitEvent};
+///
+/// let mut events = Vec::new();
+/// for_each_digit(-26.5, 16, |event| events.push(event)).unwrap();
+/// assert_eq!(events, [
+///     DigitEvent::Sign,
+///     DigitEvent::IntegerDigit(1),
+///     DigitEvent::IntegerDigit(10),
+///     DigitEvent::Point,
+///     DigitEvent::FractionDigit(8),
+/// ]);
+/// ```
+pub fn for_each_digit(value: impl Into<f64>, base: Base, mut f: impl FnMut(DigitEvent)) -> Result<bool, InvalidBaseError> {
+    if !(MIN_BASE..=MAX_BASE).contains(&base) {
+        return Err(InvalidBaseError(base));
+    }
+    let value = value.into();
+    if !value.is_finite() {
+        return Ok(false);
+    }
+
+    with_buffer(|buf| {
+        let range = write_radix(value, base, buf);
+        let mut fraction = false;
+        for &c in &buf[range] {
+            f(match c {
+                b'-' => DigitEvent::Sign,
+                b'.' => {
+                    fraction = true;
+                    DigitEvent::Point
+                }
+                // The written digits are valid in base 36.
+                _ => {
+                    let digit = (c as char).to_digit(36).unwrap_or(0) as u8;
+                    if fraction { DigitEvent::FractionDigit(digit) } else { DigitEvent::IntegerDigit(digit) }
+                }
+            });
+        }
+    });
+    Ok(true)
+}
+
+/// Returns the number of digits before the radix point in the radix string
+/// representation of the given value in the given [Base], not counting the
+/// sign. Values less than one in magnitude have the single integer digit `0`.
+/// Returns [None] for NaN and infinities.
+///
+/// Returns [InvalidBaseError] if the given [Base] is out of range of
+/// [MIN_BASE] and [MAX_BASE] (inclusive).
+///
+/// Example:
+/// ```rust
+/// use radix_ecmascript::integer_digit_count;
+///
+/// assert_eq!(integer_digit_count(255.5, 16).unwrap(), Some(2));
+/// assert_eq!(integer_digit_count(0.5, 16).unwrap(), Some(1));
+/// ```
+pub fn integer_digit_count(value: impl Into<f64>, base: Base) -> Result<Option<usize>, InvalidBaseError> {
+    let value = value.into();
+    Ok(ilog_radix(value, base)?
+        .map(|ilog| ilog.max(0) as usize + 1)
+        .or(if value == 0.0 { Some(1) } else { None }))
+}
+
+/// Returns `floor(log_base(|value|))`, the position of the most significant
+/// digit of the given value in the given [Base] relative to the radix point,
+/// computed exactly. Returns [None] for zero, NaN and infinities.
+///
+/// Returns [InvalidBaseError] if the given [Base] is out of range of
+/// [MIN_BASE] and [MAX_BASE] (inclusive).
+///
+/// Example:
+/// ```rust
+/// use radix_ecmascript::ilog_radix;
+///
+/// assert_eq!(ilog_radix(256.0, 16).unwrap(), Some(2));
+/// assert_eq!(ilog_radix(0.0625, 16).unwrap(), Some(-1));
+/// assert_eq!(ilog_radix(0.06, 16).unwrap(), Some(-2));
+/// ```
+pub fn ilog_radix(value: impl Into<f64>, base: Base) -> Result<Option<i32>, InvalidBaseError> {
+    if !(MIN_BASE..=MAX_BASE).contains(&base) {
+        return Err(InvalidBaseError(base));
+    }
+
+    let value = value.into().abs();
+    if value == 0.0 || !value.is_finite() {
+        return Ok(None);
+    }
+
+    // |value| = m * 2^e exactly.
+    let mut m = Bignum::from_u64(significand(value));
+    let e = exponent(value);
+    if value >= 1.0 {
+        // Count the digits of the integer part; the fraction can't add any.
+        if e > 0 {
+            m.shl(e as u32);
+        } else {
+            m = Bignum::from_u64(value as u64);
+        }
+        let mut digits = 0;
+        while !m.is_zero() {
+            m.div_small(base as u32);
+            digits += 1;
+        }
+        return Ok(Some(digits - 1));
+    }
+
+    // Find the smallest k with m * base^k >= 2^-e.
+    let mut one = Bignum::from_u64(1);
+    one.shl(-e as u32);
+    let mut k = 0;
+    while m < one {
+        m.mul_small(base as u32);
+        k += 1;
+    }
+    Ok(Some(-k))
+}
@@ -0,0 +1,229 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! Helper functions for inspecting 64-bit floats, implemented from Google's open-source V8 engine.
+//!
+//! A finite f64 has the value `significand(f) * 2^exponent(f)`, where the
+//! significand includes the [HIDDEN_BIT] for normal values.
+//!
+//! [V8 Source Code](https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+
+/// Mask of the sign bit.
+pub const SIGN_MASK: u64 = 0x8000_0000_0000_0000;
+/// Mask of the biased exponent bits.
+pub const EXPONENT_MASK: u64 = 0x7FF0_0000_0000_0000;
+/// Mask of the physical significand bits.
+pub const SIGNIFICAND_MASK: u64 = 0x000F_FFFF_FFFF_FFFF;
+/// The implicit leading significand bit of normal values.
+pub const HIDDEN_BIT: u64 = 0x0010_0000_0000_0000;
+/// Number of physical significand bits, excluding the [HIDDEN_BIT].
+pub const PHYSICAL_SIGNIFICAND_SIZE: i32 = 52;
+/// Number of significand bits, including the [HIDDEN_BIT].
+pub const SIGNIFICAND_SIZE: i32 = 53;
+/// The bias subtracted from the biased exponent bits to get [exponent].
+pub const EXPONENT_BIAS: i32 = 0x3FF + PHYSICAL_SIGNIFICAND_SIZE;
+/// The [exponent] of subnormal values.
+pub const DENORMAL_EXPONENT: i32 = -EXPONENT_BIAS + 1;
+/// The [exponent] of infinities and NaNs.
+pub const MAX_EXPONENT: i32 = 0x7FF - EXPONENT_BIAS;
+
+/// Bit pattern of +Infinity.
+const INFINITY: u64 = 0x7FF0_0000_0000_0000;
+
+/// Reports if f is subnormal or zero.
+pub fn is_denormal(f: f64) -> bool {
+    f.to_bits() & EXPONENT_MASK == 0
+}
+
+/// Gets the significand of f, including the [HIDDEN_BIT] if f is normal.
+pub fn significand(f: f64) -> u64 {
+    let significand = f.to_bits() & SIGNIFICAND_MASK;
+
+    if is_denormal(f) {
+        significand
+    } else {
+        significand + HIDDEN_BIT
+    }
+}
+
+/// Gets the exponent of f, such that f is `significand(f) * 2^exponent(f)`.
+pub fn exponent(f: f64) -> i32 {
+    if is_denormal(f) {
+        return DENORMAL_EXPONENT;
+    }
+
+    let biased = ((f.to_bits() & EXPONENT_MASK) >> PHYSICAL_SIGNIFICAND_SIZE) as i32;
+    biased - EXPONENT_BIAS
+}
+
+/// Gets the sign bit of f, which is 1 for negative values (including `-0`
+/// and NaNs with the sign bit set) and 0 otherwise.
+pub fn sign(f: f64) -> u8 {
+    (f.to_bits() >> 63) as u8
+}
+
+/// Gets the raw 11-bit biased exponent field of f.
+pub fn biased_exponent(f: f64) -> u16 {
+    ((f.to_bits() & EXPONENT_MASK) >> PHYSICAL_SIGNIFICAND_SIZE) as u16
+}
+
+/// Gets the raw 52-bit mantissa field of f, excluding the [HIDDEN_BIT].
+pub fn mantissa(f: f64) -> u64 {
+    f.to_bits() & SIGNIFICAND_MASK
+}
+
+/// Returns the f64 with the given sign bit, biased exponent and mantissa
+/// fields, the inverse of [sign], [biased_exponent] and [mantissa].
+///
+/// Returns `None` if the sign is not 0 or 1, the biased exponent doesn't fit
+/// in 11 bits or the mantissa doesn't fit in 52 bits.
+pub fn from_parts(sign: u8, biased_exponent: u16, mantissa: u64) -> Option<f64> {
+    if sign > 1 || biased_exponent > 0x7FF || mantissa & !SIGNIFICAND_MASK != 0 {
+        return None;
+    }
+
+    Some(f64::from_bits(
+        (sign as u64) << 63 | (biased_exponent as u64) << PHYSICAL_SIGNIFICAND_SIZE | mantissa
+    ))
+}
+
+/// Returns the next greater f64 (IEEE 754 `nextUp`).
+///
+/// Returns the smallest positive subnormal if f is `+0` or `-0`, `+Infinity`
+/// if f is `+Infinity` or [f64::MAX], `-`[f64::MAX] if f is `-Infinity`, and
+/// NaN if f is NaN.
+pub fn next_float(f: f64) -> f64 {
+    let bits = f.to_bits();
+
+    if f.is_nan() || bits == INFINITY {
+        return f;
+    }
+
+    if f == 0.0 {
+        return f64::from_bits(1);
+    }
+
+    f64::from_bits(if bits & SIGN_MASK != 0 {
+        bits - 1
+    } else {
+        bits + 1
+    })
+}
+
+/// Returns the next smaller f64 (IEEE 754 `nextDown`).
+///
+/// This is the mirror image of [next_float].
+pub fn prev_float(f: f64) -> f64 {
+    -next_float(-f)
+}
+
+/// Returns the unit in the last place of f, the distance from |f| to the next
+/// f64 further from zero.
+///
+/// Returns the smallest positive subnormal if f is zero, `+Infinity` if f is
+/// infinite, and NaN if f is NaN. As there is no finite f64 above [f64::MAX],
+/// its ULP is the distance to the f64 below it.
+pub fn ulp(f: f64) -> f64 {
+    let f = f.abs();
+
+    if f.is_nan() || f.is_infinite() {
+        return f;
+    }
+
+    if f == f64::MAX {
+        return f - prev_float(f);
+    }
+
+    next_float(f) - f
+}
+
+/// Returns f rounded toward zero, the same as [f64::trunc], by clearing the
+/// significand bits below the binary point. Unlike [f64::trunc], it's
+/// available without `std`.
+///
+/// Returns f if it's an integer, infinite or NaN, and a zero of f's sign if
+/// |f| is below one.
+pub(crate) fn trunc(f: f64) -> f64 {
+    let bits = f.to_bits();
+    let exponent = biased_exponent(f) as i32 - 0x3FF;
+
+    if exponent >= PHYSICAL_SIGNIFICAND_SIZE {
+        return f;
+    }
+
+    if exponent < 0 {
+        return f64::from_bits(bits & SIGN_MASK);
+    }
+
+    f64::from_bits(bits & !(SIGNIFICAND_MASK >> exponent))
+}
+
+/// Returns the f64 with the value `significand * 2^exponent`.
+///
+/// The significand must fit in 53 bits, unless its low bits are zero.
+/// Returns +Infinity if the value is too large and 0 if it's too small.
+#[cfg(feature = "std")]
+pub(crate) fn from_diy_fp(significand: u64, exponent: i32) -> f64 {
+    let mut significand = significand;
+    let mut exponent = exponent;
+
+    while significand > HIDDEN_BIT + SIGNIFICAND_MASK {
+        significand >>= 1;
+        exponent += 1;
+    }
+    if exponent >= MAX_EXPONENT {
+        return f64::INFINITY;
+    }
+    if exponent < DENORMAL_EXPONENT || significand == 0 {
+        return 0.0;
+    }
+    while exponent > DENORMAL_EXPONENT && significand & HIDDEN_BIT == 0 {
+        significand <<= 1;
+        exponent -= 1;
+    }
+
+    let biased_exponent = if exponent == DENORMAL_EXPONENT && significand & HIDDEN_BIT == 0 {
+        0
+    } else {
+        (exponent + EXPONENT_BIAS) as u64
+    };
+    f64::from_bits(significand & SIGNIFICAND_MASK | biased_exponent << PHYSICAL_SIGNIFICAND_SIZE)
+}
@@ -0,0 +1,347 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! Exactness queries about the radix representation of floating-point values.
+//!
+//! These describe the exact binary value of an f64, not the digits produced by
+//! [ToRadixStr::to_radix_str](crate::ToRadixStr::to_radix_str), which stops
+//! once the digits uniquely identify the value.
+
+use std::cmp::Ordering;
+use crate::{Base, InvalidBaseError, MAX_BASE, MIN_BASE};
+use crate::bignum::{ratio_to_f64, Bignum};
+use crate::double::{exponent, significand};
+
+/// Returns the digits of the given finite, non-negative integer in the given
+/// [Base].
+fn integer_digits(integer: f64, base: Base) -> Vec<u8> {
+    let mut bits = Bignum::from_u64(significand(integer));
+    match exponent(integer) {
+        e if e >= 0 => bits.shl(e as u32),
+        _ => bits = Bignum::from_u64(integer as u64),
+    }
+    bits.to_digits(base as u32)
+}
+
+/// Returns the number of binary fraction digits of the exact value of the
+/// given finite value.
+fn fraction_bits(value: f64) -> u32 {
+    let significand = significand(value);
+    if significand == 0 {
+        return 0;
+    }
+
+    let exponent = exponent(value) + significand.trailing_zeros() as i32;
+    (-exponent).max(0) as u32
+}
+
+/// Reports if the exact value of the given value has a terminating
+/// representation in the given [Base].
+///
+/// Every f64 is a fraction with a power-of-two denominator, so this is true for
+/// all even bases, and for odd bases only when the value is an integer.
+/// Returns false for NaN and infinities.
+///
+/// Returns [InvalidBaseError] if the given [Base] is out of range of
+/// [MIN_BASE] and [MAX_BASE] (inclusive).
+pub fn is_exact_in_base(value: f64, base: Base) -> Result<bool, InvalidBaseError> {
+    exact_fraction_digit_count(value, base).map(|count| count.is_some())
+}
+
+/// Returns the number of fraction digits needed to represent the exact value
+/// of the given value in the given [Base], or `None` if the representation
+/// doesn't terminate. Integers need zero fraction digits.
+///
+/// A value with `k` binary fraction digits needs `ceil(k / a)` digits in a
+/// base divisible by `2^a` (and no higher power of two).
+/// Returns `None` for NaN and infinities.
+///
+/// Returns [InvalidBaseError] if the given [Base] is out of range of
+/// [MIN_BASE] and [MAX_BASE] (inclusive).
+pub fn exact_fraction_digit_count(value: f64, base: Base) -> Result<Option<usize>, InvalidBaseError> {
+    if !(MIN_BASE..=MAX_BASE).contains(&base) {
+        return Err(InvalidBaseError(base));
+    }
+
+    if !value.is_finite() {
+        return Ok(None);
+    }
+
+    let bits = fraction_bits(value);
+    let twos = base.trailing_zeros();
+    Ok(match (bits, twos) {
+        (0, _) => Some(0),
+        (_, 0) => None,
+        _ => Some(bits.div_ceil(twos) as usize),
+    })
+}
+
+/// Rounds the given value to the nearest number with at most the given number
+/// of fraction digits in the given [Base] (ties to even), and returns the f64
+/// nearest to that number.
+///
+/// This snaps values to what a fixed-width radix encoding can carry. The sign
+/// of the value is kept, so small negative values round to `-0`. NaN and
+/// infinities are returned as is.
+///
+/// Returns [InvalidBaseError] if the given [Base] is out of range of
+/// [MIN_BASE] and [MAX_BASE] (inclusive).
+pub fn quantize(value: f64, base: Base, fraction_digits: usize) -> Result<f64, InvalidBaseError> {
+    // Values that are already exact don't need rounding, and neither do values
+    // quantized to steps below half the smallest subnormal.
+    let exact_digits = exact_fraction_digit_count(value, base)?;
+    let step_bits = fraction_digits as f64 * (base as f64).log2();
+    if !value.is_finite() || exact_digits.is_some_and(|count| count <= fraction_digits) || step_bits > 1076.0 {
+        return Ok(value);
+    }
+
+    // |value| * base^fraction_digits = numerator / denominator. The value has
+    // a fraction part here, so its exponent is negative.
+    let mut numerator = Bignum::from_u64(significand(value));
+    for _ in 0..fraction_digits {
+        numerator.mul_small(base as u32);
+    }
+    let mut denominator = Bignum::from_u64(1);
+    denominator.shl(-exponent(value) as u32);
+
+    // Round the scaled value to an integer, ties to even.
+    let mut quotient = numerator.div_rem(&denominator);
+    numerator.shl(1);
+    let round_up = match numerator.cmp(&denominator) {
+        Ordering::Greater => true,
+        Ordering::Equal => quotient.bit(0),
+        Ordering::Less => false,
+    };
+    if round_up {
+        quotient.add_small(1);
+    }
+
+    let scale = Bignum::pow(base as u32, fraction_digits as u32);
+    Ok(ratio_to_f64(&quotient, &scale).copysign(value))
+}
+
+/// Returns the exact base-10 expansion of the given value, with every digit
+/// of its binary value, as in `0.1000000000000000055511151231257827021181583404541015625`
+/// for `0.1`.
+///
+/// The expansion always terminates, since every f64 is a fraction with a
+/// power-of-two denominator. Integers have no radix point, `-0` keeps its sign,
+/// and NaN and infinities are written as `NaN`, `Infinity` and `-Infinity`.
+pub fn to_exact_decimal(value: f64) -> String {
+    if value.is_nan() {
+        return "NaN".to_string();
+    }
+    if value.is_infinite() {
+        return if value < 0.0 { "-Infinity" } else { "Infinity" }.to_string();
+    }
+
+    // Rust formats with a fixed precision exactly, so asking for every exact
+    // fraction digit gives the full expansion.
+    let fraction_digits = exact_fraction_digit_count(value, 10).unwrap().unwrap();
+    format!("{:.*}", fraction_digits, value)
+}
+
+/// An iterator over the exact fraction digits of a value, returned by
+/// [exact_fraction_digits].
+#[derive(Clone)]
+pub struct ExactFractionDigits {
+    remainder: Bignum,
+    denominator: Bignum,
+    base: Base,
+}
+
+impl Iterator for ExactFractionDigits {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.remainder.is_zero() {
+            return None;
+        }
+
+        self.remainder.mul_small(self.base as u32);
+        let digit = self.remainder.div_rem(&self.denominator);
+        Some(digit.to_u64().unwrap() as u8)
+    }
+}
+
+impl std::iter::FusedIterator for ExactFractionDigits {}
+
+/// Returns an iterator over the exact digit values (`0..base`) of the fraction
+/// part of |value| in the given [Base], generated lazily from the significand.
+///
+/// Unlike [ToRadixStr::to_radix_str](crate::ToRadixStr::to_radix_str), which
+/// stops once the digits uniquely identify the value, the iterator only ends
+/// once the exact expansion terminates (see [exact_fraction_digit_count]).
+/// Non-terminating expansions never end, so use [Iterator::take] to choose a
+/// cutoff. The iterator is empty for integers, NaN and infinities.
+///
+/// Returns [InvalidBaseError] if the given [Base] is out of range of
+/// [MIN_BASE] and [MAX_BASE] (inclusive).
+pub fn exact_fraction_digits(value: f64, base: Base) -> Result<ExactFractionDigits, InvalidBaseError> {
+    if !(MIN_BASE..=MAX_BASE).contains(&base) {
+        return Err(InvalidBaseError(base));
+    }
+
+    // fraction(|value|) = remainder / 2^-exponent.
+    let mut remainder = Bignum::zero();
+    let mut denominator = Bignum::from_u64(1);
+    if value.is_finite() && fraction_bits(value) > 0 {
+        remainder = Bignum::from_u64(significand(value));
+        denominator.shl(-exponent(value) as u32);
+        remainder.div_rem(&denominator);
+    }
+
+    Ok(ExactFractionDigits { remainder, denominator, base })
+}
+
+/// Returns radix strings in the given [Base] with the given number of fraction
+/// digits that bound the exact value of the given value from below and above,
+/// as `(lower, upper)`, or [None] for NaN and infinities.
+///
+/// The bounds are the value rounded towards negative and positive infinity,
+/// so they are equal when the value is exact in that many digits. Fraction
+/// digits are never trimmed, and zero bounds have no sign.
+///
+/// Returns [InvalidBaseError] if the given [Base] is out of range of
+/// [MIN_BASE] and [MAX_BASE] (inclusive).
+///
+/// Example:
+/// ```rust
+/// use radix_ecmascript::to_radix_interval;
+///
+/// let (lower, upper) = to_radix_interval(0.1, 3, 4).unwrap().unwrap();
+/// assert_eq!((lower.as_str(), upper.as_str()), ("0.0022", "0.0100"));
+/// let (lower, upper) = to_radix_interval(-0.1, 3, 4).unwrap().unwrap();
+/// assert_eq!((lower.as_str(), upper.as_str()), ("-0.0100", "-0.0022"));
+/// ```
+pub fn to_radix_interval(
+    value: impl Into<f64>,
+    base: Base,
+    fraction_digits: usize,
+) -> Result<Option<(String, String)>, InvalidBaseError> {
+    let value = value.into();
+    let mut fraction = exact_fraction_digits(value, base)?;
+    if !value.is_finite() {
+        return Ok(None);
+    }
+
+    // The exact integer part, followed by the fraction digits, truncated.
+    let mut truncated = integer_digits(value.abs().trunc(), base);
+    let len = truncated.len() + fraction_digits;
+    truncated.extend(fraction.by_ref().take(fraction_digits));
+    truncated.resize(len, 0);
+
+    // Add one in the last place unless the digits are exact.
+    let mut rounded_up = truncated.clone();
+    if !fraction.remainder.is_zero() {
+        let carry = rounded_up.iter_mut().rev().all(|digit| {
+            *digit = (*digit + 1) % base;
+            *digit == 0
+        });
+        if carry {
+            rounded_up.insert(0, 1);
+        }
+    }
+
+    let negative = value.is_sign_negative();
+    let write = |digits: &[u8]| {
+        let point = digits.len() - fraction_digits;
+        let mut s = String::with_capacity(digits.len() + 2);
+        if negative && digits.iter().any(|&digit| digit != 0) {
+            s.push('-');
+        }
+        for (i, &digit) in digits.iter().enumerate() {
+            if i == point {
+                s.push('.');
+            }
+            s.push(char::from_digit(digit as u32, base as u32).unwrap());
+        }
+        s
+    };
+    let (lower, upper) = if negative { (&rounded_up, &truncated) } else { (&truncated, &rounded_up) };
+    Ok(Some((write(lower), write(upper))))
+}
+
+/// Returns the exact value of the given value as a scaled integer in the
+/// given [Base]: the digits of a mantissa and an exponent such that
+/// value = mantissa × base^exponent, or [None] for NaN, infinities and values
+/// whose expansion doesn't terminate (see [exact_fraction_digit_count]).
+///
+/// The mantissa has no trailing zero digits, so the pair is unique. It has a
+/// `-` sign for negative values, and zero is `("0", 0)` whatever its sign.
+///
+/// Returns [InvalidBaseError] if the given [Base] is out of range of
+/// [MIN_BASE] and [MAX_BASE] (inclusive).
+///
+/// Example:
+/// ```rust
+/// use radix_ecmascript::to_scaled_integer;
+///
+/// assert_eq!(to_scaled_integer(-255.5, 16).unwrap(), Some(("-ff8".to_string(), -1)));
+/// assert_eq!(to_scaled_integer(1e21, 10).unwrap(), Some(("1".to_string(), 21)));
+/// assert_eq!(to_scaled_integer(0.5, 3).unwrap(), None);
+/// ```
+pub fn to_scaled_integer(value: impl Into<f64>, base: Base) -> Result<Option<(String, i32)>, InvalidBaseError> {
+    let value = value.into();
+    let Some(fraction_digits) = exact_fraction_digit_count(value, base)? else {
+        return Ok(None);
+    };
+    if value == 0.0 {
+        return Ok(Some(("0".to_string(), 0)));
+    }
+
+    // The digits of |value| * base^fraction_digits are the exact integer part
+    // followed by every fraction digit. As a single integer it can outgrow a
+    // Bignum, such as 17^1074 for the smallest subnormal in base 34.
+    let mut digits = integer_digits(value.abs().trunc(), base);
+    digits.extend(exact_fraction_digits(value, base)?);
+    let leading = digits.iter().take_while(|&&digit| digit == 0).count();
+    let zeros = digits.iter().rev().take_while(|&&digit| digit == 0).count();
+    digits.truncate(digits.len() - zeros);
+    digits.drain(..leading);
+
+    let mut s = String::with_capacity(digits.len() + 1);
+    if value < 0.0 {
+        s.push('-');
+    }
+    s.extend(digits.iter().map(|&digit| char::from_digit(digit as u32, base as u32).unwrap()));
+    Ok(Some((s, zeros as i32 - fraction_digits as i32)))
+}
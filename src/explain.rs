@@ -0,0 +1,191 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! A step-by-step account of the conversion, for teaching and for debugging
+//! mismatches against engines.
+
+use crate::double::{exponent, next_float};
+use crate::{radix_str, Base, InvalidBaseError, MAX_BASE, MIN_BASE};
+
+/// A step of the conversion of |value| by
+/// [ToRadixStr::to_radix_str](crate::ToRadixStr::to_radix_str), in the order
+/// the steps are taken. The f64 fields hold the algorithm's state after the
+/// step.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum Step {
+    /// The value was split into its integer and fraction parts, and delta was
+    /// set to half the distance to the next f64 (at least the smallest
+    /// subnormal). Fraction digits are only generated while the fraction is
+    /// at least delta.
+    Split { integer: f64, fraction: f64, delta: f64 },
+    /// The fraction and delta were multiplied by the base, and the integer
+    /// part of the fraction was taken as the next fraction digit.
+    FractionDigit { digit: u8, fraction: f64, delta: f64 },
+    /// The remaining fraction is more than half (or exactly half after an odd
+    /// digit) and the digits no longer identify the value, so the last digit
+    /// is rounded up.
+    RoundUp { fraction: f64, delta: f64 },
+    /// Rounding up dropped the fraction digits after the given position (the
+    /// first fraction digit is at 0), which were all `base - 1`, and
+    /// incremented the digit at the position to the given digit.
+    Carry { position: usize, digit: u8 },
+    /// Rounding up dropped every fraction digit and incremented the integer
+    /// part.
+    CarryIntoInteger { integer: f64 },
+    /// The remaining fraction is below delta, so the digits identify the value.
+    Stop { fraction: f64, delta: f64 },
+    /// The integer part was divided by the base, and the digit was written as
+    /// `0`, as its value is beyond the precision of an f64.
+    ZeroFill { integer: f64 },
+    /// The integer part's remainder by the base was written as the next
+    /// integer digit, from least significant, and it was divided by the base.
+    IntegerDigit { digit: u8, integer: f64 },
+}
+
+/// The steps of a conversion and its result, returned by [explain_radix_str].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Explanation {
+    steps: Vec<Step>,
+    result: String,
+}
+
+impl Explanation {
+    /// Returns the steps of the conversion. NaN, infinities and zeros have
+    /// no steps.
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    /// Returns the radix string, the same as
+    /// [ToRadixStr::to_radix_str](crate::ToRadixStr::to_radix_str) returns.
+    pub fn result(&self) -> &str {
+        &self.result
+    }
+}
+
+/// Converts the given value to a radix string in the given [Base] as
+/// [ToRadixStr::to_radix_str](crate::ToRadixStr::to_radix_str) does, recording
+/// every step taken: the fraction and delta behind each digit, rounding
+/// decisions and carry propagation.
+///
+/// Returns [InvalidBaseError] if the given [Base] is out of range of
+/// [MIN_BASE] and [MAX_BASE] (inclusive).
+///
+/// Example:
+/// ```rust
+/// use radix_ecmascript::{explain_radix_str, Step};
+///
+/// let explanation = explain_radix_str(0.75, 2).unwrap();
+/// assert_eq!(explanation.result(), "0.11");
+/// assert!(matches!(explanation.steps()[1], Step::FractionDigit { digit: 1, .. }));
+/// ```
+pub fn explain_radix_str(value: impl Into<f64>, base: Base) -> Result<Explanation, InvalidBaseError> {
+    if !(MIN_BASE..=MAX_BASE).contains(&base) {
+        return Err(InvalidBaseError(base));
+    }
+
+    let value = value.into();
+    let result = radix_str(value, base)?;
+    let mut steps = Vec::new();
+    if !value.is_finite() || value == 0.0 {
+        return Ok(Explanation { steps, result });
+    }
+
+    let value = value.abs();
+    let base_f64 = base as f64;
+
+    let mut integer = value.floor();
+    let mut fraction = value - integer;
+    let mut delta = (0.5 * (next_float(value) - value)).max(next_float(0.0));
+    steps.push(Step::Split { integer, fraction, delta });
+
+    if fraction >= delta {
+        let mut digits: Vec<u8> = Vec::new();
+        loop {
+            fraction *= base_f64;
+            delta *= base_f64;
+            let digit = fraction as u8;
+            digits.push(digit);
+            fraction -= digit as f64;
+            steps.push(Step::FractionDigit { digit, fraction, delta });
+
+            if (fraction > 0.5 || (fraction == 0.5 && (digit & 1) == 1)) && fraction + delta > 1.0 {
+                steps.push(Step::RoundUp { fraction, delta });
+                loop {
+                    let Some(digit) = digits.pop() else {
+                        integer += 1.0;
+                        steps.push(Step::CarryIntoInteger { integer });
+                        break;
+                    };
+                    if digit + 1 < base {
+                        steps.push(Step::Carry { position: digits.len(), digit: digit + 1 });
+                        digits.push(digit + 1);
+                        break;
+                    }
+                }
+                break;
+            }
+
+            if fraction < delta {
+                steps.push(Step::Stop { fraction, delta });
+                break;
+            }
+        }
+    }
+
+    while exponent(integer / base_f64) > 0 {
+        integer /= base_f64;
+        steps.push(Step::ZeroFill { integer });
+    }
+
+    loop {
+        let remainder = integer % base_f64;
+        integer = (integer - remainder) / base_f64;
+        steps.push(Step::IntegerDigit { digit: remainder as u8, integer });
+
+        if integer <= 0.0 {
+            break;
+        }
+    }
+
+    Ok(Explanation { steps, result })
+}
@@ -0,0 +1,110 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! The factorial number system (factoradic) for unsigned integers.
+//!
+//! The digit `i` places from the right has the place value `i!` and is at
+//! most `i`, so the rightmost digit is always `0`. Factoradic digits are the
+//! Lehmer code of a permutation, which makes them useful for indexing
+//! permutations.
+
+#[cfg(feature = "parse")]
+use crate::ParseRadixError;
+
+/// Returns the factoradic digits of the given integer, most significant first.
+/// Zero has the single digit `0`.
+///
+/// Example:
+/// ```rust
+/// use radix_ecmascript::factoradic_digits;
+///
+/// // 463 = 3*5! + 4*4! + 1*3! + 0*2! + 1*1! + 0*0!
+/// assert_eq!(factoradic_digits(463), [3, 4, 1, 0, 1, 0]);
+/// ```
+pub fn factoradic_digits(mut n: u64) -> Vec<u8> {
+    let mut digits = vec![0];
+    let mut radix = 2;
+    while n > 0 {
+        digits.push((n % radix) as u8);
+        n /= radix;
+        radix += 1;
+    }
+    digits.reverse();
+    digits
+}
+
+/// Returns the given integer in the factorial number system, with digits
+/// written as in base 36, as in `341010` for 463. Digits above 9 occur from
+/// 10! upwards.
+pub fn to_factoradic(n: u64) -> String {
+    factoradic_digits(n)
+        .iter()
+        .map(|d| char::from_digit(*d as u32, 36).unwrap())
+        .collect()
+}
+
+/// Returns the integer written in the factorial number system, as produced by
+/// [to_factoradic]. Digits are case-insensitive.
+///
+/// Returns [ParseRadixError] if the string is empty, a digit is larger than
+/// its position allows, or the value doesn't fit in a u64.
+#[cfg(feature = "parse")]
+pub fn from_factoradic(s: &str) -> Result<u64, ParseRadixError> {
+    if s.is_empty() {
+        return Err(ParseRadixError::Empty);
+    }
+
+    let len = s.chars().count();
+    let mut n: u64 = 0;
+    for (position, (i, c)) in s.char_indices().enumerate() {
+        let radix = (len - position) as u64;
+        let digit = c
+            .to_digit(36)
+            .map(u64::from)
+            .filter(|d| *d < radix)
+            .ok_or(ParseRadixError::InvalidDigit(i))?;
+        n = n
+            .checked_mul(radix)
+            .and_then(|n| n.checked_add(digit))
+            .ok_or(ParseRadixError::Overflow)?;
+    }
+    Ok(n)
+}
@@ -0,0 +1,104 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! Conversion that rejects NaN and infinities.
+
+use std::fmt::{Display, Formatter};
+use crate::{radix_str, Base, InvalidBaseError, MAX_BASE, MIN_BASE};
+
+/// An error returned by [to_radix_str_finite].
+#[derive(Debug)]
+pub enum FiniteError {
+    /// The given [Base] is out of range of [MIN_BASE] and [MAX_BASE].
+    InvalidBase(InvalidBaseError),
+    /// The value is NaN.
+    NaN,
+    /// The value is `+Infinity` or `-Infinity`.
+    Infinite,
+}
+
+impl Display for FiniteError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FiniteError::InvalidBase(e) => e.fmt(f),
+            FiniteError::NaN => f.write_str("cannot convert NaN to a finite radix string"),
+            FiniteError::Infinite => f.write_str("cannot convert an infinity to a finite radix string"),
+        }
+    }
+}
+
+impl std::error::Error for FiniteError {}
+
+impl From<InvalidBaseError> for FiniteError {
+    fn from(e: InvalidBaseError) -> Self {
+        FiniteError::InvalidBase(e)
+    }
+}
+
+/// Returns the radix string representation of the given value in the given
+/// [Base] as [ToRadixStr::to_radix_str] does, but returns an error for NaN
+/// and infinities instead of `NaN`, `Infinity` and `-Infinity`, which would
+/// pass for data in some formats.
+///
+/// Returns [FiniteError::InvalidBase] if the given [Base] is out of range of
+/// [MIN_BASE] and [MAX_BASE] (inclusive).
+///
+/// Example:
+/// ```rust
+/// use radix_ecmascript::{to_radix_str_finite, FiniteError};
+///
+/// assert_eq!(to_radix_str_finite(255.5, 16).unwrap(), "ff.8");
+/// assert!(matches!(to_radix_str_finite(f64::INFINITY, 16), Err(FiniteError::Infinite)));
+/// ```
+pub fn to_radix_str_finite(value: impl Into<f64>, base: Base) -> Result<String, FiniteError> {
+    if !(MIN_BASE..=MAX_BASE).contains(&base) {
+        return Err(InvalidBaseError(base).into());
+    }
+
+    let value = value.into();
+    if value.is_nan() {
+        return Err(FiniteError::NaN);
+    }
+    if value.is_infinite() {
+        return Err(FiniteError::Infinite);
+    }
+    Ok(radix_str(value, base)?)
+}
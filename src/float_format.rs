@@ -0,0 +1,271 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+
+//! Radix strings of custom binary floating-point formats.
+//!
+//! The conversion only needs two things from a format: the exact value, and
+//! how far apart neighbouring values are around it, which decides how many
+//! fraction digits are needed to tell them apart. Both follow from the bit
+//! layout described by [FloatFormat].
+
+use crate::double::{DENORMAL_EXPONENT, PHYSICAL_SIGNIFICAND_SIZE, SIGNIFICAND_MASK};
+#[cfg(feature = "std")]
+use crate::{pool, write_radix_format, Base, InvalidBaseError, ToRadixStr, MAX_BASE, MIN_BASE};
+
+/// An IEEE 754-style binary floating-point format.
+///
+/// Values are a sign bit, followed by [EXPONENT_BITS](Self::EXPONENT_BITS)
+/// biased exponent bits, followed by [SIGNIFICAND_BITS](Self::SIGNIFICAND_BITS)
+/// significand bits with an implicit leading 1 for normal values. An exponent
+/// field of zero encodes zeros and subnormals, and an all-ones exponent field
+/// encodes infinities (with a zero significand) and NaNs.
+///
+/// Every value of the format must be exactly representable as an f64, so
+/// [SIGNIFICAND_BITS](Self::SIGNIFICAND_BITS) is at most 52 and the exponent
+/// range is within f64's. Formats that don't fit fail to compile when
+/// converted.
+pub trait FloatFormat: Copy {
+    /// Number of exponent bits.
+    const EXPONENT_BITS: u32;
+    /// Number of stored significand bits, excluding the implicit leading bit.
+    const SIGNIFICAND_BITS: u32;
+    /// The bias subtracted from the exponent field. Defaults to IEEE 754's
+    /// `2^(EXPONENT_BITS - 1) - 1`.
+    const EXPONENT_BIAS: i32 = (1 << (Self::EXPONENT_BITS - 1)) - 1;
+
+    /// Returns the bit pattern of self, in the low
+    /// `1 + EXPONENT_BITS + SIGNIFICAND_BITS` bits.
+    fn bits(self) -> u64;
+}
+
+#[cfg(feature = "f64")]
+impl FloatFormat for f64 {
+    const EXPONENT_BITS: u32 = 11;
+    const SIGNIFICAND_BITS: u32 = 52;
+
+    fn bits(self) -> u64 {
+        self.to_bits()
+    }
+}
+
+#[cfg(feature = "f32")]
+impl FloatFormat for f32 {
+    const EXPONENT_BITS: u32 = 8;
+    const SIGNIFICAND_BITS: u32 = 23;
+
+    fn bits(self) -> u64 {
+        self.to_bits() as u64
+    }
+}
+
+/// A value of the IEEE 754-style format with the given number of exponent
+/// and significand bits, stored as its bit pattern.
+///
+/// Example:
+/// ```rust
+/// # #[cfg(feature = "std")] {
+/// use radix_ecmascript::{Bfloat16, ToRadixStr};
+///
+/// // 0.1 rounded to bfloat16 is 0.10009765625.
+/// assert_eq!(Bfloat16::from_bits(0x3DCD).to_radix_str(2).unwrap(), "0.00011001101");
+/// assert_eq!(Bfloat16::from_bits(0x3DCD).to_radix_str(10).unwrap(), "0.1");
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IeeeFloat<const EXPONENT_BITS: u32, const SIGNIFICAND_BITS: u32>(u64);
+
+impl<const EXPONENT_BITS: u32, const SIGNIFICAND_BITS: u32> IeeeFloat<EXPONENT_BITS, SIGNIFICAND_BITS> {
+    /// Creates a value from its bit pattern. Bits above the format's width
+    /// are ignored.
+    pub const fn from_bits(bits: u64) -> Self {
+        let width = 1 + EXPONENT_BITS + SIGNIFICAND_BITS;
+        Self(bits & (u64::MAX >> 64u32.saturating_sub(width)))
+    }
+
+    /// Returns the bit pattern.
+    pub const fn to_bits(self) -> u64 {
+        self.0
+    }
+}
+
+impl<const EXPONENT_BITS: u32, const SIGNIFICAND_BITS: u32> FloatFormat for IeeeFloat<EXPONENT_BITS, SIGNIFICAND_BITS> {
+    const EXPONENT_BITS: u32 = EXPONENT_BITS;
+    const SIGNIFICAND_BITS: u32 = SIGNIFICAND_BITS;
+
+    fn bits(self) -> u64 {
+        self.0
+    }
+}
+
+#[cfg(feature = "std")]
+impl<const EXPONENT_BITS: u32, const SIGNIFICAND_BITS: u32> ToRadixStr for IeeeFloat<EXPONENT_BITS, SIGNIFICAND_BITS> {
+    fn to_radix_str(self, base: Base) -> Result<String, InvalidBaseError> {
+        to_radix_str_format(self, base)
+    }
+}
+
+/// IEEE 754 binary16, also known as half precision.
+pub type Binary16 = IeeeFloat<5, 10>;
+/// bfloat16, the upper half of an f32.
+pub type Bfloat16 = IeeeFloat<8, 7>;
+/// The 8-bit E5M2 format, the upper half of a [Binary16].
+pub type Fp8E5M2 = IeeeFloat<5, 2>;
+/// IEEE 754 binary64, the format of f64, which conversions run on with or
+/// without the `f64` feature.
+pub(crate) type Binary64 = IeeeFloat<11, 52>;
+
+/// Returns the radix string representation of value, with only as many
+/// fraction digits as its own format needs to be told apart from its
+/// neighbours.
+///
+/// For f64 this is the same as [to_radix_str](ToRadixStr::to_radix_str),
+/// but for f32 the digits are those of the f32 rather than of the f64 it
+/// widens to.
+///
+/// Example:
+/// ```rust
+/// # #[cfg(feature = "f32")] {
+/// use radix_ecmascript::{to_radix_str_format, ToRadixStr};
+///
+/// assert_eq!(to_radix_str_format(0.1f32, 36).unwrap(), "0.3llllp");
+/// assert_eq!(0.1f32.to_radix_str(36).unwrap(), "0.3llllouddq8");
+/// # }
+/// ```
+///
+/// Returns [InvalidBaseError] if the given [Base] is out of range of
+/// [MIN_BASE] and [MAX_BASE] (inclusive).
+#[cfg(feature = "std")]
+pub fn to_radix_str_format<F: FloatFormat>(value: F, base: Base) -> Result<String, InvalidBaseError> {
+    if !(MIN_BASE..=MAX_BASE).contains(&base) {
+        return Err(InvalidBaseError(base));
+    }
+
+    Ok(pool::with_buffer(|buf| {
+        let range = write_radix_format(value, base, buf);
+        buf[range].iter().map(|c| *c as char).collect()
+    }))
+}
+
+/// The fields of a value: its sign, biased exponent and stored significand.
+#[inline(always)]
+fn fields<F: FloatFormat>(value: F) -> (bool, u64, u64) {
+    const {
+        assert!(F::SIGNIFICAND_BITS <= 52, "the format's significand is wider than f64's");
+        assert!(F::EXPONENT_BITS >= 1 && 1 + F::EXPONENT_BITS + F::SIGNIFICAND_BITS <= 64, "the format doesn't fit in 64 bits");
+        assert!(1 - F::EXPONENT_BIAS - F::SIGNIFICAND_BITS as i32 >= -1074, "the format has values below f64's");
+        assert!((1 << F::EXPONENT_BITS) - 2 - F::EXPONENT_BIAS <= 1023, "the format has values above f64's");
+    }
+
+    let bits = value.bits();
+    let significand = bits & ((1 << F::SIGNIFICAND_BITS) - 1);
+    let exponent = (bits >> F::SIGNIFICAND_BITS) & ((1 << F::EXPONENT_BITS) - 1);
+    let negative = (bits >> (F::SIGNIFICAND_BITS + F::EXPONENT_BITS)) & 1 == 1;
+    (negative, exponent, significand)
+}
+
+/// Returns the exponent of the least significant significand bit of a finite
+/// value with the given biased exponent.
+#[inline(always)]
+fn ulp_exponent<F: FloatFormat>(biased_exponent: u64) -> i32 {
+    // Subnormals share the exponent of the smallest normals.
+    biased_exponent.max(1) as i32 - F::EXPONENT_BIAS - F::SIGNIFICAND_BITS as i32
+}
+
+/// Bit pattern of +Infinity.
+const INFINITY: u64 = 0x7FF0_0000_0000_0000;
+/// Bit pattern of the quiet NaN.
+const NAN: u64 = 0x7FF8_0000_0000_0000;
+
+/// Returns 2^exponent, which must be within the range of f64.
+#[inline(always)]
+pub(crate) fn pow2(exponent: i32) -> f64 {
+    if exponent >= -1022 {
+        f64::from_bits(((exponent + 1023) as u64) << 52)
+    } else {
+        f64::from_bits(1u64.wrapping_shl((exponent + 1074) as u32))
+    }
+}
+
+/// Returns value as an f64, which is exact. Only integer operations are
+/// used, so subnormals survive on targets that flush them.
+#[inline(always)]
+pub(crate) fn to_f64<F: FloatFormat>(value: F) -> f64 {
+    let (negative, exponent, significand) = fields(value);
+    let sign = (negative as u64) << 63;
+    if exponent == (1 << F::EXPONENT_BITS) - 1 {
+        return f64::from_bits(sign | if significand == 0 { INFINITY } else { NAN });
+    }
+
+    // The value is m * 2^e. Move the leading bit of m to f64's hidden bit.
+    let mut m = if exponent == 0 { significand } else { significand | 1 << F::SIGNIFICAND_BITS };
+    if m == 0 {
+        return f64::from_bits(sign);
+    }
+    let shift = m.leading_zeros() - (63 - PHYSICAL_SIGNIFICAND_SIZE as u32);
+    m <<= shift;
+    let e = ulp_exponent::<F>(exponent) - shift as i32;
+
+    let bits = if e >= DENORMAL_EXPONENT {
+        ((e - DENORMAL_EXPONENT + 1) as u64) << PHYSICAL_SIGNIFICAND_SIZE | (m & SIGNIFICAND_MASK)
+    } else {
+        // The format fits in f64, so no set bits are shifted out.
+        m.wrapping_shr((DENORMAL_EXPONENT - e) as u32)
+    };
+    f64::from_bits(sign | bits)
+}
+
+/// Returns half the distance from a finite value to the next larger value in
+/// its format, but at least the smallest positive value of the format.
+/// Fraction digits stop once the remainder is below this.
+#[inline(always)]
+pub(crate) fn delta<F: FloatFormat>(value: F) -> f64 {
+    let (_, exponent, _) = fields(value);
+    let ulp = ulp_exponent::<F>(exponent);
+    pow2((ulp - 1).max(ulp_exponent::<F>(0)))
+}
+
+/// Returns [delta] for an f64.
+#[cfg(feature = "std")]
+#[inline(always)]
+pub(crate) fn delta_f64(value: f64) -> f64 {
+    delta(Binary64::from_bits(value.to_bits()))
+}
@@ -0,0 +1,349 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! Configurable formatting of radix strings.
+
+use crate::{write_radix, Base, InvalidBaseError, MAX_BASE, MIN_BASE};
+#[cfg(feature = "parse")]
+use crate::{ParseRadixError, double::from_parts, parse::{parse_special, split_digits_with}};
+use crate::double::mantissa;
+use crate::pool::with_buffer;
+
+/// Options for formatting values as radix strings.
+///
+/// The default options produce the same output as
+/// [ToRadixStr::to_radix_str](crate::ToRadixStr::to_radix_str).
+///
+/// Example:
+/// ```rust
+/// use radix_ecmascript::RadixFormat;
+///
+/// let format = RadixFormat::new(16).unwrap().always_fraction(true);
+/// assert_eq!(format.format(26.0), "1a.0");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RadixFormat {
+    base: Base,
+    always_fraction: bool,
+    leading_zero: bool,
+    radix_point: char,
+    grouping: Option<(usize, char)>,
+    truncation: Option<(usize, &'static str)>,
+    nan_payload: bool,
+    #[cfg(feature = "intl")]
+    group_separators: &'static str,
+}
+
+impl RadixFormat {
+    /// Returns the default options for the given [Base].
+    ///
+    /// Returns [InvalidBaseError] if the given [Base] is out of range of
+    /// [MIN_BASE] and [MAX_BASE] (inclusive).
+    pub fn new(base: Base) -> Result<Self, InvalidBaseError> {
+        if !(MIN_BASE..=MAX_BASE).contains(&base) {
+            return Err(InvalidBaseError(base));
+        }
+
+        Ok(Self {
+            base,
+            always_fraction: false,
+            leading_zero: true,
+            radix_point: '.',
+            grouping: None,
+            truncation: None,
+            nan_payload: false,
+            #[cfg(feature = "intl")]
+            group_separators: "",
+        })
+    }
+
+    /// Returns the [Base] values are formatted in.
+    pub fn base(&self) -> Base {
+        self.base
+    }
+
+    /// Sets whether integral values get a `.0` fraction part, so the radix
+    /// point always distinguishes floats from integers. Defaults to false.
+    pub fn always_fraction(mut self, enabled: bool) -> Self {
+        self.always_fraction = enabled;
+        self
+    }
+
+    /// Sets whether a zero is written before the radix point of values less
+    /// than one, as in `0.8` rather than `.8`. Defaults to true.
+    ///
+    /// When disabled, [RadixFormat::parse] also accepts a radix point with no
+    /// digits before it.
+    pub fn leading_zero(mut self, enabled: bool) -> Self {
+        self.leading_zero = enabled;
+        self
+    }
+
+    /// Sets the character written between the integer and fractional digits,
+    /// such as `,` for locales that use a decimal comma. Defaults to `.`.
+    ///
+    /// [RadixFormat::parse] expects the same character. It should not be a
+    /// sign or a valid digit in the [Base], or parsing becomes ambiguous.
+    pub fn radix_point(mut self, radix_point: char) -> Self {
+        self.radix_point = radix_point;
+        self
+    }
+
+    /// Sets the number of digits per group and the separator written between
+    /// groups, counted outwards from the radix point, as in `1010_1010.1100`.
+    /// A size of zero disables grouping, which is the default.
+    ///
+    /// Grouping is meant for reading bit patterns, not locale formatting.
+    /// [RadixFormat::parse] only accepts grouped digits when the separator is
+    /// also set with `group_separators`, which needs the `intl` feature.
+    pub fn group(mut self, size: usize, separator: char) -> Self {
+        self.grouping = if size == 0 { None } else { Some((size, separator)) };
+        self
+    }
+
+    /// Caps the output at the given number of characters, replacing the
+    /// digits that don't fit with the given marker, as in `0.1f7c…`. Disabled
+    /// by default.
+    ///
+    /// The marker counts towards the cap. `NaN` and `Infinity` are never
+    /// truncated.
+    pub fn truncate(mut self, max_len: usize, marker: &'static str) -> Self {
+        self.truncation = Some((max_len, marker));
+        self
+    }
+
+    /// Sets whether `NaN` is written with its sign and payload, the 52 bits
+    /// below the exponent in hexadecimal, as in `-NaN(0x8000000000001)`.
+    /// Defaults to false.
+    ///
+    /// When enabled, [RadixFormat::parse] restores the exact bit pattern from
+    /// this form.
+    pub fn nan_payload(mut self, enabled: bool) -> Self {
+        self.nan_payload = enabled;
+        self
+    }
+
+    /// Sets the characters [RadixFormat::parse] accepts as group separators in
+    /// the integer part, such as `".'\u{a0}"` for numbers typed into localized
+    /// UIs, as in `1.234,5` with a `,` [RadixFormat::radix_point]. Defaults to
+    /// none.
+    ///
+    /// A separator is only skipped between two digits, so `1,,2` and `,1` are
+    /// still malformed. The radix point takes precedence over a separator
+    /// using the same character. Formatting is unaffected; see
+    /// [RadixFormat::group].
+    #[cfg(feature = "intl")]
+    pub fn group_separators(mut self, separators: &'static str) -> Self {
+        self.group_separators = separators;
+        self
+    }
+
+    /// Returns the radix string representation of the given value.
+    pub fn format(&self, value: impl Into<f64>) -> String {
+        self.format_truncated(value).0
+    }
+
+    /// Returns the radix string representation of the given value and the
+    /// number of digits dropped to fit the length cap set by
+    /// [RadixFormat::truncate].
+    pub fn format_truncated(&self, value: impl Into<f64>) -> (String, usize) {
+        let value = value.into();
+        let mut result = self.format_full(value);
+        let Some((max_len, marker)) = self.truncation else {
+            return (result, 0);
+        };
+        let Some((end, _)) = result.char_indices().nth(max_len) else {
+            return (result, 0);
+        };
+        if !value.is_finite() {
+            return (result, 0);
+        }
+
+        // Cut before the marker and drop any separator left dangling at the end.
+        let keep = max_len.saturating_sub(marker.chars().count());
+        let mut cut = result.char_indices().nth(keep).map_or(end, |(i, _)| i);
+        while let Some(c) = result[..cut].chars().next_back() {
+            if c.is_digit(self.base as u32) {
+                break;
+            }
+            cut -= c.len_utf8();
+        }
+
+        let dropped = result[cut..].chars().filter(|c| c.is_digit(self.base as u32)).count();
+        result.truncate(cut);
+        result.push_str(marker);
+        (result, dropped)
+    }
+
+    /// Returns the radix string representation of the given value, without
+    /// truncation.
+    fn format_full(&self, value: f64) -> String {
+        if self.nan_payload && value.is_nan() {
+            let sign = if value.is_sign_negative() { "-" } else { "" };
+            return format!("{}NaN(0x{:x})", sign, mantissa(value));
+        }
+
+        with_buffer(|buf| {
+            let range = write_radix(value, self.base, buf);
+            self.assemble(value, &buf[range])
+        })
+    }
+
+    /// Applies the options to the given [ToRadixStr](crate::ToRadixStr)
+    /// output for the given value.
+    fn assemble(&self, value: f64, text: &[u8]) -> String {
+        if !value.is_finite() {
+            return text.iter().map(|c| *c as char).collect();
+        }
+
+        let (negative, text) = match text.strip_prefix(b"-") {
+            Some(text) => (true, text),
+            None => (false, text),
+        };
+        let (mut integer, fraction) = match text.iter().position(|c| *c == b'.') {
+            Some(point) => (&text[..point], Some(&text[point + 1..])),
+            None => (text, None),
+        };
+        let fraction = fraction.or(if self.always_fraction { Some(b"0") } else { None });
+        if !self.leading_zero && fraction.is_some() && integer == b"0" {
+            integer = b"";
+        }
+
+        let mut result = String::with_capacity(text.len() * 2 + 2);
+        if negative {
+            result.push('-');
+        }
+        self.push_digits(&mut result, integer, integer.len());
+        if let Some(fraction) = fraction {
+            result.push(self.radix_point);
+            self.push_digits(&mut result, fraction, 0);
+        }
+        result
+    }
+
+    /// Appends the given digits, inserting the group separator wherever the
+    /// distance from the radix point at the given index is a multiple of the
+    /// group size.
+    fn push_digits(&self, result: &mut String, digits: &[u8], point: usize) {
+        for (i, &c) in digits.iter().enumerate() {
+            if let Some((size, separator)) = self.grouping {
+                if i > 0 && point.abs_diff(i).is_multiple_of(size) {
+                    result.push(separator);
+                }
+            }
+            result.push(c as char);
+        }
+    }
+
+    /// Parses a radix string written with these options.
+    ///
+    /// Accepts an optional sign (`-` or `+`) followed by `Infinity`, or
+    /// case-insensitive digits with an optional radix point followed by more
+    /// digits, adjusted for the configured options. `NaN` is accepted without
+    /// a sign.
+    ///
+    /// Returns [ParseRadixError] if the string is malformed.
+    #[cfg(feature = "parse")]
+    pub fn parse(&self, s: &str) -> Result<f64, ParseRadixError> {
+        if self.nan_payload {
+            if let Some(value) = parse_nan_payload(s) {
+                return Ok(value);
+            }
+        }
+        if let Some(value) = parse_special(s) {
+            return Ok(value);
+        }
+
+        #[cfg(feature = "intl")]
+        if !self.group_separators.is_empty() {
+            let (s, offsets) = self.strip_group_separators(s);
+            return split_digits_with(&s, self.base, self.radix_point, self.leading_zero)
+                .map(|digits| digits.to_f64(self.base))
+                .map_err(|e| match e {
+                    ParseRadixError::InvalidDigit(i) => ParseRadixError::InvalidDigit(offsets[i]),
+                    e => e,
+                });
+        }
+
+        Ok(split_digits_with(s, self.base, self.radix_point, self.leading_zero)?.to_f64(self.base))
+    }
+
+    /// Removes the group separators between integer digits from the given
+    /// string, returning the result and the index in the given string of each
+    /// byte of the result, plus its length.
+    #[cfg(all(feature = "intl", feature = "parse"))]
+    fn strip_group_separators(&self, s: &str) -> (String, Vec<usize>) {
+        let is_digit = |c: Option<char>| c.is_some_and(|c| c.is_digit(self.base as u32));
+        let mut result = String::with_capacity(s.len());
+        let mut offsets = Vec::with_capacity(s.len() + 1);
+        let mut in_integer = true;
+        let mut previous = None;
+        for (i, c) in s.char_indices() {
+            let next = s[i + c.len_utf8()..].chars().next();
+            if c == self.radix_point {
+                in_integer = false;
+            } else if in_integer && self.group_separators.contains(c) && is_digit(previous) && is_digit(next) {
+                previous = Some(c);
+                continue;
+            }
+
+            result.push(c);
+            offsets.extend(std::iter::repeat_n(i, c.len_utf8()));
+            previous = Some(c);
+        }
+        offsets.push(s.len());
+        (result, offsets)
+    }
+}
+
+/// Parses `NaN` with a sign and payload, as written with
+/// [RadixFormat::nan_payload]. Returns [None] if the string isn't in that form
+/// or the payload isn't a NaN payload.
+#[cfg(feature = "parse")]
+fn parse_nan_payload(s: &str) -> Option<f64> {
+    let (sign, s) = match s.strip_prefix('-') {
+        Some(s) => (1, s),
+        None => (0, s),
+    };
+    let payload = s.strip_prefix("NaN(0x")?.strip_suffix(')')?;
+    let mantissa = u64::from_str_radix(payload, 16).ok().filter(|m| *m != 0)?;
+    from_parts(sign, 0x7FF, mantissa)
+}
@@ -0,0 +1,141 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! Short IDs: unsigned integers as compact radix strings.
+//!
+//! These cover the common `(id).toString(36)` pattern with exact integer
+//! semantics, and extend it to bases up to 62 using `0-9`, `a-z` and then
+//! `A-Z` as digits.
+
+use crate::{Base, InvalidBaseError, MIN_BASE};
+#[cfg(feature = "parse")]
+use crate::{lut::{DIGIT_VALUES, ID_DIGIT_VALUES}, ParseRadixError, MAX_BASE};
+
+/// The maximum [Base] that can be passed into the ID functions.
+pub const MAX_ID_BASE: Base = 62;
+
+/// The maximum length of an ID, which is that of [u64::MAX] in base 2.
+pub const ID_MAX_LEN: usize = 64;
+
+/// Digits of bases up to [MAX_ID_BASE], in order.
+const ID_DIGITS: &[u8; MAX_ID_BASE as usize] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Returns the given ID in base 36, as `(id).toString(36)` does for integers.
+///
+/// Example:
+/// ```rust
+/// use radix_ecmascript::encode_id;
+///
+/// assert_eq!(encode_id(1_000_000), "lfls");
+/// ```
+pub fn encode_id(id: u64) -> String {
+    encode_id_radix(id, 36).unwrap()
+}
+
+/// Returns the given ID in the given [Base].
+///
+/// Returns [InvalidBaseError] if the given [Base] is out of range of
+/// [MIN_BASE] and [MAX_ID_BASE] (inclusive).
+pub fn encode_id_radix(id: u64, base: Base) -> Result<String, InvalidBaseError> {
+    let mut buf = [0; ID_MAX_LEN];
+    Ok(write_id(id, base, &mut buf)?.to_owned())
+}
+
+/// Writes the given ID in the given [Base] into the end of buf without
+/// allocating, returning the written part.
+///
+/// Returns [InvalidBaseError] if the given [Base] is out of range of
+/// [MIN_BASE] and [MAX_ID_BASE] (inclusive).
+pub fn write_id(mut id: u64, base: Base, buf: &mut [u8; ID_MAX_LEN]) -> Result<&str, InvalidBaseError> {
+    if !(MIN_BASE..=MAX_ID_BASE).contains(&base) {
+        return Err(InvalidBaseError(base));
+    }
+
+    let mut start = ID_MAX_LEN;
+    loop {
+        start -= 1;
+        buf[start] = ID_DIGITS[(id % base as u64) as usize];
+        id /= base as u64;
+        if id == 0 {
+            break;
+        }
+    }
+    Ok(std::str::from_utf8(&buf[start..]).unwrap())
+}
+
+/// Returns the ID encoded by [encode_id].
+///
+/// Digits are case-insensitive, as in `parseInt(s, 36)`. Returns
+/// [ParseRadixError] if the string is malformed or the ID doesn't fit in a u64.
+#[cfg(feature = "parse")]
+pub fn decode_id(s: &str) -> Result<u64, ParseRadixError> {
+    decode_id_radix(s, 36)
+}
+
+/// Returns the ID encoded by [encode_id_radix] in the given [Base].
+///
+/// Digits are case-insensitive in bases up to 36 and case-sensitive above.
+/// Returns [ParseRadixError] if the given [Base] is out of range of [MIN_BASE]
+/// and [MAX_ID_BASE] (inclusive), the string is malformed, or the ID doesn't
+/// fit in a u64.
+#[cfg(feature = "parse")]
+pub fn decode_id_radix(s: &str, base: Base) -> Result<u64, ParseRadixError> {
+    if !(MIN_BASE..=MAX_ID_BASE).contains(&base) {
+        return Err(InvalidBaseError(base).into());
+    }
+    if s.is_empty() {
+        return Err(ParseRadixError::Empty);
+    }
+
+    let table = if base <= MAX_BASE { &DIGIT_VALUES } else { &ID_DIGIT_VALUES };
+    let mut id: u64 = 0;
+    for (i, c) in s.bytes().enumerate() {
+        let digit = table[c as usize];
+        if digit >= base {
+            return Err(ParseRadixError::InvalidDigit(i));
+        }
+        id = id
+            .checked_mul(base as u64)
+            .and_then(|id| id.checked_add(digit as u64))
+            .ok_or(ParseRadixError::Overflow)?;
+    }
+    Ok(id)
+}
@@ -0,0 +1,163 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! JavaScript argument handling for embedders emulating `Number.prototype.toString`.
+
+use std::fmt::{Display, Formatter};
+use crate::{radix_str, Base, Compat, InvalidBaseError, MAX_BASE, MIN_BASE};
+
+/// Converts a JavaScript radix argument to a [Base] as
+/// `Number.prototype.toString` does, applying ToIntegerOrInfinity (`NaN`
+/// becomes 0 and fractions truncate towards zero) before validating it.
+///
+/// Returns [InvalidBaseError] if the coerced radix is out of range of
+/// [MIN_BASE] and [MAX_BASE] (inclusive). The error holds the coerced radix
+/// saturated to the range of [Base].
+///
+/// Example:
+/// ```rust
+/// use radix_ecmascript::coerce_radix;
+///
+/// assert_eq!(coerce_radix(16.9).unwrap(), 16);
+/// assert!(coerce_radix(f64::NAN).is_err());
+/// ```
+pub fn coerce_radix(radix: impl Into<f64>) -> Result<Base, InvalidBaseError> {
+    let radix = radix.into();
+    let integer = if radix.is_nan() { 0.0 } else { radix.trunc() };
+    if !(MIN_BASE as f64..=MAX_BASE as f64).contains(&integer) {
+        return Err(InvalidBaseError(integer.clamp(0.0, Base::MAX as f64) as Base));
+    }
+
+    Ok(integer as Base)
+}
+
+/// Returns the radix string representation of the given value as
+/// `value.toString(radix)` does in JavaScript, where a radix of [None] stands
+/// for `undefined` and means base 10.
+///
+/// See [coerce_radix] for how the radix is converted and validated.
+pub fn to_radix_str_js(value: impl Into<f64>, radix: Option<f64>) -> Result<String, InvalidBaseError> {
+    let base = match radix {
+        Some(radix) => coerce_radix(radix)?,
+        None => 10,
+    };
+
+    radix_str(value.into(), base)
+}
+
+/// Converts the given value to an unsigned 32-bit integer as the ECMAScript
+/// ToUint32 operation does: `NaN` and infinities become 0, fractions truncate
+/// towards zero, and the result wraps modulo 2^32.
+fn to_uint32(value: f64) -> u32 {
+    if !value.is_finite() {
+        return 0;
+    }
+
+    // The remainder of an f64 is exact, so this wraps without losing bits.
+    value.trunc().rem_euclid(4294967296.0) as u32
+}
+
+/// Returns the radix string representation of the given value as
+/// `(value >>> 0).toString(base)` does in JavaScript, converting it with
+/// ToUint32 first. Negative integers wrap to their unsigned two's-complement
+/// value, which is how bitmask-oriented code prints them.
+///
+/// Returns [InvalidBaseError] if the given [Base] is out of range of
+/// [MIN_BASE] and [MAX_BASE] (inclusive).
+///
+/// Example:
+/// ```rust
+/// use radix_ecmascript::to_uint32_radix_str;
+///
+/// assert_eq!(to_uint32_radix_str(-1.0, 16).unwrap(), "ffffffff");
+/// assert_eq!(to_uint32_radix_str(-255.5, 16).unwrap(), "ffffff01");
+/// ```
+pub fn to_uint32_radix_str(value: impl Into<f64>, base: Base) -> Result<String, InvalidBaseError> {
+    radix_str(to_uint32(value.into()) as f64, base)
+}
+
+/// The `RangeError` a JavaScript engine throws for an invalid radix, for
+/// comparing failures against engine output.
+///
+/// Formats as `String(error)` does in the engine, such as
+/// `RangeError: toString() radix argument must be between 2 and 36` for V8.
+///
+/// Example:
+/// ```rust
+/// use radix_ecmascript::{to_radix_str_js, RangeError};
+///
+/// let error = to_radix_str_js(255.5, Some(37.0)).map_err(RangeError::from).unwrap_err();
+/// assert_eq!(error.to_string(), "RangeError: toString() radix argument must be between 2 and 36");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct RangeError {
+    compat: Compat,
+}
+
+impl RangeError {
+    /// Returns the error thrown by the engine of the given [Compat].
+    pub fn new(compat: Compat) -> Self {
+        Self { compat }
+    }
+
+    /// Returns the error message, without the `RangeError: ` prefix.
+    pub fn message(&self) -> &'static str {
+        match self.compat {
+            Compat::V8 | Compat::V8Legacy => "toString() radix argument must be between 2 and 36",
+            Compat::SpiderMonkey => "radix must be an integer at least 2 and no greater than 36",
+        }
+    }
+}
+
+impl Display for RangeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RangeError: {}", self.message())
+    }
+}
+
+impl std::error::Error for RangeError {}
+
+/// Returns the V8 error.
+impl From<InvalidBaseError> for RangeError {
+    fn from(_: InvalidBaseError) -> Self {
+        Self::new(Compat::V8)
+    }
+}
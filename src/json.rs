@@ -0,0 +1,100 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! [ToRadixStr] and [FromRadixStr] implementations for
+//! [serde_json::Number](https://docs.rs/serde_json/latest/serde_json/struct.Number.html),
+//! enabled by the `serde_json` feature.
+
+use serde_json::Number;
+use crate::parse::parse_f64;
+use crate::{
+    decode_id_radix, radix_str, write_id, Base, FromRadixStr, InvalidBaseError, ParseRadixError, ToRadixStr, ID_MAX_LEN,
+    MAX_BASE, MIN_BASE,
+};
+
+/// Integers are written exactly, so integers beyond 2^53 have all their
+/// digits rather than the zeros `toString` fills in. Floats are converted as
+/// [ToRadixStr::to_radix_str] converts f64.
+impl ToRadixStr for Number {
+    fn to_radix_str(self, base: Base) -> Result<String, InvalidBaseError> {
+        if !(MIN_BASE..=MAX_BASE).contains(&base) {
+            return Err(InvalidBaseError(base));
+        }
+
+        let mut buf = [0; ID_MAX_LEN];
+        if let Some(n) = self.as_u64() {
+            return Ok(write_id(n, base, &mut buf)?.to_owned());
+        }
+        if let Some(n) = self.as_i64() {
+            return Ok(format!("-{}", write_id(n.unsigned_abs(), base, &mut buf)?));
+        }
+        radix_str(self.as_f64().unwrap_or(f64::NAN), base)
+    }
+}
+
+/// Parses into the widest number that holds the value losslessly: a u64 or
+/// i64 for integers in their range, and an f64 otherwise (including `-0`).
+///
+/// Returns [ParseRadixError::NaN] for `NaN` and [ParseRadixError::Overflow]
+/// for infinities, which JSON can't represent.
+impl FromRadixStr for Number {
+    fn from_radix_str(s: &str, base: Base) -> Result<Self, ParseRadixError> {
+        if !(MIN_BASE..=MAX_BASE).contains(&base) {
+            return Err(InvalidBaseError(base).into());
+        }
+
+        let (negative, digits) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+        // Anything but an integer in range of u64 or i64 is parsed as an f64,
+        // which also reports errors.
+        match decode_id_radix(digits, base) {
+            Ok(n) if !negative => return Ok(n.into()),
+            Ok(n @ 1..=0x8000_0000_0000_0000) => return Ok((n as i64).wrapping_neg().into()),
+            _ => {}
+        }
+
+        let value = parse_f64(s, base)?;
+        Number::from_f64(value).ok_or(if value.is_nan() { ParseRadixError::NaN } else { ParseRadixError::Overflow })
+    }
+}
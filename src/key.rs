@@ -39,69 +39,47 @@
  * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
  */
 
-//! Helper functions for 64-bit floats, implemented from Google's open-source V8 engine.
-//!
-//! [V8 Source Code](https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+//! Canonical string keys for floating-point values.
 
-const K_SIGN_MASK: u64 = 0x8000_0000_0000_0000;
-const K_EXPONENT_MASK: u64 = 0x7FF0_0000_0000_0000;
-const K_SIGNIFICAND_MASK: u64 = 0x000F_FFFF_FFFF_FFFF;
-const K_HIDDEN_BIT: u64 = 0x0010_0000_0000_0000;
-const K_INFINITY: u64 = 0x7FF0_0000_0000_0000;
-const K_PHYSICAL_SIGNIFICAND_SIZE: i32 = 52; // Excludes hidden bit.
-const K_EXPONENT_BIAS: i32 = 0x3FF + K_PHYSICAL_SIGNIFICAND_SIZE;
-const K_DENORMAL_EXPONENT: i32 = -K_EXPONENT_BIAS + 1;
+/// Number of base-36 digits needed to hold any u64.
+const KEY_LEN: usize = 13;
 
-/// Reports if the given floating-point bits is subnormal.
-fn is_denormal(bits: u64) -> bool {
-    bits & K_EXPONENT_MASK == 0
-}
-
-/// Gets the significand of the given floating-point bits.
-pub fn significand(bits: u64) -> u64 {
-    let significand = bits & K_SIGNIFICAND_MASK;
-
-    if is_denormal(bits) {
-        significand
+/// Returns a canonical key for the given value, for use as a map or database
+/// key where f64's lack of `Ord` and `Hash` is a problem.
+///
+/// Keys are 13 base-36 digits long and compare lexicographically in the same
+/// order as the values compare numerically. `-0` and `+0` share a key, as do
+/// all NaNs, which sort after `+Infinity`.
+pub fn canonical_key(value: f64) -> String {
+    let value = if value.is_nan() {
+        f64::NAN
+    } else if value == 0.0 {
+        0.0
     } else {
-        significand + K_HIDDEN_BIT
-    }
-}
-
-/// Reports if the given floating-point bits is positive.
-fn is_pos(bits: u64) -> bool {
-    bits & K_SIGN_MASK == 0
-}
-
-/// Returns the next greater f64.
-/// Returns +Infinity if f == +Infinity.
-pub(crate) fn next_float(f: f64) -> f64 {
-    let bits = f.to_bits();
+        value
+    };
 
-    if bits == K_INFINITY {
-        return f;
-    }
+    // Flip the sign bit of positive values and all bits of negative values so
+    // the bit patterns sort as unsigned integers.
+    let bits = value.to_bits();
+    let mut ordered = if bits >> 63 == 0 { bits | 1 << 63 } else { !bits };
 
-    let is_neg = !is_pos(bits);
-    if is_neg && significand(bits) == 0 {
-        return 0.0;
+    let mut key = [b'0'; KEY_LEN];
+    for c in key.iter_mut().rev() {
+        *c = char::from_digit((ordered % 36) as u32, 36).unwrap() as u8;
+        ordered /= 36;
     }
-
-    f64::from_bits(if is_neg {
-        bits - 1
-    } else {
-        bits + 1
-    })
+    key.iter().map(|c| *c as char).collect()
 }
 
-/// Gets the exponent of f.
-pub(crate) fn exponent(f: f64) -> i32 {
-    let bits = f.to_bits();
-
-    if is_denormal(bits) {
-        return K_DENORMAL_EXPONENT;
+/// Returns the value of the given key produced by [canonical_key], or `None`
+/// if the key is malformed.
+pub fn from_canonical_key(key: &str) -> Option<f64> {
+    if key.len() != KEY_LEN || key.bytes().any(|c| c.is_ascii_uppercase()) {
+        return None;
     }
 
-    let biased = (bits & K_EXPONENT_MASK >> K_PHYSICAL_SIGNIFICAND_SIZE) as i32;
-    biased - K_EXPONENT_BIAS
+    let ordered = u64::from_str_radix(key, 36).ok()?;
+    let bits = if ordered >> 63 == 1 { ordered & !(1 << 63) } else { !ordered };
+    Some(f64::from_bits(bits))
 }
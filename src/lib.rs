@@ -41,7 +41,7 @@
 
 //! `radix-ecmascript` adds a function, `to_radix_str`, to floating-point types (`f32` and `f64`)
 //! to allow callers to obtain their radix string representation, just like in JavaScript,
-//! in pure Rust. This library has no dependencies and is very lightweight.
+//! in pure Rust. This library has no required dependencies and is very lightweight.
 //!
 //! This library implements ECMAScript Language Specification Section 9.8.1,
 //! "ToString Applied to the Number Type", and uses the same logic as found in
@@ -52,9 +52,11 @@
 //!
 //! Example:
 //! ```rust
+//! # #[cfg(all(feature = "std", feature = "f64"))] {
 //! use radix_ecmascript::ToRadixStr;
 //!
 //! println!("{}", (0.123).to_radix_str(16).unwrap());
+//! # }
 //! ```
 //! This code prints `0.1f7ced916872b`, which can also be achieved by running
 //! `(0.123).toString(16)` in JavaScript.
@@ -63,34 +65,287 @@
 //! error in real cases. `to_radix_str` will only return `InvalidBaseError` if the
 //! given `Base` is outside of the valid range, `MIN_BASE` and `MAX_BASE`.
 
-mod f64_util;
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+// Without `std`, `small-stack` leaves the buffered conversion unused.
+#![cfg_attr(all(feature = "small-stack", not(feature = "std")), allow(dead_code))]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+#![cfg_attr(not(feature = "fast"), forbid(unsafe_code))]
+
+#[macro_use]
+mod trace;
+
+#[cfg(feature = "std")]
+mod align;
+#[cfg(feature = "std")]
+mod alphabet;
+#[cfg(feature = "allocator_api")]
+mod allocator;
+#[cfg(feature = "quickcheck")]
+pub mod arbitrary;
+#[cfg(feature = "bumpalo")]
+mod arena;
+mod array_string;
+#[cfg(feature = "parse")]
+mod arith;
+#[cfg(feature = "futures")]
+mod async_stream;
+#[cfg(feature = "std")]
+pub mod bignum;
+#[cfg(feature = "std")]
+mod bijective;
+#[cfg(all(feature = "std", any(feature = "f32", feature = "f64")))]
+mod bits;
+mod bounds;
+#[cfg(feature = "ecma-methods")]
+mod codegen;
+#[cfg(feature = "nom")]
+pub mod combinators;
+#[cfg(feature = "std")]
+mod compat;
+pub mod consts;
+#[cfg(feature = "num-complex")]
+mod complex;
+#[cfg(feature = "corpus")]
+pub mod corpus;
+#[cfg(feature = "std")]
+mod continued_fraction;
+#[cfg(feature = "std")]
+mod digits;
+pub mod double;
+#[cfg(feature = "std")]
+mod exact;
+#[cfg(feature = "std")]
+mod explain;
+#[cfg(feature = "std")]
+mod factoradic;
+#[cfg(feature = "std")]
+mod finite;
+#[cfg(feature = "heapless")]
+mod fixed;
+mod float_format;
+#[cfg(feature = "format")]
+mod format;
+#[cfg(feature = "std")]
+mod id;
+#[cfg(feature = "ecma-methods")]
+mod js;
+#[cfg(feature = "serde_json")]
+mod json;
+#[cfg(feature = "std")]
+mod key;
+#[cfg(feature = "std")]
+mod list;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+#[cfg(feature = "ecma-methods")]
+mod literal;
+#[cfg(feature = "parse")]
+mod lut;
+#[cfg(feature = "std")]
+mod normalize;
+#[cfg(feature = "parse")]
+mod obfuscate;
+#[cfg(all(feature = "ordered-float", any(feature = "f32", feature = "f64")))]
+mod ordered;
+#[cfg(feature = "parse")]
+mod parse;
+#[cfg(feature = "std")]
+mod pool;
+#[cfg(feature = "softposit")]
+mod posit;
+#[cfg(feature = "std")]
+pub mod prelude;
+#[cfg(kani)]
+mod proofs;
+#[cfg(feature = "std")]
+mod radix;
+#[cfg(feature = "std")]
+mod radix_string;
+#[cfg(feature = "simd")]
+mod simd;
+mod softfloat;
+#[cfg(feature = "std")]
+mod spidermonkey;
+mod stream;
+#[cfg(feature = "sqlx")]
+mod sql;
+#[cfg(test)]
 mod tests;
-
-use std::fmt::{Display, Formatter};
+#[cfg(feature = "primitive-types")]
+mod u256;
+#[cfg(feature = "std")]
+mod utf16;
+mod write;
+
+use core::fmt::{Display, Formatter};
+use core::ops::{Range, RangeInclusive};
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+
+#[cfg(feature = "std")]
+pub use align::{align_radix_column, AlignedColumn};
+#[cfg(feature = "std")]
+pub use alphabet::{char_to_digit, digit_to_char, Alphabet, Case};
+#[cfg(feature = "allocator_api")]
+pub use allocator::to_radix_vec_in;
+#[cfg(feature = "bumpalo")]
+pub use arena::to_radix_str_bump;
+pub use array_string::RadixArrayString;
+#[cfg(feature = "parse")]
+pub use arith::{add_radix_str, rebase_radix_str, sub_radix_str};
+#[cfg(feature = "futures")]
+pub use async_stream::RadixStream;
+#[cfg(feature = "std")]
+pub use bijective::{to_bijective, SPREADSHEET_ALPHABET};
+#[cfg(feature = "parse")]
+pub use bijective::from_bijective;
+#[cfg(all(feature = "std", feature = "f64"))]
+pub use bits::bits_to_radix_str;
+#[cfg(all(feature = "std", feature = "f32"))]
+pub use bits::bits_to_radix_str_f32;
+#[cfg(all(feature = "parse", feature = "f64"))]
+pub use bits::bits_from_radix_str;
+#[cfg(all(feature = "parse", feature = "f32"))]
+pub use bits::bits_from_radix_str_f32;
+pub use bounds::MAX_LEN;
+#[cfg(feature = "f64")]
+pub use bounds::max_len;
+#[cfg(feature = "f32")]
+pub use bounds::max_len_f32;
+#[cfg(feature = "ecma-methods")]
+pub use codegen::{DefaultToString, NumberPrinter, ShortestLiteral};
+#[cfg(feature = "std")]
+pub use compat::{to_radix_str_compat, Compat};
+#[cfg(feature = "num-complex")]
+pub use complex::complex_to_radix_str;
+#[cfg(feature = "std")]
+pub use continued_fraction::{continued_fraction, ContinuedFraction};
+#[cfg(feature = "std")]
+pub use digits::{for_each_digit, ilog_radix, integer_digit_count, to_radix_digits, DigitEvent, RadixDigits};
+#[cfg(feature = "std")]
+pub use exact::{
+    exact_fraction_digit_count, exact_fraction_digits, is_exact_in_base, quantize, to_exact_decimal, to_radix_interval,
+    to_scaled_integer, ExactFractionDigits,
+};
+#[cfg(feature = "std")]
+pub use explain::{explain_radix_str, Explanation, Step};
+#[cfg(feature = "std")]
+pub use factoradic::{factoradic_digits, to_factoradic};
+#[cfg(feature = "parse")]
+pub use factoradic::from_factoradic;
+#[cfg(feature = "std")]
+pub use finite::{to_radix_str_finite, FiniteError};
+#[cfg(feature = "heapless")]
+pub use fixed::to_radix_heapless;
+pub use float_format::{Bfloat16, Binary16, FloatFormat, Fp8E5M2, IeeeFloat};
+#[cfg(feature = "std")]
+pub use float_format::to_radix_str_format;
+#[cfg(feature = "format")]
+pub use format::RadixFormat;
+#[cfg(feature = "std")]
+pub use id::{encode_id, encode_id_radix, write_id, ID_MAX_LEN, MAX_ID_BASE};
+#[cfg(feature = "parse")]
+pub use id::{decode_id, decode_id_radix};
+#[cfg(feature = "ecma-methods")]
+pub use js::{coerce_radix, to_radix_str_js, to_uint32_radix_str, RangeError};
+#[cfg(feature = "std")]
+pub use key::{canonical_key, from_canonical_key};
+#[cfg(feature = "std")]
+pub use list::RadixList;
+#[cfg(feature = "ecma-methods")]
+pub use literal::shortest_js_literal;
+#[cfg(all(feature = "ecma-methods", feature = "parse"))]
+pub use literal::{parse_json5_number, scan_js_literal, scan_js_numeric_literal, JsLiteral};
+#[cfg(feature = "std")]
+pub use normalize::{normalize_radix_str, NegativeZero};
+#[cfg(feature = "parse")]
+pub use obfuscate::Obfuscator;
+#[cfg(feature = "parse")]
+pub use parse::{from_repeating_radix_str, parse_radix_partial, scan_radix_literal, FromRadixStr, ParseRadixError};
+#[cfg(feature = "buffer-pool")]
+pub use pool::set_buffer_pool_enabled;
+#[cfg(feature = "std")]
+pub use radix::{Hex, Radix};
+#[cfg(feature = "std")]
+pub use radix_string::RadixString;
+#[cfg(feature = "rkyv")]
+pub use radix_string::ArchivedRadixString;
+#[cfg(feature = "std")]
+pub use softfloat::to_radix_str_deterministic;
+pub use stream::write_radix_streaming;
+#[cfg(feature = "std")]
+pub use utf16::{to_radix_utf16, try_write_utf16};
+pub use write::{try_write, WriteError};
+#[cfg(feature = "std")]
+pub use write::extend_radix;
 
 /// A floating-point base.
 pub type Base = u8;
 
+/// A [Result](core::result::Result) whose error defaults to [InvalidBaseError],
+/// the error of most conversions in this crate.
+pub type Result<T, E = InvalidBaseError> = core::result::Result<T, E>;
+
 /// The minimum [Base] that can be passed into [ToRadixStr::to_radix_str].
 pub const MIN_BASE: Base = 2;
 
 /// The maximum [Base] that can be passed into [ToRadixStr::to_radix_str].
 pub const MAX_BASE: Base = 36;
 
+/// Validation and iteration of supported bases, called on the [Base] type,
+/// such as `Base::is_valid(16)` and `Base::ALL`.
+///
+/// The bounds are named `MIN_RADIX` and `MAX_RADIX` because [Base] is [u8],
+/// whose own `MIN` and `MAX` would take precedence.
+///
+/// Example:
+/// ```rust
+/// use radix_ecmascript::{Base, BaseExt};
+///
+/// assert!(Base::is_valid(36));
+/// assert!(!Base::is_valid(37));
+/// assert_eq!(Base::ALL.count(), 35);
+/// assert_eq!(Base::ALL.last(), Some(Base::MAX_RADIX));
+/// ```
+pub trait BaseExt: Sized {
+    /// The minimum base, [MIN_BASE].
+    const MIN_RADIX: Self;
+    /// The maximum base, [MAX_BASE].
+    const MAX_RADIX: Self;
+    /// Every supported base in ascending order, [MIN_BASE] to [MAX_BASE]
+    /// (inclusive).
+    const ALL: RangeInclusive<Self>;
+
+    /// Reports if the given base is in range of [MIN_BASE] and [MAX_BASE]
+    /// (inclusive), that is, if it won't cause an [InvalidBaseError].
+    fn is_valid(base: Self) -> bool;
+}
+
+impl BaseExt for Base {
+    const MIN_RADIX: Base = MIN_BASE;
+    const MAX_RADIX: Base = MAX_BASE;
+    const ALL: RangeInclusive<Base> = MIN_BASE..=MAX_BASE;
+
+    fn is_valid(base: Base) -> bool {
+        Self::ALL.contains(&base)
+    }
+}
+
 /// An error indicating that a given [Base] value is out of range of
 /// [MIN_BASE] and [MAX_BASE].
 #[derive(Debug)]
 pub struct InvalidBaseError(Base);
 
 impl Display for InvalidBaseError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "invalid base: {}", self.0)
     }
 }
 
-impl std::error::Error for InvalidBaseError {}
+impl core::error::Error for InvalidBaseError {}
 
 /// Allows a type to be converted to radix string representation.
+#[cfg(feature = "std")]
 pub trait ToRadixStr: Sized {
     /// Returns the radix string representation of self using the functionality
     /// as defined in the ECMAScript Language Specification Section 9.8.1
@@ -101,154 +356,355 @@ pub trait ToRadixStr: Sized {
     fn to_radix_str(self, base: Base) -> Result<String, InvalidBaseError>;
 }
 
+#[cfg(all(feature = "std", feature = "f64"))]
 impl ToRadixStr for f64 {
     fn to_radix_str(self, base: Base) -> Result<String, InvalidBaseError> {
-        use crate::f64_util::{exponent, next_float};
+        radix_str(self, base)
+    }
+}
 
-        // Validate base
-        if !(MIN_BASE..=MAX_BASE).contains(&base) {
-            return Err(InvalidBaseError(base));
-        }
+#[cfg(all(feature = "std", feature = "f32"))]
+impl ToRadixStr for f32 {
+    fn to_radix_str(self, base: Base) -> Result<String, InvalidBaseError> {
+        radix_str(self as f64, base)
+    }
+}
 
-        // The result is always "NaN" if self is NaN.
-        if self.is_nan() {
-            return Ok("NaN".into());
-        }
+/// Returns the radix string representation of the given value, as
+/// [to_radix_str](ToRadixStr::to_radix_str) does for f64.
+#[cfg(feature = "std")]
+pub(crate) fn radix_str(value: f64, base: Base) -> Result<String, InvalidBaseError> {
+    // Validate base
+    if !(MIN_BASE..=MAX_BASE).contains(&base) {
+        return Err(InvalidBaseError(base));
+    }
 
-        // If self is +0 or -0, return "0".
-        if self == 0.0 {
-            return Ok("0".into());
-        }
+    Ok(pool::with_buffer(|buf| {
+        let range = write_radix(value, base, buf);
+        buf[range].iter().map(|c| *c as char).collect()
+    }))
+}
+
+/// Returns the radix string representation of the given value in the given
+/// `BASE`, which is checked at compile time, so no error can occur.
+///
+/// Example:
+/// ```rust
+/// use radix_ecmascript::to_radix_str_base;
+///
+/// assert_eq!(to_radix_str_base::<16>(0.123), "0.1f7ced916872b");
+/// ```
+#[cfg(feature = "std")]
+pub fn to_radix_str_base<const BASE: Base>(value: impl Into<f64>) -> String {
+    const { assert!(MIN_BASE <= BASE && BASE <= MAX_BASE, "invalid base") };
+
+    let value = value.into();
+    pool::with_buffer(|buf| {
+        let range = write_radix_const::<BASE>(value, float_format::delta_f64(value), buf);
+        buf[range].iter().map(|c| *c as char).collect()
+    })
+}
+
+/// Returns the radix string representation of the given value in the given
+/// [Base], as [ToRadixStr::to_radix_str] does, borrowing the constant results
+/// `NaN`, `Infinity`, `-Infinity` and `0` rather than allocating them.
+///
+/// Returns [InvalidBaseError] if the given [Base] is out of range of
+/// [MIN_BASE] and [MAX_BASE] (inclusive).
+///
+/// Example:
+/// ```rust
+/// use std::borrow::Cow;
+/// use radix_ecmascript::to_radix_cow;
+///
+/// assert!(matches!(to_radix_cow(f64::NAN, 16).unwrap(), Cow::Borrowed("NaN")));
+/// assert!(matches!(to_radix_cow(255.5, 16).unwrap(), Cow::Owned(s) if s == "ff.8"));
+/// ```
+#[cfg(feature = "std")]
+pub fn to_radix_cow(value: impl Into<f64>, base: Base) -> Result<Cow<'static, str>, InvalidBaseError> {
+    if !(MIN_BASE..=MAX_BASE).contains(&base) {
+        return Err(InvalidBaseError(base));
+    }
+
+    let value = value.into();
+    Ok(if value.is_nan() {
+        Cow::Borrowed("NaN")
+    } else if value == 0.0 {
+        Cow::Borrowed("0")
+    } else if value == f64::INFINITY {
+        Cow::Borrowed("Infinity")
+    } else if value == f64::NEG_INFINITY {
+        Cow::Borrowed("-Infinity")
+    } else {
+        Cow::Owned(radix_str(value, base)?)
+    })
+}
+
+/// Length of the buffer used for conversion.
+///
+/// We start at the radix point and write to the left for the integer part and
+/// to the right for the fractional part. The radix point goes just past the
+/// room the integer part can need (see [radix_point_position]), so the buffer
+/// only needs to hold the longest radix string, [MAX_LEN], with a little
+/// slack. Keeping it small matters on targets with tiny stacks, and every
+/// index fits in a 16-bit usize.
+pub(crate) const BUFFER_LEN: usize = MAX_LEN + 8;
+
+/// Returns where the radix point goes in the conversion buffer for the given
+/// finite value in the given [Base]: past the sign and the most integer digits
+/// the value can have, including a carry from the fraction part.
+#[inline]
+pub(crate) fn radix_point_position(value: f64, base: Base) -> usize {
+    use crate::double::{exponent, SIGNIFICAND_SIZE};
+
+    // After a carry, the integer part is below 2^bits, and each digit holds at
+    // least floor(log2(base)) bits. One more digit covers the rounding of the
+    // zero-filled digits in bases that aren't powers of two.
+    // The clamp keeps invalid bases from dividing by zero, so this can't panic.
+    let bits = (exponent(value) + SIGNIFICAND_SIZE).max(0) as usize + 1;
+    let digit_bits = (Base::BITS - 1).saturating_sub(base.leading_zeros()).max(1);
+    2 + bits.div_ceil(digit_bits as usize)
+}
+
+/// Writes the radix string representation of value into buf, returning the
+/// range of buf that was written to. The [Base] must be valid, or nothing is
+/// written.
+#[inline]
+pub(crate) fn write_radix(value: f64, base: Base, buf: &mut [u8; BUFFER_LEN]) -> Range<usize> {
+    write_radix_format(float_format::Binary64::from_bits(value.to_bits()), base, buf)
+}
+
+/// Writes the radix string representation of value in its [FloatFormat] into
+/// buf, returning the range of buf that was written to. The [Base] must be
+/// valid, or nothing is written.
+#[inline]
+pub(crate) fn write_radix_format<F: FloatFormat>(value: F, base: Base, buf: &mut [u8; BUFFER_LEN]) -> Range<usize> {
+    write_radix_delta(float_format::to_f64(value), float_format::delta(value), base, buf)
+}
+
+/// Writes the radix string representation of value into buf, with fraction
+/// digits until the remainder is below delta, returning the range of buf that
+/// was written to. The [Base] must be valid, or nothing is written.
+///
+/// Dispatches to a copy of [write_radix_const] specialized for the [Base], so
+/// arithmetic by the base is on a compile-time constant.
+#[inline]
+pub(crate) fn write_radix_delta(value: f64, delta: f64, base: Base, buf: &mut [u8; BUFFER_LEN]) -> Range<usize> {
+    macro_rules! dispatch {
+        ($($base:literal)*) => {
+            match base {
+                $($base => write_radix_const::<$base>(value, delta, buf),)*
+                // Callers validate the base; nothing is written otherwise, so
+                // conversion can't panic.
+                _ => 0..0,
+            }
+        };
+    }
 
-        // If self is +Infinity, return "Infinity".
-        // If self is -Infinity, return "-Infinity".
-        if self.is_infinite() {
-            return Ok(if self.is_sign_positive() {
-                "Infinity"
-            } else {
-                "-Infinity"
-            }.into());
+    dispatch!(2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31 32 33 34 35 36)
+}
+
+/// The length of [ExactPowers::TABLE]. Base 3 has the most exact powers, up
+/// to 3^33, the last below 2^53.
+const EXACT_POWERS_LEN: usize = 34;
+
+/// The powers of `BASE` that f64 holds exactly, those whose odd part is below
+/// 2^53.
+struct ExactPowers<const BASE: Base>;
+
+impl<const BASE: Base> ExactPowers<BASE> {
+    /// `BASE^k` at index k while it's exact, then zeros.
+    const TABLE: [f64; EXACT_POWERS_LEN] = {
+        let odd = (BASE >> BASE.trailing_zeros()) as u64;
+        let mut table = [0.0; EXACT_POWERS_LEN];
+        let mut power = 1.0;
+        let mut odd_power = 1u64;
+        let mut k = 0;
+        while k < EXACT_POWERS_LEN && odd_power < 1 << 53 {
+            table[k] = power;
+            power *= BASE as f64;
+            odd_power = odd_power.saturating_mul(odd);
+            k += 1;
         }
+        table
+    };
+}
+
+/// Writes the radix string representation of value in the given `BASE` into
+/// buf, returning the range of buf that was written to. Fraction digits are
+/// written until the remainder is below delta, half the gap to the next value
+/// of the value's format, which is a power of two.
+#[inline(always)]
+fn write_radix_const<const BASE: Base>(value: f64, delta: f64, buf: &mut [u8; BUFFER_LEN]) -> Range<usize> {
+    use crate::double::{exponent, trunc};
+
+    let base = BASE;
+
+    // Targets without an FPU use integer arithmetic, with identical output.
+    if cfg!(feature = "soft-float") {
+        return softfloat::write_radix_soft(value, delta, base, buf);
+    }
+
+    count!(conversion, base);
+
+    // Writes a constant result.
+    let mut write_const = |s: &str| {
+        buf.iter_mut().zip(s.bytes()).for_each(|(slot, c)| *slot = c);
+        0..s.len()
+    };
 
-        // Character array used for conversion.
-        const CHARS: [char; 36] = [
-            '0', '1', '2', '3', '4', '5',
-            '6', '7', '8', '9', 'a', 'b',
-            'c', 'd', 'e', 'f', 'g', 'h',
-            'i', 'j', 'k', 'l', 'm', 'n',
-            'o', 'p', 'q', 'r', 's', 't',
-            'u', 'v', 'w', 'x', 'y', 'z'
-        ];
-
-        // Temporary buffer for the result. We start with the decimal point in the
-        // middle and write to the left for the integer part and to the right for the
-        // fractional part. 1024 characters for the exponent and 52 for the mantissa
-        // either way, with additional space for sign, decimal point and string
-        // termination should be sufficient.
-        const BUFFER_LEN: usize = 2200;
-        // Allocate buffer and cursors.
-        let mut buf: [char; BUFFER_LEN] = ['\0'; BUFFER_LEN];
-        let mut int_cursor = BUFFER_LEN / 2;
-        let mut fraction_cursor = int_cursor;
-
-        // The value to reference and modify instead of self
-        let value = self.abs();
-
-        // Split the value into an integer part and a fractional part.
-        let mut integer = value.floor();
-        let mut fraction = value - integer;
-        // We only compute fractional digits up to the input's precision.
-        let mut delta = 0.5 * (next_float(value) - value);
-        delta = delta.max(next_float(0.0));
-        // Base as f64
-        let base_f64 = base as f64;
-        if fraction >= delta {
-            // Insert decimal point.
-            buf[fraction_cursor] = '.';
+    // The result is always "NaN" if value is NaN.
+    if value.is_nan() {
+        return write_const("NaN");
+    }
+
+    // If value is +0 or -0, return "0".
+    if value == 0.0 {
+        return write_const("0");
+    }
+
+    // If value is +Infinity, return "Infinity".
+    // If value is -Infinity, return "-Infinity".
+    if value.is_infinite() {
+        return write_const(if value.is_sign_positive() {
+            "Infinity"
+        } else {
+            "-Infinity"
+        });
+    }
+
+    // Values below one, which dominate many workloads, have the single
+    // integer digit 0 (or 1 after a carry), so they skip the integer digit
+    // loop and the probing for zero-filled digits.
+    let below_one = value.abs() < 1.0;
+
+    // Cursors into the buffer, starting at the radix point.
+    let point = if below_one { 2 } else { radix_point_position(value, base) };
+    let mut int_cursor = point;
+    let mut fraction_cursor = point;
+
+    // The sign is added at the end.
+    let negative = value.is_sign_negative();
+    let value = value.abs();
+
+    // Split the value into an integer part and a fractional part.
+    let mut integer = trunc(value);
+    let mut fraction = value - integer;
+    // We only compute fractional digits up to the input's precision.
+    let initial_delta = delta;
+    let mut delta = delta;
+    // Base as f64
+    let base_f64 = base as f64;
+    if fraction >= delta {
+        // Insert decimal point.
+        put(buf, fraction_cursor, b'.');
+        fraction_cursor += 1;
+
+        let mut digits = 0;
+        loop {
+            // Shift up by one digit.
+            fraction *= base_f64;
+            digits += 1;
+            // While the power of the base is exact, scaling the initial delta,
+            // a power of two, by it gives the same result as the step-by-step
+            // products without waiting on the previous one. The fraction has
+            // to be scaled step by step, rounding as V8 does.
+            delta = match ExactPowers::<BASE>::TABLE.get(digits) {
+                Some(&power) if power != 0.0 => initial_delta * power,
+                _ => delta * base_f64,
+            };
+
+            // Write digit.
+            let digit = fraction as usize;
+            put(buf, fraction_cursor, digit_char::<BASE>(digit));
             fraction_cursor += 1;
 
-            loop {
-                // Shift up by one digit.
-                fraction *= base_f64;
-                delta *= base_f64;
-
-                // Write digit.
-                let digit = fraction as usize;
-                buf[fraction_cursor] = CHARS[digit];
-                fraction_cursor += 1;
-
-                // Calculate remainder.
-                fraction -= digit as f64;
-
-                // Round to even.
-                if (fraction > 0.5 || (fraction == 0.5 && (digit & 1) == 1)) && fraction + delta > 1.0 {
-                    // We need to back trace already written digits in case of carry-over.
-                    loop {
-                        fraction_cursor -= 1;
-                        if fraction_cursor == BUFFER_LEN / 2 {
-                            // Carry over the integer part.
-                            integer += 1.0;
-                            break;
-                        }
-
-                        let c = buf[fraction_cursor];
-                        // Reconstruct digit.
-                        let digit = if c > '9' {
-                            (c as u32) - ('a' as u32) + 10
-                        } else {
-                            (c as u32) - ('0' as u32)
-                        };
-                        if digit + 1 < base as u32 {
-                            buf[fraction_cursor] = CHARS[digit as usize + 1];
-                            fraction_cursor += 1;
-                            break;
-                        }
+            // Calculate remainder.
+            fraction -= digit as f64;
+
+            // Round to even.
+            if (fraction > 0.5 || (fraction == 0.5 && (digit & 1) == 1)) && fraction + delta > 1.0 {
+                // We need to back trace already written digits in case of carry-over.
+                let carry_start = fraction_cursor;
+                loop {
+                    fraction_cursor -= 1;
+                    if fraction_cursor == point {
+                        // Carry over the integer part.
+                        integer += 1.0;
+                        break;
                     }
 
-                    break;
+                    // Reconstruct digit.
+                    let c = buf.get(fraction_cursor).copied().unwrap_or(b'0');
+                    let digit = (c as char).to_digit(36).unwrap_or(0);
+                    if digit + 1 < base as u32 {
+                        put(buf, fraction_cursor, digit_char::<BASE>(digit as usize + 1));
+                        fraction_cursor += 1;
+                        break;
+                    }
                 }
+                trace_event!("carry back-propagation", value = value, digits = carry_start - fraction_cursor + 1);
+                count!(ROUND_UPS);
 
-                if fraction < delta {
-                    break;
-                }
+                break;
+            }
+
+            if fraction < delta {
+                break;
             }
         }
+    }
 
+    if below_one {
+        int_cursor -= 1;
+        put(buf, int_cursor, if integer == 0.0 { b'0' } else { b'1' });
+    } else {
         // Compute integer digits. Fill unrepresented digits with zero.
         while exponent(integer / base_f64) > 0 {
             integer /= base_f64;
             int_cursor -= 1;
-            buf[int_cursor] = '0';
+            put(buf, int_cursor, b'0');
         }
 
         loop {
             let remainder = integer % base_f64;
             int_cursor -= 1;
-            buf[int_cursor] = CHARS[remainder as usize];
+            put(buf, int_cursor, digit_char::<BASE>(remainder as usize));
             integer = (integer - remainder) / base_f64;
 
             if integer <= 0.0 {
                 break;
             }
         }
+    }
 
-        // Add sign if negative.
-        if self.is_sign_negative() {
-            int_cursor -= 1;
-            buf[int_cursor] = '-';
-        }
-
-        // Create result.
-        let mut result = String::with_capacity(fraction_cursor - int_cursor);
-        for c in &buf[int_cursor..fraction_cursor] {
-            result.push(*c);
-        }
-        Ok(result)
+    // Add sign if negative.
+    if negative {
+        int_cursor -= 1;
+        put(buf, int_cursor, b'-');
     }
+
+    int_cursor..fraction_cursor
 }
 
-impl ToRadixStr for f32 {
-    fn to_radix_str(self, base: Base) -> Result<String, InvalidBaseError> {
-        (self as f64).to_radix_str(base)
+/// Character array used for conversion.
+const CHARS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+/// Returns the character for the given digit. Digits are always below the
+/// base, so the clamp never changes them, but it lets the compiler drop the
+/// bounds check.
+#[inline(always)]
+fn digit_char<const BASE: Base>(digit: usize) -> u8 {
+    debug_assert!(digit < BASE as usize);
+    CHARS[digit.min(CHARS.len() - 1)]
+}
+
+/// Writes c at index i of buf. The cursors never leave the buffer, so this
+/// always writes, but unlike indexing it can't panic.
+#[inline(always)]
+fn put(buf: &mut [u8; BUFFER_LEN], i: usize, c: u8) {
+    debug_assert!(i < BUFFER_LEN);
+    if let Some(slot) = buf.get_mut(i) {
+        *slot = c;
     }
 }
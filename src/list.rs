@@ -0,0 +1,122 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! Formatting lists of values as a single radix string.
+
+use crate::{write_radix, Base, InvalidBaseError, MAX_BASE, MIN_BASE};
+use crate::pool::with_buffer;
+
+/// Options for formatting slices and arrays of values as radix strings
+/// joined by a separator and wrapped in brackets.
+///
+/// The default options produce a JavaScript array literal, such as
+/// `[1, 0.5, NaN]`, which is valid JavaScript in base 10.
+///
+/// Example:
+/// ```rust
+/// use radix_ecmascript::RadixList;
+///
+/// let list = RadixList::new(16).unwrap();
+/// assert_eq!(list.format(&[255.5, -1.0]), "[ff.8, -1]");
+/// assert_eq!(list.separator(" ").brackets(None).format(&[10.0, 11.0]), "a b");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RadixList {
+    base: Base,
+    separator: &'static str,
+    brackets: Option<(&'static str, &'static str)>,
+}
+
+impl RadixList {
+    /// Returns the default options for the given [Base]: elements separated by
+    /// `", "` inside `[` and `]`.
+    ///
+    /// Returns [InvalidBaseError] if the given [Base] is out of range of
+    /// [MIN_BASE] and [MAX_BASE] (inclusive).
+    pub fn new(base: Base) -> Result<Self, InvalidBaseError> {
+        if !(MIN_BASE..=MAX_BASE).contains(&base) {
+            return Err(InvalidBaseError(base));
+        }
+
+        Ok(Self {
+            base,
+            separator: ", ",
+            brackets: Some(("[", "]")),
+        })
+    }
+
+    /// Returns the [Base] values are formatted in.
+    pub fn base(&self) -> Base {
+        self.base
+    }
+
+    /// Sets the separator written between elements.
+    pub fn separator(mut self, separator: &'static str) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Sets the opening and closing brackets written around the elements, or
+    /// removes them with `None`.
+    pub fn brackets(mut self, brackets: Option<(&'static str, &'static str)>) -> Self {
+        self.brackets = brackets;
+        self
+    }
+
+    /// Returns the given values as radix strings joined by the separator and
+    /// wrapped in the brackets. Arrays, slices and vectors can all be passed
+    /// by reference.
+    pub fn format<T: Into<f64> + Copy>(&self, values: &[T]) -> String {
+        let (open, close) = self.brackets.unwrap_or(("", ""));
+        let mut s = String::from(open);
+        with_buffer(|buf| {
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    s.push_str(self.separator);
+                }
+                let range = write_radix((*value).into(), self.base, buf);
+                s.extend(buf[range].iter().map(|c| *c as char));
+            }
+        });
+        s.push_str(close);
+        s
+    }
+}
@@ -0,0 +1,320 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! Shortest JavaScript numeric literal generation, for code generators and minifiers.
+
+/// Returns the shortest decimal digits that uniquely identify the given positive
+/// finite value, along with the decimal exponent `n` such that the value is
+/// `0.digits * 10^n`.
+pub(crate) fn shortest_digits(value: f64) -> (String, i32) {
+    // Rust's exponential formatting produces the shortest round-trip digits,
+    // which are the same digits chosen by ECMAScript's Number::toString.
+    let formatted = format!("{:e}", value);
+    let (mantissa, exponent) = formatted.split_once('e').unwrap();
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    (digits, exponent.parse::<i32>().unwrap() + 1)
+}
+
+/// Returns the decimal string representation of the given positive finite value
+/// as defined by ECMAScript's Number::toString with no radix.
+pub(crate) fn to_decimal_str(value: f64) -> String {
+    if value == 0.0 {
+        return "0".into();
+    }
+
+    let (digits, n) = shortest_digits(value);
+    let k = digits.len() as i32;
+    if k <= n && n <= 21 {
+        // Integer with trailing zeros.
+        format!("{}{}", digits, "0".repeat((n - k) as usize))
+    } else if 0 < n && n <= 21 {
+        // Decimal point inside the digits.
+        format!("{}.{}", &digits[..n as usize], &digits[n as usize..])
+    } else if -6 < n && n <= 0 {
+        // Leading zeros after the decimal point.
+        format!("0.{}{}", "0".repeat(-n as usize), digits)
+    } else {
+        // Exponential notation.
+        let sign = if n - 1 < 0 { '-' } else { '+' };
+        let exponent = (n - 1).abs();
+        if k == 1 {
+            format!("{}e{}{}", digits, sign, exponent)
+        } else {
+            format!("{}.{}e{}{}", &digits[..1], &digits[1..], sign, exponent)
+        }
+    }
+}
+
+/// Returns the shortest JavaScript numeric literal that evaluates to the given value.
+///
+/// The candidates are the decimal form (with the leading zero of fractions
+/// omitted, such as `.5`), exponent forms such as `1e3` and `1.5e-7`, and the
+/// hexadecimal form for integers, such as `0x10000`. Ties prefer the decimal form.
+///
+/// Negative values are prefixed with `-`, which strictly speaking makes them
+/// unary expressions rather than literals. NaN and infinities are returned as
+/// `NaN`, `Infinity` and `-Infinity`.
+pub fn shortest_js_literal(value: f64) -> String {
+    if value.is_nan() {
+        return "NaN".into();
+    }
+
+    let sign = if value.is_sign_negative() { "-" } else { "" };
+    if value.is_infinite() {
+        return format!("{}Infinity", sign);
+    }
+
+    let value = value.abs();
+    if value == 0.0 {
+        return format!("{}0", sign);
+    }
+
+    // Decimal form.
+    let decimal = to_decimal_str(value);
+    let mut best = match decimal.strip_prefix("0.") {
+        Some(fraction) => format!(".{}", fraction),
+        None => decimal,
+    };
+
+    let mut consider = |candidate: String| {
+        if candidate.len() < best.len() {
+            best = candidate;
+        }
+    };
+
+    // Exponent forms, with an integer mantissa and with a single integer digit.
+    let (digits, n) = shortest_digits(value);
+    let k = digits.len() as i32;
+    if n != k {
+        consider(format!("{}e{}", digits, n - k));
+    }
+    if k > 1 {
+        consider(format!("{}.{}e{}", &digits[..1], &digits[1..], n - 1));
+    }
+
+    // Hexadecimal form.
+    if value.fract() == 0.0 {
+        consider(format!("0x{}", crate::radix_str(value, 16).unwrap()));
+    }
+
+    format!("{}{}", sign, best)
+}
+
+/// Scans the JavaScript NumericLiteral at the start of the given string,
+/// returning its length in bytes and its value, or [None] if the string doesn't
+/// start with one.
+///
+/// Decimal literals with an optional exponent (`1.5e-7`, `.5`, `1.`) and
+/// `0x`, `0o` and `0b` literals are accepted, with `_` separators between
+/// digits. Legacy octal literals such as `017` are not, so only their leading
+/// `0` is scanned, as in strict mode.
+#[cfg(feature = "parse")]
+pub fn scan_js_numeric_literal(s: &str) -> Option<(usize, f64)> {
+    use crate::parse::digits_to_f64;
+
+    let bytes = s.as_bytes();
+    let prefixed_base = match bytes {
+        [b'0', b'x' | b'X', ..] => 16,
+        [b'0', b'o' | b'O', ..] => 8,
+        [b'0', b'b' | b'B', ..] => 2,
+        _ => 0,
+    };
+    if prefixed_base != 0 {
+        let (len, digits) = scan_digits(&bytes[2..], prefixed_base);
+        if digits.is_empty() {
+            return None;
+        }
+        return Some((2 + len, digits_to_f64(&digits, &[], prefixed_base)));
+    }
+
+    // Only a single zero may start a decimal integer.
+    let (mut len, integer) = scan_digits(bytes, 10);
+    if integer.first() == Some(&0) {
+        len = 1;
+    }
+    let mut has_digits = !integer.is_empty();
+    if bytes.get(len) == Some(&b'.') {
+        let (fraction_len, fraction) = scan_digits(&bytes[len + 1..], 10);
+        if has_digits || !fraction.is_empty() {
+            len += 1 + fraction_len;
+            has_digits = true;
+        }
+    }
+    if !has_digits {
+        return None;
+    }
+
+    if let Some(b'e' | b'E') = bytes.get(len) {
+        let sign_len = matches!(bytes.get(len + 1), Some(b'-' | b'+')) as usize;
+        let (exponent_len, exponent) = scan_digits(&bytes[len + 1 + sign_len..], 10);
+        if !exponent.is_empty() {
+            len += 1 + sign_len + exponent_len;
+        }
+    }
+
+    // Rust's float parsing is correctly rounded, as ECMAScript requires.
+    let text: String = s[..len].chars().filter(|c| *c != '_').collect();
+    Some((len, text.parse().unwrap()))
+}
+
+/// A JavaScript numeric literal scanned by [scan_js_literal].
+#[cfg(feature = "parse")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JsLiteral {
+    /// A Number literal, such as `1.5e-7` or `0xff`.
+    Number(f64),
+    /// A BigInt literal, such as `123n` or `0xffn`.
+    BigInt(i128),
+}
+
+/// Scans the JavaScript NumericLiteral at the start of the given string like
+/// [scan_js_numeric_literal], also recognizing BigInt literals, which are
+/// integers without an exponent followed by `n`, such as `123n`, `0n` and
+/// `0xffn`.
+///
+/// Returns [None] if the string doesn't start with a literal, or if a BigInt
+/// literal is greater than [i128::MAX].
+///
+/// Example:
+/// ```rust
+/// use radix_ecmascript::{scan_js_literal, JsLiteral};
+///
+/// assert_eq!(scan_js_literal("0xffn;"), Some((5, JsLiteral::BigInt(255))));
+/// assert_eq!(scan_js_literal("1.5n"), Some((3, JsLiteral::Number(1.5))));
+/// ```
+#[cfg(feature = "parse")]
+pub fn scan_js_literal(s: &str) -> Option<(usize, JsLiteral)> {
+    let (len, value) = scan_js_numeric_literal(s)?;
+    let bytes = s.as_bytes();
+    if bytes.get(len) != Some(&b'n') {
+        return Some((len, JsLiteral::Number(value)));
+    }
+
+    // The literal must be all digits after its prefix, without a radix point
+    // or exponent. Decimal ones can't have a leading zero, which the scan
+    // above already stops at.
+    let (base, start) = match bytes {
+        [b'0', b'x' | b'X', ..] => (16, 2),
+        [b'0', b'o' | b'O', ..] => (8, 2),
+        [b'0', b'b' | b'B', ..] => (2, 2),
+        _ => (10, 0),
+    };
+    let (digits_len, digits) = scan_digits(&bytes[start..len], base);
+    if start + digits_len != len {
+        return Some((len, JsLiteral::Number(value)));
+    }
+
+    let mut n: i128 = 0;
+    for digit in digits {
+        n = n.checked_mul(base as i128)?.checked_add(digit as i128)?;
+    }
+    Some((len + 1, JsLiteral::BigInt(n)))
+}
+
+/// Parses a JSON5 number, a JavaScript NumericLiteral (see
+/// [scan_js_numeric_literal]), `Infinity` or `NaN` with an optional `+` or `-`
+/// sign, as in `+.5`, `-0x1F` or `-Infinity`.
+///
+/// Unlike JSON, a leading `+`, a leading or trailing radix point and hex
+/// literals are accepted. Unlike JavaScript's `Number(s)`, whitespace and the
+/// empty string are not.
+///
+/// Returns [ParseRadixError::Empty](crate::ParseRadixError::Empty) if the
+/// string has no digits, or
+/// [ParseRadixError::InvalidDigit](crate::ParseRadixError::InvalidDigit) with
+/// the index of the first character that isn't part of the number.
+///
+/// Example:
+/// ```rust
+/// use radix_ecmascript::parse_json5_number;
+///
+/// assert_eq!(parse_json5_number("+.5").unwrap(), 0.5);
+/// assert_eq!(parse_json5_number("-0x1F").unwrap(), -31.0);
+/// assert!(parse_json5_number("1.5x").is_err());
+/// ```
+#[cfg(feature = "parse")]
+pub fn parse_json5_number(s: &str) -> Result<f64, crate::ParseRadixError> {
+    use crate::ParseRadixError;
+
+    let (negative, literal) = match s.as_bytes().first() {
+        Some(b'-') => (true, &s[1..]),
+        Some(b'+') => (false, &s[1..]),
+        _ => (false, s),
+    };
+    if literal.is_empty() {
+        return Err(ParseRadixError::Empty);
+    }
+
+    let start = s.len() - literal.len();
+    let value = match literal {
+        "Infinity" => f64::INFINITY,
+        "NaN" => f64::NAN,
+        _ => match scan_js_numeric_literal(literal) {
+            Some((len, value)) if len == literal.len() => value,
+            Some((len, _)) => return Err(ParseRadixError::InvalidDigit(start + len)),
+            None => return Err(ParseRadixError::InvalidDigit(start)),
+        },
+    };
+    Ok(if negative { -value } else { value })
+}
+
+/// Scans digits in the given base with `_` separators between them, returning
+/// the number of bytes scanned and the digit values.
+#[cfg(feature = "parse")]
+fn scan_digits(bytes: &[u8], base: crate::Base) -> (usize, Vec<u8>) {
+    use crate::lut::digit_value;
+
+    let is_digit = |c: &u8| digit_value(*c, base).is_some();
+    let mut digits = Vec::new();
+    let mut len = 0;
+    while let Some(&c) = bytes.get(len) {
+        if c == b'_' && len > 0 && bytes.get(len + 1).is_some_and(is_digit) {
+            len += 1;
+            continue;
+        }
+        match digit_value(c, base) {
+            Some(digit) => digits.push(digit),
+            None => break,
+        }
+        len += 1;
+    }
+    (len, digits)
+}
@@ -0,0 +1,123 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+
+//! Process-wide counters of conversions and of the expensive branches they
+//! take, enabled by the `metrics` feature.
+//!
+//! Counting is a relaxed atomic increment, cheap enough to leave on in
+//! production. Without the feature, nothing is counted.
+//!
+//! Example:
+//! ```rust
+//! use radix_ecmascript::{metrics, ToRadixStr};
+//!
+//! let before = metrics::snapshot();
+//! (0.1).to_radix_str(16).unwrap();
+//! assert!(metrics::snapshot().conversions(16) > before.conversions(16));
+//! ```
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use crate::{Base, MAX_BASE};
+
+/// Conversions, indexed by [Base].
+pub(crate) static CONVERSIONS: [AtomicU64; MAX_BASE as usize + 1] = [const { AtomicU64::new(0) }; MAX_BASE as usize + 1];
+/// Conversions whose last fraction digit was rounded up.
+pub(crate) static ROUND_UPS: AtomicU64 = AtomicU64::new(0);
+/// Conversions with SpiderMonkey's big integer algorithm.
+pub(crate) static BIGNUM_CONVERSIONS: AtomicU64 = AtomicU64::new(0);
+/// Parses with big integer arithmetic.
+pub(crate) static BIGNUM_PARSES: AtomicU64 = AtomicU64::new(0);
+/// Conversion buffers allocated because the pool had none to reuse.
+pub(crate) static BUFFER_ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+/// The counters at one point in time.
+///
+/// The counters are read one at a time, so conversions on other threads
+/// while taking a snapshot may be counted in some but not others.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Metrics {
+    conversions: [u64; MAX_BASE as usize + 1],
+    /// Conversions whose last fraction digit was rounded up, which may carry
+    /// back through earlier digits.
+    pub round_ups: u64,
+    /// Conversions with SpiderMonkey's big integer algorithm, which is much
+    /// slower than V8's.
+    pub bignum_conversions: u64,
+    /// Parses with exact big integer arithmetic, which every finite,
+    /// non-zero string takes.
+    pub bignum_parses: u64,
+    /// Conversion buffers allocated because the pool had none to reuse.
+    pub buffer_allocations: u64,
+}
+
+impl Metrics {
+    /// Returns the number of values converted to radix strings in the given
+    /// [Base], or 0 if it's invalid.
+    pub fn conversions(&self, base: Base) -> u64 {
+        self.conversions.get(base as usize).copied().unwrap_or(0)
+    }
+
+    /// Returns the number of values converted to radix strings in any base.
+    pub fn total_conversions(&self) -> u64 {
+        self.conversions.iter().sum()
+    }
+}
+
+/// Returns the current counters.
+pub fn snapshot() -> Metrics {
+    let load = |counter: &AtomicU64| counter.load(Ordering::Relaxed);
+    Metrics {
+        conversions: CONVERSIONS.each_ref().map(load),
+        round_ups: load(&ROUND_UPS),
+        bignum_conversions: load(&BIGNUM_CONVERSIONS),
+        bignum_parses: load(&BIGNUM_PARSES),
+        buffer_allocations: load(&BUFFER_ALLOCATIONS),
+    }
+}
+
+/// Sets every counter to zero.
+pub fn reset() {
+    let counters = CONVERSIONS.iter().chain([&ROUND_UPS, &BIGNUM_CONVERSIONS, &BIGNUM_PARSES, &BUFFER_ALLOCATIONS]);
+    for counter in counters {
+        counter.store(0, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,102 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+
+//! Canonical forms of radix strings.
+
+/// What [normalize_radix_str] does with negative zero.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NegativeZero {
+    /// Writes negative zero as `0`, as [ToRadixStr](crate::ToRadixStr) does,
+    /// so it equals positive zero.
+    #[default]
+    Collapse,
+    /// Writes negative zero as `-0`, keeping it apart from positive zero.
+    Keep,
+}
+
+/// Returns the canonical form of the given radix string: no `+` sign, no
+/// leading zeros before the integer digit, no trailing zeros after the radix
+/// point (nor the radix point if no digits follow it), and lowercase digits.
+/// Zero's sign is decided by the given [NegativeZero] policy.
+///
+/// Two strings in the same base have the same canonical form exactly when
+/// they have the same value, so it suits equality comparisons and hashing.
+/// `NaN` and the infinities are kept as they are, without a `+` sign.
+///
+/// The string should have the syntax accepted by
+/// [FromRadixStr::from_radix_str](crate::FromRadixStr::from_radix_str),
+/// without an exponent; other strings get no particular form.
+///
+/// Example:
+/// ```rust
+/// use radix_ecmascript::{normalize_radix_str, NegativeZero};
+///
+/// assert_eq!(normalize_radix_str("+00FF.80", NegativeZero::Collapse), "ff.8");
+/// assert_eq!(normalize_radix_str("-0.000", NegativeZero::Collapse), "0");
+/// assert_eq!(normalize_radix_str("-0.000", NegativeZero::Keep), "-0");
+/// ```
+pub fn normalize_radix_str(s: &str, negative_zero: NegativeZero) -> String {
+    let (negative, unsigned) = match s.strip_prefix('-') {
+        Some(unsigned) => (true, unsigned),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+    if matches!(unsigned, "NaN" | "Infinity") {
+        return if negative { s.to_owned() } else { unsigned.to_owned() };
+    }
+
+    let (integer, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+    let integer = integer.trim_start_matches('0');
+    let fraction = fraction.trim_end_matches('0');
+    let zero = integer.is_empty() && fraction.is_empty();
+
+    let mut normalized = String::with_capacity(s.len());
+    if negative && (!zero || negative_zero == NegativeZero::Keep) {
+        normalized.push('-');
+    }
+    normalized.push_str(if integer.is_empty() { "0" } else { integer });
+    if !fraction.is_empty() {
+        normalized.push('.');
+        normalized.push_str(fraction);
+    }
+    normalized.make_ascii_lowercase();
+    normalized
+}
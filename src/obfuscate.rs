@@ -0,0 +1,172 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! Seeded obfuscation of radix strings.
+//!
+//! An [Obfuscator] deterministically picks a [Base] and a digit alphabet for
+//! every value index from a seed, so the same seed always produces the same
+//! scheme and can be used to decode what it encoded.
+
+use crate::parse::parse_f64;
+use crate::{radix_str, Base, ParseRadixError, MAX_BASE, MIN_BASE};
+
+/// Digits in their standard order.
+const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+/// Encodes and decodes values using a per-index [Base] and digit alphabet
+/// derived from a seed.
+///
+/// Values are encoded with [ToRadixStr::to_radix_str] and then have their
+/// digits substituted, so the signs, radix point, `NaN` and `Infinity` are
+/// left as is. Decoding returns the f64 nearest to the encoded digits, which is
+/// the original value whenever its radix string is exact; like V8, the radix
+/// string of some values stops a digit short of identifying them uniquely.
+///
+/// The mapping from seed to scheme is part of the encoding format and will not
+/// change between versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Obfuscator {
+    seed: u64,
+}
+
+/// A [Base] and digit alphabet picked for a single value.
+struct Scheme {
+    base: Base,
+    alphabet: [u8; 36],
+}
+
+impl Obfuscator {
+    /// Returns an [Obfuscator] using the given seed.
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+
+    /// Returns the [Base] used for the value at the given index.
+    pub fn base(&self, index: usize) -> Base {
+        self.scheme(index).base
+    }
+
+    /// Encodes the value at the given index.
+    pub fn encode(&self, index: usize, value: f64) -> String {
+        let scheme = self.scheme(index);
+        radix_str(value, scheme.base)
+            .unwrap()
+            .bytes()
+            .map(|c| match digit_value(c) {
+                Some(digit) if !value.is_nan() && !value.is_infinite() => scheme.alphabet[digit] as char,
+                _ => c as char,
+            })
+            .collect()
+    }
+
+    /// Decodes the value at the given index.
+    ///
+    /// Returns [ParseRadixError] if the input wasn't produced by [Obfuscator::encode]
+    /// with the same seed and index.
+    pub fn decode(&self, index: usize, encoded: &str) -> Result<f64, ParseRadixError> {
+        let scheme = self.scheme(index);
+        if matches!(encoded, "NaN" | "Infinity" | "-Infinity") {
+            return parse_f64(encoded, scheme.base);
+        }
+
+        let digits = &scheme.alphabet[..scheme.base as usize];
+        let mut decoded = String::with_capacity(encoded.len());
+        for (i, c) in encoded.bytes().enumerate() {
+            match digits.iter().position(|d| *d == c) {
+                Some(digit) => decoded.push(DIGITS[digit] as char),
+                None if c.is_ascii_lowercase() || c.is_ascii_digit() => {
+                    return Err(ParseRadixError::InvalidDigit(i));
+                }
+                None => decoded.push(c as char),
+            }
+        }
+        parse_f64(&decoded, scheme.base)
+    }
+
+    /// Encodes all values, using their position in the slice as their index.
+    pub fn encode_all(&self, values: &[f64]) -> Vec<String> {
+        values.iter()
+            .enumerate()
+            .map(|(index, value)| self.encode(index, *value))
+            .collect()
+    }
+
+    /// Decodes all values, using their position in the slice as their index.
+    pub fn decode_all<S: AsRef<str>>(&self, encoded: &[S]) -> Result<Vec<f64>, ParseRadixError> {
+        encoded.iter()
+            .enumerate()
+            .map(|(index, s)| self.decode(index, s.as_ref()))
+            .collect()
+    }
+
+    /// Returns the scheme for the value at the given index.
+    fn scheme(&self, index: usize) -> Scheme {
+        let mut state = mix(self.seed ^ mix(index as u64));
+        let base = MIN_BASE + (next(&mut state) % (MAX_BASE - MIN_BASE + 1) as u64) as Base;
+
+        // Fisher-Yates shuffle.
+        let mut alphabet = *DIGITS;
+        for i in (1..alphabet.len()).rev() {
+            let j = (next(&mut state) % (i as u64 + 1)) as usize;
+            alphabet.swap(i, j);
+        }
+
+        Scheme { base, alphabet }
+    }
+}
+
+/// Returns the value of the given standard digit character.
+fn digit_value(c: u8) -> Option<usize> {
+    DIGITS.iter().position(|d| *d == c)
+}
+
+/// Advances the SplitMix64 state and returns the next output.
+fn next(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    mix(*state)
+}
+
+/// The SplitMix64 output function.
+fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
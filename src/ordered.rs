@@ -0,0 +1,105 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! [ToRadixStr] and [FromRadixStr] implementations for the
+//! [ordered-float](https://docs.rs/ordered-float) wrappers, enabled by the
+//! `ordered-float` feature.
+
+use ordered_float::{NotNan, OrderedFloat};
+use crate::{Base, FromRadixStr, InvalidBaseError, ParseRadixError, ToRadixStr};
+
+#[cfg(feature = "f64")]
+impl ToRadixStr for OrderedFloat<f64> {
+    fn to_radix_str(self, base: Base) -> Result<String, InvalidBaseError> {
+        self.0.to_radix_str(base)
+    }
+}
+
+#[cfg(feature = "f32")]
+impl ToRadixStr for OrderedFloat<f32> {
+    fn to_radix_str(self, base: Base) -> Result<String, InvalidBaseError> {
+        self.0.to_radix_str(base)
+    }
+}
+
+#[cfg(feature = "f64")]
+impl ToRadixStr for NotNan<f64> {
+    fn to_radix_str(self, base: Base) -> Result<String, InvalidBaseError> {
+        self.into_inner().to_radix_str(base)
+    }
+}
+
+#[cfg(feature = "f32")]
+impl ToRadixStr for NotNan<f32> {
+    fn to_radix_str(self, base: Base) -> Result<String, InvalidBaseError> {
+        self.into_inner().to_radix_str(base)
+    }
+}
+
+#[cfg(feature = "f64")]
+impl FromRadixStr for OrderedFloat<f64> {
+    fn from_radix_str(s: &str, base: Base) -> Result<Self, ParseRadixError> {
+        f64::from_radix_str(s, base).map(OrderedFloat)
+    }
+}
+
+#[cfg(feature = "f32")]
+impl FromRadixStr for OrderedFloat<f32> {
+    fn from_radix_str(s: &str, base: Base) -> Result<Self, ParseRadixError> {
+        f32::from_radix_str(s, base).map(OrderedFloat)
+    }
+}
+
+/// Returns [ParseRadixError::NaN] for `NaN`.
+#[cfg(feature = "f64")]
+impl FromRadixStr for NotNan<f64> {
+    fn from_radix_str(s: &str, base: Base) -> Result<Self, ParseRadixError> {
+        NotNan::new(f64::from_radix_str(s, base)?).map_err(|_| ParseRadixError::NaN)
+    }
+}
+
+/// Returns [ParseRadixError::NaN] for `NaN`.
+#[cfg(feature = "f32")]
+impl FromRadixStr for NotNan<f32> {
+    fn from_radix_str(s: &str, base: Base) -> Result<Self, ParseRadixError> {
+        NotNan::new(f32::from_radix_str(s, base)?).map_err(|_| ParseRadixError::NaN)
+    }
+}
@@ -0,0 +1,734 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! Parsing of radix strings, the inverse of [ToRadixStr](crate::ToRadixStr).
+//!
+//! Parsing is correctly rounded: the exact value of the digits is computed
+//! with a [Bignum] and rounded once to the nearest f64 (ties to even).
+
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use crate::{Base, InvalidBaseError, MAX_BASE, MIN_BASE};
+#[cfg(feature = "f32")]
+use crate::bignum::F32_FORMAT;
+use crate::bignum::{
+    ratio_to_f64, round_ratio, BinaryFormat, Bignum, BIGIT_CAPACITY, BIGIT_SIZE, F64_FORMAT,
+};
+use crate::double::from_diy_fp;
+use crate::lut::digit_value;
+
+/// An error returned when parsing a radix string fails.
+#[derive(Debug)]
+pub enum ParseRadixError {
+    /// The given [Base] is out of range of [MIN_BASE] and [MAX_BASE].
+    InvalidBase(InvalidBaseError),
+    /// The input string is empty.
+    Empty,
+    /// The input has an invalid or missing digit at the given byte index.
+    InvalidDigit(usize),
+    /// The input is `NaN`, which the target type can't represent.
+    NaN,
+    /// The input is too large for the target type.
+    Overflow,
+}
+
+impl Display for ParseRadixError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseRadixError::InvalidBase(e) => e.fmt(f),
+            ParseRadixError::Empty => f.write_str("cannot parse radix string from empty string"),
+            ParseRadixError::InvalidDigit(index) => write!(f, "invalid or missing digit at index {}", index),
+            ParseRadixError::NaN => f.write_str("cannot parse NaN into a type that excludes it"),
+            ParseRadixError::Overflow => f.write_str("number too large to fit in target type"),
+        }
+    }
+}
+
+impl std::error::Error for ParseRadixError {}
+
+impl From<InvalidBaseError> for ParseRadixError {
+    fn from(e: InvalidBaseError) -> Self {
+        ParseRadixError::InvalidBase(e)
+    }
+}
+
+/// Allows a type to be parsed from radix string representation.
+pub trait FromRadixStr: Sized {
+    /// Parses a radix string in the given [Base], as produced by
+    /// [ToRadixStr::to_radix_str](crate::ToRadixStr::to_radix_str).
+    ///
+    /// The accepted syntax is an optional sign (`-` or `+`) followed by
+    /// `Infinity`, or digits with an optional radix point followed by more digits.
+    /// `NaN` is accepted without a sign. Digits are case-insensitive.
+    ///
+    /// Floats also accept an exponent after the digits: `@` and a signed
+    /// decimal exponent multiplies by a power of the base, as in GMP, and in
+    /// bases 2, 4, 8 and 16, `p` (or `P`) and a signed decimal exponent
+    /// multiplies by a power of two, as in C's hexadecimal floats. So
+    /// `1.8@2` and `1.8p8` are both 384 in base 16.
+    ///
+    /// Returns [ParseRadixError] if the given [Base] is out of range of
+    /// [MIN_BASE] and [MAX_BASE] (inclusive) or the string is malformed.
+    fn from_radix_str(s: &str, base: Base) -> Result<Self, ParseRadixError>;
+}
+
+#[cfg(feature = "f64")]
+impl FromRadixStr for f64 {
+    fn from_radix_str(s: &str, base: Base) -> Result<Self, ParseRadixError> {
+        parse_f64(s, base)
+    }
+}
+
+/// Rounds once from the exact value of the string, so the result is the
+/// nearest f32 rather than the f32 nearest to the nearest f64.
+#[cfg(feature = "f32")]
+impl FromRadixStr for f32 {
+    fn from_radix_str(s: &str, base: Base) -> Result<Self, ParseRadixError> {
+        if !(MIN_BASE..=MAX_BASE).contains(&base) {
+            return Err(InvalidBaseError(base).into());
+        }
+        if let Some(value) = parse_special(s) {
+            return Ok(value as f32);
+        }
+
+        Ok(split_scientific(s, base)?.to_f32(base))
+    }
+}
+
+/// The sign and digits of a finite radix string.
+pub(crate) struct Digits {
+    /// Whether the string has a `-` sign.
+    pub(crate) negative: bool,
+    /// Digit values, most significant first.
+    pub(crate) digits: Vec<u8>,
+    /// Number of digits before the radix point.
+    pub(crate) point: usize,
+}
+
+impl Digits {
+    /// Returns the integer digits.
+    pub(crate) fn integer(&self) -> &[u8] {
+        &self.digits[..self.point]
+    }
+
+    /// Returns the fractional digits.
+    pub(crate) fn fraction(&self) -> &[u8] {
+        &self.digits[self.point..]
+    }
+
+    /// Multiplies the digits by base^exponent, moving the radix point and
+    /// padding with zeros as needed.
+    pub(crate) fn scale(&mut self, exponent: i64) {
+        // Past this far, the value is out of range of f64 either way, as
+        // 2^1100 is.
+        const LIMIT: i64 = 1100;
+        let len = self.digits.len() as i64;
+        let point = (self.point as i64).saturating_add(exponent).clamp(-LIMIT, len + LIMIT);
+        if point < 0 {
+            self.digits.splice(0..0, std::iter::repeat_n(0, -point as usize));
+            self.point = 0;
+        } else {
+            if point > len {
+                self.digits.resize(point as usize, 0);
+            }
+            self.point = point as usize;
+        }
+    }
+
+    /// Multiplies the digits by the given factor, which is at most the base.
+    pub(crate) fn mul_small(&mut self, factor: u32, base: Base) {
+        let mut carry = 0;
+        for digit in self.digits.iter_mut().rev() {
+            let product = *digit as u32 * factor + carry;
+            *digit = (product % base as u32) as u8;
+            carry = product / base as u32;
+        }
+        if carry > 0 {
+            self.digits.insert(0, carry as u8);
+            self.point += 1;
+        }
+    }
+
+    /// Returns the f64 nearest to the signed value of the digits.
+    pub(crate) fn to_f64(&self, base: Base) -> f64 {
+        let value = digits_to_f64(self.integer(), self.fraction(), base);
+        if self.negative { -value } else { value }
+    }
+
+    /// Returns the f32 nearest to the signed value of the digits.
+    #[cfg(feature = "f32")]
+    pub(crate) fn to_f32(&self, base: Base) -> f32 {
+        let value = match digits_to_parts(self.integer(), self.fraction(), base, F32_FORMAT) {
+            // The parts are an f32 (or beyond f32::MAX), so narrowing is exact.
+            Some((significand, exponent)) => from_diy_fp(significand, exponent) as f32,
+            None => f32::INFINITY,
+        };
+        if self.negative { -value } else { value }
+    }
+}
+
+/// An exponent after the digits of a radix string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Exponent {
+    /// `@`, multiplying by a power of the base.
+    Base(i64),
+    /// `p`, multiplying by a power of two.
+    Binary(i64),
+}
+
+/// Splits the exponent off the given radix string, returning the digits
+/// before it and the exponent, if any. The [Base] must be valid.
+///
+/// `p` is only an exponent marker in bases 2, 4, 8 and 16; in higher bases
+/// it's a digit, and other bases can't scale by two by moving the point.
+pub(crate) fn split_exponent(s: &str, base: Base) -> Result<(&str, Option<Exponent>), ParseRadixError> {
+    let binary = matches!(base, 2 | 4 | 8 | 16);
+    let Some(marker) = s.find(|c| c == '@' || (binary && (c == 'p' || c == 'P'))) else {
+        return Ok((s, None));
+    };
+    let (mantissa, exponent) = (&s[..marker], &s[marker + 1..]);
+    if mantissa.is_empty() || parse_special(mantissa).is_some() {
+        return Err(ParseRadixError::InvalidDigit(marker));
+    }
+
+    let (negative, digits) = match exponent.as_bytes().first() {
+        Some(b'-') => (true, &exponent[1..]),
+        Some(b'+') => (false, &exponent[1..]),
+        _ => (false, exponent),
+    };
+    let digits_start = s.len() - digits.len();
+    if digits.is_empty() {
+        return Err(ParseRadixError::InvalidDigit(s.len()));
+    }
+    let mut value = 0i64;
+    for (i, c) in digits.bytes().enumerate() {
+        if !c.is_ascii_digit() {
+            return Err(ParseRadixError::InvalidDigit(digits_start + i));
+        }
+        value = value.saturating_mul(10).saturating_add((c - b'0') as i64);
+    }
+    let value = if negative { -value } else { value };
+
+    Ok((mantissa, Some(if s.as_bytes()[marker] == b'@' { Exponent::Base(value) } else { Exponent::Binary(value) })))
+}
+
+/// Splits the given finite radix string, which may have an exponent, into its
+/// sign and digits scaled by the exponent. The [Base] must be valid.
+pub(crate) fn split_scientific(s: &str, base: Base) -> Result<Digits, ParseRadixError> {
+    let (mantissa, exponent) = split_exponent(s, base)?;
+    let mut digits = split_digits(mantissa, base)?;
+    match exponent {
+        Some(Exponent::Base(exponent)) => digits.scale(exponent),
+        Some(Exponent::Binary(exponent)) => {
+            // 2^exponent is base^q * 2^r, with 2^r a single digit.
+            let bits = base.trailing_zeros() as i64;
+            digits.mul_small(1 << exponent.rem_euclid(bits), base);
+            digits.scale(exponent.div_euclid(bits));
+        }
+        None => {}
+    }
+    Ok(digits)
+}
+
+/// Splits the given finite radix string into its sign and digits. The [Base]
+/// must be valid.
+pub(crate) fn split_digits(s: &str, base: Base) -> Result<Digits, ParseRadixError> {
+    split_digits_with(s, base, '.', true)
+}
+
+/// Splits the given finite radix string into its sign and digits, using the
+/// given radix point character and optionally accepting a radix point with no
+/// digits before it. The [Base] must be valid.
+pub(crate) fn split_digits_with(
+    s: &str,
+    base: Base,
+    radix_point: char,
+    require_integer: bool,
+) -> Result<Digits, ParseRadixError> {
+    let bytes = s.as_bytes();
+    let (negative, start) = match bytes.first() {
+        None => return Err(ParseRadixError::Empty),
+        Some(b'-') => (true, 1),
+        Some(b'+') => (false, 1),
+        _ => (false, 0),
+    };
+
+    // Digits are ASCII, so only the radix point can advance by more than a byte
+    // and i always stays on a char boundary.
+    let mut digits = Vec::with_capacity(bytes.len());
+    let mut point = None;
+    #[cfg(feature = "simd")]
+    let vectorize = !radix_point.is_digit(base as u32);
+    let mut i = start;
+    while i < bytes.len() {
+        #[cfg(feature = "simd")]
+        if vectorize {
+            i += crate::simd::convert_digit_chunks(&bytes[i..], base, &mut digits);
+        }
+
+        let Some(&c) = bytes.get(i) else { break };
+        if point.is_none() && (i > start || !require_integer) && s[i..].starts_with(radix_point) {
+            point = Some(digits.len());
+            i += radix_point.len_utf8();
+            continue;
+        }
+        match digit_value(c, base) {
+            Some(digit) => digits.push(digit),
+            None => return Err(ParseRadixError::InvalidDigit(i)),
+        }
+        i += 1;
+    }
+    if digits.is_empty() || point == Some(digits.len()) {
+        return Err(ParseRadixError::InvalidDigit(bytes.len()));
+    }
+
+    let point = point.unwrap_or(digits.len());
+    Ok(Digits { negative, digits, point })
+}
+
+/// Scans the longest radix numeral at the start of the given string in the
+/// given [Base], returning its length in bytes and its value, or [None] if the
+/// string doesn't start with a numeral.
+///
+/// Numerals use the syntax accepted by [FromRadixStr::from_radix_str], without
+/// an exponent. A radix
+/// point not followed by a digit ends the numeral before the point, so a
+/// tokenizer can handle it (e.g. as member access).
+///
+/// Returns [InvalidBaseError] if the given [Base] is out of range of
+/// [MIN_BASE] and [MAX_BASE] (inclusive).
+///
+/// Example:
+/// ```rust
+/// use radix_ecmascript::scan_radix_literal;
+///
+/// assert_eq!(scan_radix_literal("1a.8)", 16).unwrap(), Some((4, 26.5)));
+/// assert_eq!(scan_radix_literal("ff.toString()", 16).unwrap(), Some((2, 255.0)));
+/// assert_eq!(scan_radix_literal("xyz", 16).unwrap(), None);
+/// ```
+pub fn scan_radix_literal(s: &str, base: Base) -> Result<Option<(usize, f64)>, InvalidBaseError> {
+    if !(MIN_BASE..=MAX_BASE).contains(&base) {
+        return Err(InvalidBaseError(base));
+    }
+
+    let bytes = s.as_bytes();
+    let sign_len = matches!(bytes.first(), Some(b'-' | b'+')) as usize;
+    let is_digit = |c: &&u8| digit_value(**c, base).is_some();
+    let mut len = 0;
+    let integer_len = bytes[sign_len..].iter().take_while(is_digit).count();
+    if integer_len > 0 {
+        len = sign_len + integer_len;
+        if bytes.get(len) == Some(&b'.') {
+            let fraction_len = bytes[len + 1..].iter().take_while(is_digit).count();
+            if fraction_len > 0 {
+                len += 1 + fraction_len;
+            }
+        }
+    }
+
+    // In high bases `NaN` and `Infinity` are also digits; like parsing,
+    // prefer the special value on a tie.
+    let special_len = if s[sign_len..].starts_with("Infinity") {
+        sign_len + "Infinity".len()
+    } else if s.starts_with("NaN") {
+        "NaN".len()
+    } else {
+        0
+    };
+    if special_len == 0 && len == 0 {
+        return Ok(None);
+    }
+    let len = len.max(special_len);
+
+    Ok(Some((len, parse_f64(&s[..len], base).expect("scanned numeral should parse"))))
+}
+
+/// Parses the longest radix numeral at the start of the given bytes in the
+/// given [Base], returning its value and the number of bytes consumed, like
+/// C's `strtod` returns a value and an end pointer.
+///
+/// As in `strtod`, leading ASCII whitespace is skipped and a radix point
+/// directly after the integer digits is consumed, and if no numeral is found,
+/// `(0.0, 0)` is returned. Numerals otherwise use the syntax accepted by
+/// [FromRadixStr::from_radix_str] without an exponent, so `Infinity` and `NaN`
+/// are spelled as in ECMAScript. Values out of range become infinity or zero.
+///
+/// Returns [InvalidBaseError] if the given [Base] is out of range of
+/// [MIN_BASE] and [MAX_BASE] (inclusive).
+///
+/// Example:
+/// ```rust
+/// use radix_ecmascript::parse_radix_partial;
+///
+/// assert_eq!(parse_radix_partial(b"  ff.8 px", 16).unwrap(), (255.5, 6));
+/// assert_eq!(parse_radix_partial(b"12.", 10).unwrap(), (12.0, 3));
+/// assert_eq!(parse_radix_partial(b"px", 16).unwrap(), (0.0, 0));
+/// ```
+pub fn parse_radix_partial(bytes: &[u8], base: Base) -> Result<(f64, usize), InvalidBaseError> {
+    // Numerals are ASCII, so they're always within the valid UTF-8 prefix.
+    let s = match std::str::from_utf8(bytes) {
+        Ok(s) => s,
+        Err(e) => std::str::from_utf8(&bytes[..e.valid_up_to()]).unwrap(),
+    };
+    let start = s.len() - s.trim_start_matches([' ', '\t', '\n', '\x0B', '\x0C', '\r']).len();
+    let Some((mut len, value)) = scan_radix_literal(&s[start..], base)? else {
+        return Ok((0.0, 0));
+    };
+
+    let numeral = &s[start..start + len];
+    if !numeral.contains('.') && parse_special(numeral).is_none() && s[start + len..].starts_with('.') {
+        len += 1;
+    }
+    Ok((value, start + len))
+}
+
+/// Parses the given radix string into an f64.
+pub(crate) fn parse_f64(s: &str, base: Base) -> Result<f64, ParseRadixError> {
+    if !(MIN_BASE..=MAX_BASE).contains(&base) {
+        return Err(InvalidBaseError(base).into());
+    }
+    if let Some(value) = parse_special(s) {
+        return Ok(value);
+    }
+
+    Ok(split_scientific(s, base)?.to_f64(base))
+}
+
+/// Parses a radix string in the given [Base] whose fraction may end with a
+/// repeating period in parentheses, as in `0.(142857)` for 1/7 or `0.1(6)` for
+/// 1/6, returning the f64 nearest to its exact value (ties to even).
+///
+/// Inputs of more than about 4000 bits are rounded from a long expansion
+/// instead, which in odd bases can round the wrong way when the value is
+/// exactly halfway between two f64s.
+///
+/// Strings without a period use the syntax accepted by
+/// [FromRadixStr::from_radix_str]. A period needs a radix point before it,
+/// though the fraction digits between the two are optional.
+///
+/// Returns [ParseRadixError] if the given [Base] is out of range of
+/// [MIN_BASE] and [MAX_BASE] (inclusive) or the string is malformed.
+///
+/// Example:
+/// ```rust
+/// use radix_ecmascript::from_repeating_radix_str;
+///
+/// assert_eq!(from_repeating_radix_str("0.(142857)", 10).unwrap(), 1.0 / 7.0);
+/// assert_eq!(from_repeating_radix_str("0.(3)", 16).unwrap(), 0.2);
+/// ```
+pub fn from_repeating_radix_str(s: &str, base: Base) -> Result<f64, ParseRadixError> {
+    let Some(open) = s.find('(') else {
+        return parse_f64(s, base);
+    };
+    if !(MIN_BASE..=MAX_BASE).contains(&base) {
+        return Err(InvalidBaseError(base).into());
+    }
+
+    let prefix = &s[..open];
+    if !prefix.contains('.') {
+        return Err(ParseRadixError::InvalidDigit(open));
+    }
+    let digits = split_digits(prefix.strip_suffix('.').unwrap_or(prefix), base)?;
+    let Some(period) = s[open + 1..].strip_suffix(')') else {
+        return Err(ParseRadixError::InvalidDigit(s.len()));
+    };
+    if period.is_empty() {
+        return Err(ParseRadixError::InvalidDigit(open + 1));
+    }
+    let period = period
+        .bytes()
+        .enumerate()
+        .map(|(i, c)| digit_value(c, base).ok_or(ParseRadixError::InvalidDigit(open + 1 + i)))
+        .collect::<Result<Vec<u8>, _>>()?;
+    let value = repeating_to_f64(&digits, &period, base);
+    Ok(if digits.negative { -value } else { value })
+}
+
+/// Returns the f64 nearest to the magnitude of the given digits followed by
+/// the given period repeated forever (ties to even).
+fn repeating_to_f64(digits: &Digits, period: &[u8], base: Base) -> f64 {
+    let log2_base = (base as f64).log2();
+    let significant_len = digits.digits.iter().skip_while(|d| **d == 0).count();
+    let fraction_len = digits.fraction().len();
+    let bits = (significant_len.max(fraction_len) + period.len()) as f64 * log2_base;
+
+    // P is the value of the digits as an integer and R that of the period, so
+    // with k fraction digits and an r-digit period, the value is
+    // (P * base^r + R - P) / (base^(k + r) - base^k). Leave room for the shifts
+    // in ratio_to_f64.
+    if bits + 64.0 <= (BIGIT_CAPACITY as u32 * BIGIT_SIZE) as f64 {
+        let mut numerator = Bignum::zero();
+        let mut prefix = Bignum::zero();
+        for &digit in &digits.digits {
+            prefix.mul_small(base as u32);
+            prefix.add_small(digit as u32);
+        }
+        for &digit in digits.digits.iter().chain(period) {
+            numerator.mul_small(base as u32);
+            numerator.add_small(digit as u32);
+        }
+        numerator.sub(&prefix);
+        let mut denominator = Bignum::pow(base as u32, (fraction_len + period.len()) as u32);
+        denominator.sub(&Bignum::pow(base as u32, fraction_len as u32));
+        return ratio_to_f64(&numerator, &denominator);
+    }
+
+    // Too long for exact arithmetic. A period of zeros adds nothing, and one of
+    // base - 1 digits adds one unit in the last place.
+    if period.iter().all(|d| *d == 0) {
+        return digits_to_f64(digits.integer(), digits.fraction(), base);
+    }
+    if period.iter().all(|d| *d == base - 1) {
+        let mut all = digits.digits.clone();
+        let carry = all.iter_mut().rev().all(|d| {
+            *d = (*d + 1) % base;
+            *d == 0
+        });
+        if carry {
+            all.insert(0, 1);
+        }
+        let point = all.len() - fraction_len;
+        return digits_to_f64(&all[..point], &all[point..], base);
+    }
+
+    // Otherwise repeat the period past the leading zeros and the significant
+    // digits digits_to_f64 keeps, leaving a non-zero tail that rounds like the
+    // infinite expansion unless the value is exactly halfway between two f64s.
+    let repeats = (max_significant_digits(base) + digits.digits.len()) / period.len() + 2;
+    let mut fraction = digits.fraction().to_vec();
+    for _ in 0..repeats {
+        fraction.extend_from_slice(period);
+    }
+    digits_to_f64(digits.integer(), &fraction, base)
+}
+
+/// Parses `NaN` and signed `Infinity`, returning [None] for any other string.
+pub(crate) fn parse_special(s: &str) -> Option<f64> {
+    if s == "NaN" {
+        return Some(f64::NAN);
+    }
+    match s.strip_prefix(['-', '+']).unwrap_or(s) {
+        "Infinity" if s.starts_with('-') => Some(f64::NEG_INFINITY),
+        "Infinity" => Some(f64::INFINITY),
+        _ => None,
+    }
+}
+
+/// The bits of precision [digits_to_f64] keeps from long inputs: enough for
+/// every bit down to the smallest subnormal of the widest value, and two more.
+const SIGNIFICANT_BITS: u32 = 1074 + 53 + 2;
+
+// A truncated input below one is divided by the base to the power of its
+// digits and leading zeros: up to SIGNIFICANT_BITS, 1076 and five digits of
+// base 36 rounding, which round_ratio shifts by up to 54 more bits.
+const _: () = assert!(SIGNIFICANT_BITS + 1076 + 5 * 6 + 54 <= BIGIT_CAPACITY as u32 * BIGIT_SIZE);
+
+/// Returns the number of significant digits in the given [Base] that
+/// [digits_to_f64] converts exactly. Longer inputs are truncated to as many
+/// and then compared digit by digit with the nearest halfway value, if the
+/// dropped digits could decide the rounding.
+fn max_significant_digits(base: Base) -> usize {
+    (SIGNIFICANT_BITS as f64 / (base as f64).log2()).ceil() as usize + 2
+}
+
+/// Returns the f64 nearest to the value of the given integer and fractional
+/// digits in the given base (ties to even).
+pub(crate) fn digits_to_f64(integer: &[u8], fraction: &[u8], base: Base) -> f64 {
+    match digits_to_parts(integer, fraction, base, F64_FORMAT) {
+        Some((significand, exponent)) => from_diy_fp(significand, exponent),
+        None => f64::INFINITY,
+    }
+}
+
+/// Rounds the value of the given integer and fractional digits in the given
+/// base to `significand * 2^exponent` in the given [BinaryFormat] (ties to
+/// even), returning [None] if it's infinite. The bounds for f64 also hold for
+/// narrower floats.
+fn digits_to_parts(
+    integer: &[u8],
+    fraction: &[u8],
+    base: Base,
+    format: BinaryFormat,
+) -> Option<(u64, i32)> {
+    let log2_base = (base as f64).log2();
+
+    // Drop leading zeros, keeping track of the position of the first
+    // significant digit relative to the radix point.
+    let all_digits = integer.iter().chain(fraction);
+    let leading_zeros = all_digits.clone().take_while(|d| **d == 0).count();
+    let significant: Vec<u8> = all_digits.skip(leading_zeros).copied().collect();
+    let trailing_zeros = significant.iter().rev().take_while(|d| **d == 0).count();
+    let significant = &significant[..significant.len() - trailing_zeros];
+    if significant.is_empty() {
+        return Some((0, 0));
+    }
+
+    // The value is in [base^(point - 1), base^point).
+    let point = integer.len() as i64 - leading_zeros as i64;
+    if (point - 1) as f64 * log2_base >= 1025.0 {
+        return None;
+    }
+    if point as f64 * log2_base <= -1076.0 {
+        return Some((0, 0));
+    }
+
+    // Truncate overly long inputs, remembering whether any dropped digit was non-zero.
+    let max_digits = max_significant_digits(base);
+    let (truncated, sticky) = if significant.len() > max_digits {
+        (&significant[..max_digits], true)
+    } else {
+        (significant, false)
+    };
+    trace_event!("bignum parse", digits = truncated.len(), truncated = sticky);
+    count!(BIGNUM_PARSES);
+
+    // value = numerator * base^scale.
+    let mut numerator = Bignum::zero();
+    for &digit in truncated {
+        numerator.mul_small(base as u32);
+        numerator.add_small(digit as u32);
+    }
+    let mut scale = point - truncated.len() as i64;
+    if sticky {
+        // A trailing one keeps the value strictly above the truncated digits.
+        numerator.mul_small(base as u32);
+        numerator.add_small(1);
+        scale -= 1;
+    }
+    let mut denominator = Bignum::from_u64(1);
+    if scale >= 0 {
+        for _ in 0..scale {
+            numerator.mul_small(base as u32);
+        }
+    } else {
+        denominator = Bignum::pow(base as u32, -scale as u32);
+    }
+
+    let rounded = round_ratio(&numerator, &denominator, format);
+    if sticky {
+        round_exactly(significant, point, base, rounded, format)
+    } else {
+        rounded
+    }
+}
+
+/// Corrects the rounding of a truncated input, given as the parts returned by
+/// [round_ratio], to that of all of the given significant digits, whose value
+/// is in [base^(point - 1), base^point).
+///
+/// The truncated and full values are far closer together than a unit in the
+/// last place, so they can only round differently if the halfway value on
+/// either side of the rounded one lies between them.
+fn round_exactly(
+    significant: &[u8],
+    point: i64,
+    base: Base,
+    rounded: Option<(u64, i32)>,
+    format: BinaryFormat,
+) -> Option<(u64, i32)> {
+    let BinaryFormat { significand_bits, denormal_exponent, max_exponent } = format;
+    let hidden_bit = 1 << (significand_bits - 1);
+
+    // Normalize a carry out of the significand, and treat infinity as the
+    // power of two it rounded to.
+    let (mut significand, mut exponent) = rounded.unwrap_or((hidden_bit << 1, max_exponent - 1));
+    if significand == hidden_bit << 1 {
+        significand = hidden_bit;
+        exponent += 1;
+    }
+    let parts = |significand, exponent| (exponent < max_exponent).then_some((significand, exponent));
+
+    // The halfway values are (4 * significand +- 2) * 2^(exponent - 2), except
+    // below a power of two, where the spacing halves.
+    if exponent < max_exponent {
+        let above = 4 * significand + 2;
+        match compare_digits(significant, point, base, above, exponent - 2) {
+            Ordering::Greater => return parts(significand + 1, exponent),
+            Ordering::Equal if significand & 1 == 1 => return parts(significand + 1, exponent),
+            _ => {}
+        }
+    }
+    if significand == 0 {
+        return parts(significand, exponent);
+    }
+    let (below, previous) = if significand == hidden_bit && exponent > denormal_exponent {
+        (4 * significand - 1, ((hidden_bit << 1) - 1, exponent - 1))
+    } else {
+        (4 * significand - 2, (significand - 1, exponent))
+    };
+    match compare_digits(significant, point, base, below, exponent - 2) {
+        Ordering::Less => Some(previous),
+        Ordering::Equal if significand & 1 == 1 => Some(previous),
+        _ => parts(significand, exponent),
+    }
+}
+
+/// Compares the value of the given significant digits, which is in
+/// [base^(point - 1), base^point), with `significand * 2^exponent`, generating
+/// the digits of the latter until they differ.
+fn compare_digits(significant: &[u8], point: i64, base: Base, significand: u64, exponent: i32) -> Ordering {
+    // The other value divided by base^(point - 1), as a ratio whose integer
+    // part is the digit at the first significant position.
+    let mut numerator = Bignum::from_u64(significand);
+    let mut denominator = Bignum::from_u64(1);
+    if exponent >= 0 {
+        numerator.shl(exponent as u32);
+    } else {
+        denominator.shl(-exponent as u32);
+    }
+    for _ in 0..(point - 1).abs() {
+        if point > 1 {
+            denominator.mul_small(base as u32);
+        } else {
+            numerator.mul_small(base as u32);
+        }
+    }
+
+    for &digit in significant {
+        // The quotient can exceed one digit only at the first position.
+        let other = numerator.div_rem(&denominator).to_u64().unwrap_or(u64::MAX);
+        match (digit as u64).cmp(&other) {
+            Ordering::Equal => numerator.mul_small(base as u32),
+            ordering => return ordering,
+        }
+    }
+    if numerator.is_zero() { Ordering::Equal } else { Ordering::Less }
+}
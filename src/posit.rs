@@ -0,0 +1,150 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+
+//! [ToRadixStr] implementations for the posit types of
+//! [softposit](https://docs.rs/softposit), enabled by the `softposit` feature.
+//!
+//! Posits are decoded exactly from their regime, exponent and fraction fields,
+//! and get as many fraction digits as are needed to tell them apart from the
+//! next posit away from zero, just as floats do from the next float. NaR, the
+//! posit that isn't a real number, is written as `"NaR"`.
+
+use softposit::{PxE1, PxE2, P16E1, P32E2, P8E0};
+use crate::float_format::pow2;
+use crate::{pool, write_radix_delta, Base, InvalidBaseError, ToRadixStr, MAX_BASE, MIN_BASE};
+
+/// Returns the value of the posit of `n` bits and `es` exponent bits held in
+/// the top of bits, which is exact, as every posit of up to 32 bits fits in
+/// an f64.
+fn decode(bits: u32, n: u32, es: u32) -> f64 {
+    if bits == 0 {
+        return 0.0;
+    }
+
+    // Negative posits are the two's complement of their magnitude.
+    let negative = bits >> 31 == 1;
+    let magnitude = if negative { bits.wrapping_neg() } else { bits };
+
+    // The regime is a run of identical bits after the sign, ended by the
+    // opposite bit or the end of the posit.
+    let mut rest = (magnitude as u64) << 33;
+    let mut remaining = n - 1;
+    let run = if rest >> 63 == 1 { rest.leading_ones() } else { rest.leading_zeros() }.min(remaining);
+    let regime = if rest >> 63 == 1 { run as i32 - 1 } else { -(run as i32) };
+    let used = (run + 1).min(remaining);
+    rest = rest.checked_shl(used).unwrap_or(0);
+    remaining -= used;
+
+    // Exponent bits cut off by the end of the posit are zero.
+    let exponent = rest.checked_shr(64 - es).unwrap_or(0) as i32;
+    rest = rest.checked_shl(es).unwrap_or(0);
+    remaining -= es.min(remaining);
+
+    // What's left is the fraction, after an implicit leading 1.
+    let fraction = rest.checked_shr(64 - remaining).unwrap_or(0);
+    let significand = (1 << remaining) | fraction;
+    let scale = (regime << es) + exponent - remaining as i32;
+    let value = significand as f64 * pow2(scale);
+
+    if negative {
+        -value
+    } else {
+        value
+    }
+}
+
+/// Returns the radix string representation of the posit of `n` bits and `es`
+/// exponent bits held in the top of bits.
+fn posit_to_radix_str(bits: u32, n: u32, es: u32, base: Base) -> Result<String, InvalidBaseError> {
+    if !(MIN_BASE..=MAX_BASE).contains(&base) {
+        return Err(InvalidBaseError(base));
+    }
+
+    if bits == 1 << 31 {
+        return Ok("NaR".to_owned());
+    }
+    let value = decode(bits, n, es);
+
+    // Half the gap to the next posit away from zero, or for the largest
+    // posit, which is an integer, to the one below it.
+    let ulp = 1 << (32 - n);
+    let magnitude = if bits >> 31 == 1 { bits.wrapping_neg() } else { bits };
+    let next = if magnitude == (1 << 31) - ulp { magnitude - ulp } else { magnitude + ulp };
+    let delta = 0.5 * (decode(next, n, es) - value.abs()).abs();
+
+    Ok(pool::with_buffer(|buf| {
+        let range = write_radix_delta(value, delta, base, buf);
+        buf[range].iter().map(|c| *c as char).collect()
+    }))
+}
+
+impl ToRadixStr for P8E0 {
+    fn to_radix_str(self, base: Base) -> Result<String, InvalidBaseError> {
+        posit_to_radix_str((self.to_bits() as u32) << 24, 8, 0, base)
+    }
+}
+
+impl ToRadixStr for P16E1 {
+    fn to_radix_str(self, base: Base) -> Result<String, InvalidBaseError> {
+        posit_to_radix_str((self.to_bits() as u32) << 16, 16, 1, base)
+    }
+}
+
+impl ToRadixStr for P32E2 {
+    fn to_radix_str(self, base: Base) -> Result<String, InvalidBaseError> {
+        posit_to_radix_str(self.to_bits(), 32, 2, base)
+    }
+}
+
+/// `N`-bit posits are held in the top `N` bits.
+impl<const N: u32> ToRadixStr for PxE1<N> {
+    fn to_radix_str(self, base: Base) -> Result<String, InvalidBaseError> {
+        posit_to_radix_str(self.to_bits(), N, 1, base)
+    }
+}
+
+/// `N`-bit posits are held in the top `N` bits.
+impl<const N: u32> ToRadixStr for PxE2<N> {
+    fn to_radix_str(self, base: Base) -> Result<String, InvalidBaseError> {
+        posit_to_radix_str(self.to_bits(), N, 2, base)
+    }
+}
@@ -0,0 +1,82 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! [Kani](https://model-checking.github.io/kani/) proof harnesses for the
+//! conversion's buffer handling. Run with `cargo kani`.
+//!
+//! Kani checks every buffer index and cursor subtraction for all inputs, and the
+//! unwinding assertions prove that each loop, including the carry back-trace,
+//! terminates within the given bound.
+
+use crate::{max_len, write_radix, Base, BUFFER_LEN, MAX_BASE, MIN_BASE};
+
+/// Every finite value in every base stays within the buffer and [max_len].
+#[kani::proof]
+#[kani::unwind(1200)]
+fn write_radix_in_bounds() {
+    let value = f64::from_bits(kani::any());
+    let base: Base = kani::any();
+    kani::assume(value.is_finite());
+    kani::assume((MIN_BASE..=MAX_BASE).contains(&base));
+
+    let mut buf = [0; BUFFER_LEN];
+    let range = write_radix(value, base, &mut buf);
+    assert!(range.start < range.end && range.end <= BUFFER_LEN);
+    assert!(range.len() <= max_len(base));
+}
+
+/// The carry back-trace stops at the radix point, which is the only place an
+/// integer carry can come from. Values just below a power of two maximize
+/// carries.
+#[kani::proof]
+#[kani::unwind(1200)]
+fn write_radix_carry_terminates() {
+    let biased_exponent: u64 = kani::any();
+    let base: Base = kani::any();
+    kani::assume((1..=2047).contains(&biased_exponent));
+    kani::assume((MIN_BASE..=MAX_BASE).contains(&base));
+
+    // All significand bits set, just below 2^(biased_exponent - 1023).
+    let value = f64::from_bits((biased_exponent << 52) - 1);
+    let mut buf = [0; BUFFER_LEN];
+    let range = write_radix(value, base, &mut buf);
+    assert!(range.end <= BUFFER_LEN);
+}
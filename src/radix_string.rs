@@ -0,0 +1,380 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! Radix strings that remember their base.
+
+use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
+use crate::{normalize_radix_str, radix_str, Base, NegativeZero, Result};
+#[cfg(feature = "parse")]
+use crate::{parse::parse_f64, ParseRadixError};
+
+/// A radix string together with the [Base] it is written in.
+///
+/// Example:
+/// ```rust
+/// use radix_ecmascript::RadixString;
+///
+/// let s = RadixString::new(0.5, 16).unwrap();
+/// assert_eq!(s.as_str(), "0.8");
+/// assert_eq!(s.base(), 16);
+/// ```
+///
+/// Equality and hashing go by value within a base, as if both strings were
+/// normalized with [NegativeZero::Collapse], so `"ff.80"` and `"FF.8"` in
+/// base 16 are the same key. Strings in different bases are never equal.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize))]
+pub struct RadixString {
+    text: String,
+    base: Base,
+}
+
+impl RadixString {
+    /// Returns the radix string representation of the given value, as
+    /// [ToRadixStr::to_radix_str] does.
+    ///
+    /// Returns [InvalidBaseError](crate::InvalidBaseError) if the given [Base]
+    /// is out of range of [MIN_BASE](crate::MIN_BASE) and
+    /// [MAX_BASE](crate::MAX_BASE) (inclusive).
+    pub fn new(value: impl Into<f64>, base: Base) -> Result<Self> {
+        Ok(Self {
+            text: radix_str(value.into(), base)?,
+            base,
+        })
+    }
+
+    /// Wraps an existing radix string in the given [Base], checking that it
+    /// has the syntax accepted by [FromRadixStr::from_radix_str], without an
+    /// exponent.
+    ///
+    /// Returns [ParseRadixError] if the given [Base] is out of range of
+    /// [MIN_BASE](crate::MIN_BASE) and [MAX_BASE](crate::MAX_BASE) (inclusive)
+    /// or the string is malformed.
+    #[cfg(feature = "parse")]
+    pub fn from_radix_str(text: impl Into<String>, base: Base) -> Result<Self, ParseRadixError> {
+        let text = text.into();
+        parse_f64(&text, base)?;
+        if let (mantissa, Some(_)) = crate::parse::split_exponent(&text, base)? {
+            return Err(ParseRadixError::InvalidDigit(mantissa.len()));
+        }
+        Ok(Self { text, base })
+    }
+
+    /// Returns the [Base] the string is written in.
+    pub fn base(&self) -> Base {
+        self.base
+    }
+
+    /// Returns the string.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the value of the string, rounded to the nearest f64.
+    #[cfg(feature = "parse")]
+    pub fn to_f64(&self) -> f64 {
+        // The syntax was checked when self was created.
+        parse_f64(&self.text, self.base).unwrap()
+    }
+
+    /// Reports if the string has a `-` sign, which `-Infinity` does.
+    pub fn is_negative(&self) -> bool {
+        self.text.starts_with('-')
+    }
+
+    /// Reports if the string is a number rather than `NaN` or an infinity.
+    pub fn is_finite(&self) -> bool {
+        !matches!(self.unsigned(), "NaN" | "Infinity")
+    }
+
+    /// Returns the digits before the radix point, without the sign. This is
+    /// `NaN` or `Infinity` for those strings.
+    ///
+    /// Example:
+    /// ```rust
+    /// use radix_ecmascript::RadixString;
+    ///
+    /// let s = RadixString::new(-255.5, 16).unwrap();
+    /// assert_eq!(s.integer_part(), "ff");
+    /// assert_eq!(s.fraction_part(), "8");
+    /// ```
+    pub fn integer_part(&self) -> &str {
+        let unsigned = self.unsigned();
+        unsigned.split_once('.').map_or(unsigned, |(integer, _)| integer)
+    }
+
+    /// Returns the digits after the radix point, which are empty if there is
+    /// none.
+    pub fn fraction_part(&self) -> &str {
+        self.unsigned().split_once('.').map_or("", |(_, fraction)| fraction)
+    }
+
+    /// Returns the number of digits after the radix point.
+    pub fn num_fraction_digits(&self) -> usize {
+        self.fraction_part().len()
+    }
+
+    /// Returns the value of the digit at the given index, counting from the
+    /// most significant digit and skipping the sign and radix point.
+    ///
+    /// Returns [None] if the index is past the last digit, or if the string
+    /// isn't [finite](Self::is_finite).
+    ///
+    /// Example:
+    /// ```rust
+    /// use radix_ecmascript::RadixString;
+    ///
+    /// let s = RadixString::new(255.5, 16).unwrap();
+    /// assert_eq!(s.digit_at(0), Some(15));
+    /// assert_eq!(s.digit_at(2), Some(8));
+    /// assert_eq!(s.digit_at(3), None);
+    /// ```
+    pub fn digit_at(&self, i: usize) -> Option<u8> {
+        if !self.is_finite() {
+            return None;
+        }
+
+        let c = self.unsigned().bytes().filter(|c| *c != b'.').nth(i)?;
+        (c as char).to_digit(36).map(|digit| digit as u8)
+    }
+
+    /// Returns the string rounded to at most the given number of digits after
+    /// the radix point, to nearest with ties to even, without converting it
+    /// to a number. Rounding up carries through to the integer digits, and
+    /// a zero result loses its sign.
+    ///
+    /// Strings with no more fraction digits than that, and strings that
+    /// aren't [finite](Self::is_finite), are returned as they are.
+    ///
+    /// Example:
+    /// ```rust
+    /// use radix_ecmascript::RadixString;
+    ///
+    /// let s = RadixString::new(255.96875, 16).unwrap();
+    /// assert_eq!(s.as_str(), "ff.f8");
+    /// assert_eq!(s.round_to(1).as_str(), "100.0");
+    /// assert_eq!(s.round_to(0).as_str(), "100");
+    /// ```
+    pub fn round_to(&self, fraction_digits: usize) -> RadixString {
+        let fraction = self.fraction_part();
+        if !self.is_finite() || fraction.len() <= fraction_digits {
+            return self.clone();
+        }
+
+        let base = self.base as u32;
+        let value = |c: u8| (c as char).to_digit(36).unwrap_or(0);
+        let sign = &self.text[..self.text.len() - self.unsigned().len()];
+        let integer = self.integer_part();
+        let (kept, dropped) = fraction.as_bytes().split_at(fraction_digits);
+        let mut digits: Vec<u8> = integer.bytes().chain(kept.iter().copied()).collect();
+
+        // Compare the dropped digits with one half, which is b/2 followed by
+        // zeros in even bases, and (b-1)/2 repeating in odd ones.
+        let even = base.is_multiple_of(2);
+        let half = |i: usize| match (even, i) {
+            (true, 0) => base / 2,
+            (true, _) => 0,
+            (false, _) => (base - 1) / 2,
+        };
+        let round_up = match dropped.iter().enumerate().map(|(i, c)| value(*c).cmp(&half(i))).find(|o| o.is_ne()) {
+            Some(ordering) => ordering.is_gt(),
+            // Exactly half, which only even bases can write.
+            None => even && digits.last().is_some_and(|c| value(*c) % 2 == 1),
+        };
+
+        if round_up {
+            let uppercase = self.text.bytes().any(|c| c.is_ascii_uppercase());
+            let mut carry = true;
+            for c in digits.iter_mut().rev() {
+                let digit = value(*c) + 1;
+                if digit < base {
+                    let next = char::from_digit(digit, base).unwrap_or('0');
+                    *c = if uppercase { next.to_ascii_uppercase() } else { next } as u8;
+                    carry = false;
+                    break;
+                }
+                *c = b'0';
+            }
+            if carry {
+                digits.insert(0, b'1');
+            }
+        }
+
+        let (integer, fraction) = digits.split_at(digits.len() - fraction_digits);
+        let mut text = String::with_capacity(self.text.len() + 1);
+        if digits.iter().any(|c| *c != b'0') {
+            text.push_str(sign);
+        }
+        text.extend(integer.iter().map(|c| *c as char));
+        if !fraction.is_empty() {
+            text.push('.');
+            text.extend(fraction.iter().map(|c| *c as char));
+        }
+        RadixString { text, base: self.base }
+    }
+
+    /// Returns the canonical form of the string, as [normalize_radix_str]
+    /// does.
+    pub fn normalize(&self, negative_zero: NegativeZero) -> RadixString {
+        RadixString {
+            text: normalize_radix_str(&self.text, negative_zero),
+            base: self.base,
+        }
+    }
+
+    /// Returns the string without its sign.
+    fn unsigned(&self) -> &str {
+        self.text.strip_prefix(['-', '+']).unwrap_or(&self.text)
+    }
+
+    /// Returns the sign, integer digits and fraction digits of the canonical
+    /// form, which differ from it only in case.
+    fn canonical(&self) -> (bool, &str, &str) {
+        let unsigned = self.unsigned();
+        let (integer, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+        let integer = integer.trim_start_matches('0');
+        let fraction = fraction.trim_end_matches('0');
+        let zero = integer.is_empty() && fraction.is_empty();
+        (self.is_negative() && !zero, integer, fraction)
+    }
+}
+
+impl PartialEq for RadixString {
+    fn eq(&self, other: &Self) -> bool {
+        // `NaN` and `Infinity` are case-sensitive, unlike digits: in base 36,
+        // `nan` and `infinity` are numbers.
+        if !self.is_finite() || !other.is_finite() {
+            return self.base == other.base
+                && self.is_negative() == other.is_negative()
+                && self.unsigned() == other.unsigned();
+        }
+
+        let (negative, integer, fraction) = self.canonical();
+        let (other_negative, other_integer, other_fraction) = other.canonical();
+        self.base == other.base
+            && negative == other_negative
+            && integer.eq_ignore_ascii_case(other_integer)
+            && fraction.eq_ignore_ascii_case(other_fraction)
+    }
+}
+
+impl Eq for RadixString {}
+
+impl Hash for RadixString {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        if !self.is_finite() {
+            self.base.hash(state);
+            self.is_negative().hash(state);
+            // Unlike digits, these aren't lowercased, so they differ from the
+            // numbers `nan` and `infinity`.
+            state.write(self.unsigned().as_bytes());
+            return;
+        }
+
+        let (negative, integer, fraction) = self.canonical();
+        self.base.hash(state);
+        negative.hash(state);
+        for c in integer.bytes() {
+            state.write_u8(c.to_ascii_lowercase());
+        }
+        // Neither part holds a radix point, so it separates them.
+        state.write_u8(b'.');
+        for c in fraction.bytes() {
+            state.write_u8(c.to_ascii_lowercase());
+        }
+    }
+}
+
+impl Display for RadixString {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl AsRef<str> for RadixString {
+    fn as_ref(&self) -> &str {
+        &self.text
+    }
+}
+
+impl From<RadixString> for String {
+    fn from(s: RadixString) -> Self {
+        s.text
+    }
+}
+
+/// The archived form of a [RadixString], which is read in place.
+///
+/// Archives aren't trusted to hold well-formed radix strings: deserializing
+/// checks the syntax as [RadixString::from_radix_str] does.
+#[cfg(feature = "rkyv")]
+impl ArchivedRadixString {
+    /// Returns the [Base] the string is written in, which may be invalid.
+    pub fn base(&self) -> Base {
+        self.base
+    }
+
+    /// Returns the string.
+    pub fn as_str(&self) -> &str {
+        self.text.as_str()
+    }
+
+    /// Returns the value of the string, rounded to the nearest f64.
+    ///
+    /// Returns [ParseRadixError] if the archived base or string is malformed.
+    pub fn to_f64(&self) -> Result<f64, ParseRadixError> {
+        parse_f64(self.as_str(), self.base)
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl<D> rkyv::Deserialize<RadixString, D> for ArchivedRadixString
+where
+    D: rkyv::rancor::Fallible + ?Sized,
+    D::Error: rkyv::rancor::Source,
+{
+    fn deserialize(&self, _: &mut D) -> Result<RadixString, D::Error> {
+        use rkyv::rancor::Source;
+
+        RadixString::from_radix_str(self.as_str(), self.base).map_err(D::Error::new)
+    }
+}
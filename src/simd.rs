@@ -0,0 +1,104 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! Vectorized digit conversion, enabled by the `simd` feature.
+//!
+//! Long runs of digits are validated and converted 16 bytes at a time with
+//! SSE2 on x86_64. Other targets fall back to the scalar parser.
+//! The intrinsics are unsafe, so the `simd` feature enables the `fast` feature.
+
+use crate::Base;
+
+/// The number of bytes converted at a time.
+const CHUNK_LEN: usize = 16;
+
+/// Converts whole 16-byte chunks from the start of the given bytes that are
+/// entirely digits in the given [Base], appending their values to digits and
+/// returning the number of bytes converted. Stops at the first chunk with a
+/// non-digit, which the scalar parser then handles.
+#[cfg(target_arch = "x86_64")]
+pub(crate) fn convert_digit_chunks(bytes: &[u8], base: Base, digits: &mut Vec<u8>) -> usize {
+    // SAFETY: SSE2 is part of the x86_64 baseline, so it is always available.
+    unsafe { convert_digit_chunks_sse2(bytes, base, digits) }
+}
+
+/// The SSE2 implementation of [convert_digit_chunks].
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse2")]
+fn convert_digit_chunks_sse2(bytes: &[u8], base: Base, digits: &mut Vec<u8>) -> usize {
+    use std::arch::x86_64::*;
+
+    let mut converted = 0;
+    for chunk in bytes.chunks_exact(CHUNK_LEN) {
+        // SAFETY: chunk is 16 bytes long, and unaligned loads are allowed.
+        let v = unsafe { _mm_loadu_si128(chunk.as_ptr() as *const __m128i) };
+
+        // Decimal digits are c - '0' < 10, and letters of either case are
+        // (c | 0x20) - 'a' < 26. Unsigned x < n is min(x, n - 1) == x.
+        let decimal = _mm_sub_epi8(v, _mm_set1_epi8(b'0' as i8));
+        let is_decimal = _mm_cmpeq_epi8(_mm_min_epu8(decimal, _mm_set1_epi8(9)), decimal);
+        let letter = _mm_sub_epi8(_mm_or_si128(v, _mm_set1_epi8(0x20)), _mm_set1_epi8(b'a' as i8));
+        let is_letter = _mm_cmpeq_epi8(_mm_min_epu8(letter, _mm_set1_epi8(25)), letter);
+
+        let values = _mm_or_si128(
+            _mm_and_si128(is_decimal, decimal),
+            _mm_and_si128(is_letter, _mm_add_epi8(letter, _mm_set1_epi8(10))),
+        );
+        let in_base = _mm_cmpeq_epi8(_mm_min_epu8(values, _mm_set1_epi8(base as i8 - 1)), values);
+        let valid = _mm_and_si128(_mm_or_si128(is_decimal, is_letter), in_base);
+        if _mm_movemask_epi8(valid) != 0xFFFF {
+            break;
+        }
+
+        let mut out = [0u8; CHUNK_LEN];
+        // SAFETY: out is 16 bytes long, and unaligned stores are allowed.
+        unsafe { _mm_storeu_si128(out.as_mut_ptr() as *mut __m128i, values) };
+        digits.extend_from_slice(&out);
+        converted += CHUNK_LEN;
+    }
+    converted
+}
+
+/// Converts nothing on targets without a vectorized path.
+#[cfg(not(target_arch = "x86_64"))]
+pub(crate) fn convert_digit_chunks(_bytes: &[u8], _base: Base, _digits: &mut Vec<u8>) -> usize {
+    0
+}
@@ -0,0 +1,416 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! Conversion using only integer arithmetic on the bit pattern of the value.
+//!
+//! [Soft] emulates the f64 operations of [write_radix](crate::write_radix)
+//! with IEEE 754 round-to-nearest-even, so the digits are the same as those of
+//! [ToRadixStr::to_radix_str](crate::ToRadixStr::to_radix_str) on a conforming
+//! FPU, but don't depend on the FPU at all. Targets that keep extra precision
+//! (x87), flush subnormals to zero or lack an FPU produce identical output.
+//!
+//! The `soft-float` feature uses this for every conversion, which is faster
+//! than compiler-emitted softfloat calls on targets without an FPU, such as
+//! Cortex-M0 microcontrollers. Multiplying and dividing by the base, the
+//! operations in the digit loops, only need 64-bit integer arithmetic.
+
+use core::cmp::Ordering;
+use core::ops::Range;
+use crate::double::{
+    exponent, significand, DENORMAL_EXPONENT, EXPONENT_BIAS, EXPONENT_MASK, HIDDEN_BIT, MAX_EXPONENT, SIGNIFICAND_MASK,
+    SIGNIFICAND_SIZE, SIGN_MASK,
+};
+use crate::{radix_point_position, Base, BUFFER_LEN};
+#[cfg(feature = "std")]
+use crate::{pool, InvalidBaseError, MAX_BASE, MIN_BASE};
+
+/// A non-negative, finite f64 held as its bit pattern.
+///
+/// Bit patterns of non-negative values order the same as the values, so the
+/// derived comparisons are numeric comparisons.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct Soft(u64);
+
+impl Soft {
+    /// `0.0`.
+    pub(crate) const ZERO: Soft = Soft(0);
+    /// `0.5`.
+    pub(crate) const HALF: Soft = Soft(0x3FE0_0000_0000_0000);
+    /// `1.0`.
+    pub(crate) const ONE: Soft = Soft(0x3FF0_0000_0000_0000);
+
+    /// Returns |value|, which must be finite.
+    pub(crate) fn from_f64_abs(value: f64) -> Soft {
+        Soft(value.to_bits() & !SIGN_MASK)
+    }
+
+    /// Returns the value of the given integer, rounded to nearest.
+    pub(crate) fn from_u64(n: u64) -> Soft {
+        round_pack(n as u128, 0, false)
+    }
+
+    /// Returns the significand and exponent of self, as [significand] and
+    /// [exponent] do.
+    fn unpack(self) -> (u64, i32) {
+        let f = f64::from_bits(self.0);
+        (significand(f), exponent(f))
+    }
+
+    /// Returns the significand and exponent of self with the significand
+    /// shifted up to 53 bits, so subnormals have a smaller exponent than
+    /// [DENORMAL_EXPONENT]. Self must not be zero.
+    fn unpack_normalized(self) -> (u64, i32) {
+        let (m, e) = self.unpack();
+        let shift = m.leading_zeros() as i32 - (64 - SIGNIFICAND_SIZE);
+        (m << shift, e - shift)
+    }
+
+    /// Returns the exponent of self, as [exponent] does.
+    pub(crate) fn exponent(self) -> i32 {
+        self.unpack().1
+    }
+
+    /// Returns self rounded towards zero to an integer.
+    pub(crate) fn trunc(self) -> Soft {
+        let (_, e) = self.unpack();
+        if e >= 0 {
+            self
+        } else if e <= -SIGNIFICAND_SIZE {
+            // Values below one (including subnormals) have no integer bits.
+            Soft::ZERO
+        } else {
+            Soft(self.0 & !((1 << -e) - 1))
+        }
+    }
+
+    /// Returns self, which must be an integer below 2^64, as an integer.
+    pub(crate) fn to_u64(self) -> u64 {
+        let (m, e) = self.unpack();
+        if e < 0 { m.checked_shr(-e as u32).unwrap_or(0) } else { m << e }
+    }
+
+    /// Returns `self % base` (fmod), which is always exact.
+    pub(crate) fn rem_small(self, base: u32) -> Soft {
+        let (m, e) = self.unpack();
+        if e < 0 {
+            if self < Soft::from_u64(base as u64) {
+                return self;
+            }
+            // Values of at least base have few fraction bits.
+            return round_pack(m as u128 % ((base as u128) << -e), e, false);
+        }
+
+        let base = base as u64;
+        let mut scale = 1;
+        for _ in 0..e {
+            scale = scale * 2 % base;
+        }
+        Soft::from_u64(m % base * scale % base)
+    }
+
+    /// Returns `self + other`.
+    pub(crate) fn add(self, other: Soft) -> Soft {
+        let (a, b) = if self >= other { (self, other) } else { (other, self) };
+        if b == Soft::ZERO {
+            return a;
+        }
+
+        let ((ma, ea), (mb, eb)) = (a.unpack(), b.unpack());
+        let d = ea - eb;
+        if d <= 64 {
+            return round_pack(((ma as u128) << d) + mb as u128, eb, false);
+        }
+
+        // The larger value is normal, so the smaller one only affects rounding.
+        let (mb, lost) = shr_sticky(mb, d - 64);
+        round_pack(((ma as u128) << 64) + mb as u128, ea - 64, lost)
+    }
+
+    /// Returns `self - other`, where other must not be greater than self.
+    pub(crate) fn sub(self, other: Soft) -> Soft {
+        debug_assert!(other <= self);
+        if other == Soft::ZERO {
+            return self;
+        }
+
+        let ((ma, ea), (mb, eb)) = (self.unpack(), other.unpack());
+        let d = ea - eb;
+        if d <= 64 {
+            return round_pack(((ma as u128) << d) - mb as u128, eb, false);
+        }
+
+        // The exact difference lies strictly between m and m + 1.
+        let (mb, lost) = shr_sticky(mb, d - 64);
+        round_pack(((ma as u128) << 64) - mb as u128 - lost as u128, ea - 64, lost)
+    }
+}
+
+impl Soft {
+    /// Returns `self * base`, rounded to nearest, with a 64-bit product: a
+    /// 53-bit significand times a 6-bit base fits, which saves a 128-bit
+    /// multiply on 32-bit targets.
+    pub(crate) fn mul_small(self, base: u32) -> Soft {
+        let (m, e) = self.unpack();
+        round_pack(u128::from(m * base as u64), e, false)
+    }
+
+    /// Returns `self / base`, rounded to nearest, with a 64-bit division: the
+    /// significand shifted to the top of a u64 keeps at least 58 quotient
+    /// bits, enough to round correctly.
+    pub(crate) fn div_small(self, base: u32) -> Soft {
+        if self == Soft::ZERO {
+            return self;
+        }
+
+        let (m, e) = self.unpack_normalized();
+        let shift = 64 - SIGNIFICAND_SIZE;
+        let numerator = m << shift;
+        let base = base as u64;
+        round_pack(u128::from(numerator / base), e - shift, !numerator.is_multiple_of(base))
+    }
+}
+
+/// Returns `m >> shift` and whether any set bits were shifted out.
+fn shr_sticky(m: u64, shift: i32) -> (u64, bool) {
+    if shift >= 64 {
+        return (0, m != 0);
+    }
+    (m >> shift, m & ((1 << shift) - 1) != 0)
+}
+
+/// Returns the f64 nearest to `m * 2^e` (ties to even), where sticky reports
+/// that the exact value is slightly above `m * 2^e`. Sticky results must have
+/// more bits than a significand. Values too large for an f64 round to
+/// +Infinity.
+fn round_pack(m: u128, e: i32, sticky: bool) -> Soft {
+    if m == 0 {
+        return Soft::ZERO;
+    }
+
+    // Shift to 53 significand bits, or fewer if the value is subnormal.
+    let len = 128 - m.leading_zeros() as i32;
+    let shift = (len - SIGNIFICAND_SIZE).max(DENORMAL_EXPONENT - e);
+    let (mut m, mut e) = (m, e);
+    match shift.cmp(&0) {
+        Ordering::Greater => {
+            let (half, rest) = if shift > 128 {
+                (false, m != 0)
+            } else {
+                let below = m & (u128::MAX >> (128 - shift));
+                (below >> (shift - 1) == 1, below & !(1 << (shift - 1)) != 0)
+            };
+            m = m.checked_shr(shift as u32).unwrap_or(0);
+            e += shift;
+            if half && (rest || sticky || m & 1 == 1) {
+                m += 1;
+            }
+        }
+        Ordering::Less => {
+            debug_assert!(!sticky);
+            m <<= -shift;
+            e += shift;
+        }
+        Ordering::Equal => debug_assert!(!sticky),
+    }
+
+    // Rounding up can carry into a new bit.
+    if m >> SIGNIFICAND_SIZE != 0 {
+        m >>= 1;
+        e += 1;
+    }
+    if e >= MAX_EXPONENT {
+        return Soft(EXPONENT_MASK);
+    }
+
+    let m = m as u64;
+    if m & HIDDEN_BIT == 0 {
+        Soft(m)
+    } else {
+        Soft(((e + EXPONENT_BIAS) as u64) << (SIGNIFICAND_SIZE - 1) | m & SIGNIFICAND_MASK)
+    }
+}
+
+/// Writes the radix string representation of value into buf as
+/// [write_radix](crate::write_radix) does, returning the range of buf that was
+/// written to, but with [Soft] arithmetic. The [Base] must be valid, and
+/// delta is as for [write_radix_const](crate::write_radix_const).
+pub(crate) fn write_radix_soft(value: f64, delta: f64, base: Base, buf: &mut [u8; BUFFER_LEN]) -> Range<usize> {
+    count!(conversion, base);
+
+    // Writes a constant result.
+    let mut write_const = |s: &str| {
+        buf[..s.len()].copy_from_slice(s.as_bytes());
+        0..s.len()
+    };
+
+    // Special values are recognized from their bits.
+    let bits = value.to_bits();
+    let negative = bits & SIGN_MASK != 0;
+    match bits & !SIGN_MASK {
+        0 => return write_const("0"),
+        EXPONENT_MASK => return write_const(if negative { "-Infinity" } else { "Infinity" }),
+        abs if abs > EXPONENT_MASK => return write_const("NaN"),
+        _ => {}
+    }
+
+    // Character array used for conversion.
+    const CHARS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+    // Cursors into the buffer, starting at the radix point.
+    let point = radix_point_position(value, base);
+    let mut int_cursor = point;
+    let mut fraction_cursor = point;
+
+    let value = Soft::from_f64_abs(value);
+    let base = base as u32;
+
+    // Split the value into an integer part and a fractional part.
+    let mut integer = value.trunc();
+    let mut fraction = value.sub(integer);
+    if fraction != Soft::ZERO {
+        // A non-zero fraction is at least one ulp of the value, so it's never
+        // below delta.
+        let mut delta = Soft::from_f64_abs(delta);
+
+        // Insert decimal point.
+        buf[fraction_cursor] = b'.';
+        fraction_cursor += 1;
+
+        loop {
+            // Shift up by one digit.
+            fraction = fraction.mul_small(base);
+            delta = delta.mul_small(base);
+
+            // Write digit.
+            let digit = fraction.to_u64() as usize;
+            buf[fraction_cursor] = CHARS[digit];
+            fraction_cursor += 1;
+
+            // Calculate remainder.
+            fraction = fraction.sub(Soft::from_u64(digit as u64));
+
+            // Round to even.
+            let round_up = fraction > Soft::HALF || (fraction == Soft::HALF && digit & 1 == 1);
+            if round_up && fraction.add(delta) > Soft::ONE {
+                count!(ROUND_UPS);
+                // We need to back trace already written digits in case of carry-over.
+                loop {
+                    fraction_cursor -= 1;
+                    if fraction_cursor == point {
+                        // Carry over the integer part.
+                        integer = integer.add(Soft::ONE);
+                        break;
+                    }
+
+                    let digit = (buf[fraction_cursor] as char).to_digit(36).unwrap();
+                    if digit + 1 < base {
+                        buf[fraction_cursor] = CHARS[digit as usize + 1];
+                        fraction_cursor += 1;
+                        break;
+                    }
+                }
+
+                break;
+            }
+
+            if fraction < delta {
+                break;
+            }
+        }
+    }
+
+    // Compute integer digits. Fill unrepresented digits with zero.
+    while integer.div_small(base).exponent() > 0 {
+        integer = integer.div_small(base);
+        int_cursor -= 1;
+        buf[int_cursor] = b'0';
+    }
+
+    loop {
+        let remainder = integer.rem_small(base);
+        int_cursor -= 1;
+        buf[int_cursor] = CHARS[remainder.to_u64() as usize];
+        integer = integer.sub(remainder).div_small(base);
+
+        if integer == Soft::ZERO {
+            break;
+        }
+    }
+
+    // Add sign if negative.
+    if negative {
+        int_cursor -= 1;
+        buf[int_cursor] = b'-';
+    }
+
+    int_cursor..fraction_cursor
+}
+
+/// Returns the radix string representation of the given value in the given
+/// [Base] using only integer arithmetic on its bit pattern.
+///
+/// The result is the same as [ToRadixStr::to_radix_str](crate::ToRadixStr::to_radix_str)
+/// on an IEEE 754 conforming FPU, and is bit-identical on every target, even
+/// those with extended precision, flushed subnormals or no FPU, which matters
+/// when replicas must agree on the output. It's slower than
+/// [ToRadixStr::to_radix_str](crate::ToRadixStr::to_radix_str).
+///
+/// Returns [InvalidBaseError] if the given [Base] is out of range of
+/// [MIN_BASE] and [MAX_BASE] (inclusive).
+///
+/// Example:
+/// ```rust
+/// use radix_ecmascript::to_radix_str_deterministic;
+///
+/// assert_eq!(to_radix_str_deterministic(0.123, 16).unwrap(), "0.1f7ced916872b");
+/// ```
+#[cfg(feature = "std")]
+pub fn to_radix_str_deterministic(value: impl Into<f64>, base: Base) -> Result<String, InvalidBaseError> {
+    if !(MIN_BASE..=MAX_BASE).contains(&base) {
+        return Err(InvalidBaseError(base));
+    }
+
+    let value = value.into();
+    Ok(pool::with_buffer(|buf| {
+        let range = write_radix_soft(value, crate::float_format::delta_f64(value), base, buf);
+        buf[range].iter().map(|c| *c as char).collect()
+    }))
+}
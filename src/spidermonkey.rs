@@ -0,0 +1,155 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! SpiderMonkey's `js_dtobasestr`, which Firefox uses for `Number.prototype.toString`
+//! with a radix.
+//!
+//! Unlike V8, SpiderMonkey computes every integer digit exactly and generates
+//! the shortest fraction digits that round back to the input, using exact
+//! big integer arithmetic. The two engines therefore often differ in the last
+//! digits.
+//!
+//! [SpiderMonkey Source Code](https://searchfox.org/mozilla-central/source/js/src/jsdtoa.cpp)
+
+use std::cmp::Ordering;
+use crate::Base;
+use crate::bignum::Bignum;
+use crate::double::{biased_exponent, exponent, significand};
+
+/// Converts a finite value using SpiderMonkey's algorithm. The [Base] must be valid.
+pub(crate) fn to_radix_str_spidermonkey(value: f64, base: Base) -> String {
+    let to_char = |digit: u32| char::from_digit(digit, base as u32).unwrap();
+    let mut result = String::new();
+
+    let d = value.abs();
+    if value < 0.0 {
+        result.push('-');
+    }
+
+    trace_event!("bignum conversion", value = value, base = base);
+    count!(conversion, base);
+    count!(BIGNUM_CONVERSIONS);
+
+    // Output the integer part exactly.
+    let integer = d.floor();
+    let mut integer_bignum = Bignum::from_u64(significand(integer));
+    if exponent(integer) > 0 {
+        integer_bignum.shl(exponent(integer) as u32);
+    } else {
+        integer_bignum = Bignum::from_u64(integer as u64);
+    }
+    result.extend(integer_bignum.to_digits(base as u32).into_iter().map(|digit| to_char(digit as u32)));
+
+    let fraction = d - integer;
+    if fraction == 0.0 {
+        return result;
+    }
+    result.push('.');
+
+    // 1/2^s2 is half the gap between d and the next f64.
+    let mut s2 = 1023 + 53 - (biased_exponent(d) as i32).max(1);
+    let mut mlo = Bignum::from_u64(1);
+    let mut mhi = Bignum::from_u64(1);
+    if significand(d).is_power_of_two() && biased_exponent(d) > 1 {
+        // The gap below a power of two is half the gap above it, so we want to
+        // be within a quarter of the last significant digit below d.
+        s2 += 1;
+        mhi = Bignum::from_u64(2);
+    }
+
+    // fraction = b / 2^s2, and the gaps to the neighbouring f64s are
+    // 2 * mlo / 2^s2 and 2 * mhi / 2^s2.
+    let shift = exponent(fraction) + s2;
+    let mut b = if shift >= 0 {
+        let mut b = Bignum::from_u64(significand(fraction));
+        b.shl(shift as u32);
+        b
+    } else {
+        Bignum::from_u64(significand(fraction) >> -shift)
+    };
+    let mut s = Bignum::from_u64(1);
+    s.shl(s2 as u32);
+
+    let even = significand(d) & 1 == 0;
+    loop {
+        b.mul_small(base as u32);
+        let mut digit = b.div_rem(&s).to_u64().unwrap() as u32;
+        mlo.mul_small(base as u32);
+        mhi.mul_small(base as u32);
+
+        // Do we yet have the shortest string that will round to d?
+        let j = b.cmp(&mlo);
+        let j1 = if mhi > s {
+            Ordering::Greater
+        } else {
+            let mut delta = s.clone();
+            delta.sub(&mhi);
+            b.cmp(&delta)
+        };
+
+        let done = if j1 == Ordering::Equal && even {
+            if j == Ordering::Greater {
+                digit += 1;
+            }
+            true
+        } else if j == Ordering::Less || (j == Ordering::Equal && even) {
+            if j1 == Ordering::Greater {
+                // Either digit or digit + 1 would work here as the least
+                // significant digit. Use whichever is closer to d.
+                b.shl(1);
+                if b > s {
+                    digit += 1;
+                }
+            }
+            true
+        } else if j1 == Ordering::Greater {
+            digit += 1;
+            true
+        } else {
+            false
+        };
+
+        result.push(to_char(digit));
+        if done {
+            return result;
+        }
+    }
+}
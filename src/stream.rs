@@ -0,0 +1,212 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! Conversion straight into a writer with a small, fixed stack footprint.
+
+use core::fmt::Write;
+use crate::double::{exponent, next_float, trunc};
+use crate::{Base, InvalidBaseError, WriteError, MAX_BASE, MIN_BASE};
+
+/// Character array used for conversion.
+const CHARS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+/// Length of the chunk buffer. It holds the integer digits left after the
+/// zero-filled ones, which are below `2^53 * base` (at most 54 digits), and is
+/// then reused to batch fraction digits.
+const CHUNK_LEN: usize = 64;
+
+/// Zeros for the zero-filled integer digits.
+const ZEROS: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// The outcome of the fraction loop.
+struct FractionDigits {
+    /// The number of fraction digits written.
+    len: usize,
+    /// Whether the last digit is incremented by rounding up.
+    increment_last: bool,
+    /// Whether rounding up carried past every fraction digit into the integer
+    /// part.
+    carry: bool,
+}
+
+/// Calls emit with each fraction digit generated from the given fraction
+/// and delta until the loop stops, returning the outcome after carries.
+fn fraction_digits(mut fraction: f64, mut delta: f64, base: Base, mut emit: impl FnMut(u8)) -> FractionDigits {
+    let base_f64 = base as f64;
+    let mut len = 0;
+    // One past the last digit a carry can stop at, which is below base - 1.
+    let mut incrementable_len = 0;
+    loop {
+        fraction *= base_f64;
+        delta *= base_f64;
+        let digit = fraction as u8;
+        emit(digit);
+        len += 1;
+        if digit + 1 < base {
+            incrementable_len = len;
+        }
+        fraction -= digit as f64;
+
+        if (fraction > 0.5 || (fraction == 0.5 && (digit & 1) == 1)) && fraction + delta > 1.0 {
+            return FractionDigits {
+                len: incrementable_len,
+                increment_last: incrementable_len > 0,
+                carry: incrementable_len == 0,
+            };
+        }
+        if fraction < delta {
+            return FractionDigits { len, increment_last: false, carry: false };
+        }
+    }
+}
+
+/// Writes the radix string representation of the given value in the given
+/// [Base] into the given writer, the same as
+/// [ToRadixStr::to_radix_str](crate::ToRadixStr::to_radix_str) returns, with
+/// about a hundred bytes of stack rather than a buffer of
+/// [MAX_LEN](crate::MAX_LEN) bytes.
+///
+/// The fraction digits are generated twice, once to resolve rounding carries
+/// and once to write them, so this is slower than buffered conversion.
+///
+/// Returns [WriteError::InvalidBase] if the given [Base] is out of range of
+/// [MIN_BASE] and [MAX_BASE] (inclusive), and [WriteError::Fmt] if the writer
+/// fails, after which it may hold part of the radix string.
+///
+/// Example:
+/// ```rust
+/// use radix_ecmascript::write_radix_streaming;
+///
+/// let mut s = String::new();
+/// write_radix_streaming(-255.5, 16, &mut s).unwrap();
+/// assert_eq!(s, "-ff.8");
+/// ```
+pub fn write_radix_streaming(value: impl Into<f64>, base: Base, out: &mut impl Write) -> Result<(), WriteError> {
+    if !(MIN_BASE..=MAX_BASE).contains(&base) {
+        return Err(InvalidBaseError(base).into());
+    }
+
+    count!(conversion, base);
+    let value = value.into();
+    if value.is_nan() {
+        return Ok(out.write_str("NaN")?);
+    }
+    if value == 0.0 {
+        return Ok(out.write_str("0")?);
+    }
+    if value.is_infinite() {
+        return Ok(out.write_str(if value < 0.0 { "-Infinity" } else { "Infinity" })?);
+    }
+    if value < 0.0 {
+        out.write_char('-')?;
+    }
+
+    let value = value.abs();
+    let base_f64 = base as f64;
+    let mut integer = trunc(value);
+    let fraction = value - integer;
+    let delta = (0.5 * (next_float(value) - value)).max(next_float(0.0));
+
+    // Resolve the fraction digits first, as rounding can carry into the
+    // integer part.
+    let digits = if fraction >= delta {
+        Some(fraction_digits(fraction, delta, base, |_| {}))
+    } else {
+        None
+    };
+    if digits.as_ref().is_some_and(|digits| digits.increment_last || digits.carry) {
+        count!(ROUND_UPS);
+    }
+    if digits.as_ref().is_some_and(|digits| digits.carry) {
+        integer += 1.0;
+    }
+
+    // Count the zero-filled integer digits, then generate the rest from the
+    // least significant into the end of the chunk.
+    let mut zeros = 0;
+    while exponent(integer / base_f64) > 0 {
+        integer /= base_f64;
+        zeros += 1;
+    }
+    let mut chunk = [0; CHUNK_LEN];
+    let mut start = CHUNK_LEN;
+    loop {
+        let remainder = integer % base_f64;
+        start -= 1;
+        chunk[start] = CHARS[remainder as usize];
+        integer = (integer - remainder) / base_f64;
+
+        if integer <= 0.0 {
+            break;
+        }
+    }
+    out.write_str(core::str::from_utf8(&chunk[start..]).unwrap())?;
+    while zeros > 0 {
+        let n = zeros.min(ZEROS.len());
+        out.write_str(&ZEROS[..n])?;
+        zeros -= n;
+    }
+
+    let Some(digits) = digits.filter(|digits| digits.len > 0) else {
+        return Ok(());
+    };
+
+    // Generate the fraction digits again, writing the ones that survive
+    // rounding a chunk at a time.
+    chunk[0] = b'.';
+    let mut chunk_len = 1;
+    let mut written = 0;
+    let mut result = Ok(());
+    fraction_digits(fraction, delta, base, |digit| {
+        if written == digits.len || result.is_err() {
+            return;
+        }
+        written += 1;
+        let digit = if written == digits.len && digits.increment_last { digit + 1 } else { digit };
+        chunk[chunk_len] = CHARS[digit as usize];
+        chunk_len += 1;
+        if chunk_len == CHUNK_LEN || written == digits.len {
+            result = out.write_str(core::str::from_utf8(&chunk[..chunk_len]).unwrap());
+            chunk_len = 0;
+        }
+    });
+    Ok(result?)
+}
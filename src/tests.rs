@@ -42,53 +42,51 @@
 #[cfg(test)]
 use crate::*;
 
+/// Checks the golden expectations generated by `examples/generate_golden.rs`.
+#[cfg(all(feature = "std", feature = "f64"))]
 #[test]
 fn test_to_radix_str() {
-    let x = 0.05217266072382676;
-
-    const EXPECTED: [&str; 35] = [
-        "0.00001101010110110010111111111111100011011101110000011",
-        "0.00110200022020012220120101220020111",
-        "0.003111230233333320313130012",
-        "0.0112300443304443032401333",
-        "0.015134100114141415452",
-        "0.023616030022055516622",
-        "0.032554577770673406",
-        "0.042026618651180643",
-        "0.05217266072382676",
-        "0.06349506198377117",
-        "0.0761a289186a5058",
-        "0.08a814611c1a0636",
-        "0.0a3239cda0bc978",
-        "0.0bb139330b51876",
-        "0.0d5b2fff8ddc18",
-        "0.0f158c3647753f",
-        "0.0gg4fe405e39cb",
-        "0.0ifg3cei7ia9h",
-        "0.10h7ca5eabje8",
-        "0.1203c8f80ka74",
-        "0.135bgf5hk3ll2",
-        "0.14di134k07bii",
-        "0.1615f6hcdi284",
-        "0.17f4nfon3e1ie",
-        "0.196pgpp81pf5f",
-        "0.1b0oihjj5ijc",
-        "0.1cp86j9br2n3",
-        "0.1epcl695l16b",
-        "0.1gsjpjk0r2dp",
-        "0.1j48gudjoqjq",
-        "0.1ldivvsdrgc",
-        "0.1nqulj60i8vr",
-        "0.1qak6wn4rq5o",
-        "0.1svvkxr2efv",
-        "0.1vm61aaabtc",
-    ];
+    const GOLDEN: &str = include_str!("../tests/golden/to_radix_str.txt");
 
-    for base in MIN_BASE..=MAX_BASE {
-        assert_eq!(x.to_radix_str(base).unwrap(), EXPECTED[base as usize - 2]);
+    for line in GOLDEN.lines() {
+        let mut fields = line.split(' ');
+        let bits = u64::from_str_radix(fields.next().unwrap(), 16).unwrap();
+        let base: Base = fields.next().unwrap().parse().unwrap();
+        let expected = fields.next().unwrap();
+
+        let value = f64::from_bits(bits);
+        assert_eq!(value.to_radix_str(base).unwrap(), expected, "{:e} in base {}", value, base);
     }
+
+    // Engines don't use the radix algorithm for base 10, so it isn't in the corpus.
+    assert_eq!((0.05217266072382676).to_radix_str(10).unwrap(), "0.05217266072382676");
 }
 
+#[cfg(all(feature = "std", feature = "f64"))]
+#[test]
+fn test_to_radix_cow() {
+    use std::borrow::Cow;
+
+    let constants = [
+        (f64::NAN, "NaN"),
+        (0.0, "0"),
+        (-0.0, "0"),
+        (f64::INFINITY, "Infinity"),
+        (f64::NEG_INFINITY, "-Infinity"),
+    ];
+    for (value, expected) in constants {
+        assert!(matches!(to_radix_cow(value, 7).unwrap(), Cow::Borrowed(s) if s == expected), "{}", value);
+    }
+    for value in [0.5, -255.5, 5e-324, f64::MAX] {
+        let cow = to_radix_cow(value, 36).unwrap();
+        assert!(matches!(cow, Cow::Owned(_)));
+        assert_eq!(cow, value.to_radix_str(36).unwrap());
+    }
+    assert_eq!(to_radix_cow(0.1f32, 10).unwrap(), "0.10000000149011612");
+    assert!(to_radix_cow(f64::NAN, 37).is_err());
+}
+
+#[cfg(all(feature = "std", feature = "f64"))]
 #[test]
 fn test_to_radix_str_ranges() {
     // Valid ranges
@@ -100,3 +98,1992 @@ fn test_to_radix_str_ranges() {
     assert!((0.0).to_radix_str(MIN_BASE-1).is_err());
     assert!((0.0).to_radix_str(MAX_BASE+1).is_err());
 }
+
+#[cfg(all(feature = "std", feature = "f64"))]
+#[test]
+fn test_small_integers() {
+    // The exponent of integer / base was once read from the low bits of the
+    // significand, so 1 and 2 were zero-filled in base 3.
+    assert_eq!((1.0).to_radix_str(3).unwrap(), "1");
+    assert_eq!((2.0).to_radix_str(3).unwrap(), "2");
+}
+
+#[cfg(all(feature = "std", feature = "f64"))]
+#[test]
+fn test_large_integers() {
+    // Digits below the precision of the input are filled with zeros.
+    assert_eq!((1e21).to_radix_str(7).unwrap(), "5135235413265003022600000");
+    assert_eq!((123.456e30).to_radix_str(36).unwrap(), "98h98x2lfsg0000000000");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_continued_fraction() {
+    assert_eq!(continued_fraction(0.1).unwrap().to_string(), "[0; 9, 1, 1801439850948197, 2]");
+    assert_eq!(continued_fraction(-0.75).unwrap().to_string(), "[-1; 4]");
+    assert_eq!(continued_fraction(3.0).unwrap().to_string(), "[3]");
+    assert_eq!(continued_fraction(0.75).unwrap().to_radix_str(2).unwrap(), "[0; 1, 11]");
+
+    let pi = continued_fraction(std::f64::consts::PI).unwrap();
+    assert_eq!(pi.num_terms(), 27);
+    assert_eq!(pi.term_u64(4), Some(292));
+
+    // The smallest subnormal has a 1074-bit second term.
+    let tiny = continued_fraction(5e-324).unwrap();
+    assert_eq!(tiny.term_u64(1), None);
+    assert_eq!(tiny.to_radix_str(2).unwrap(), format!("[0; 1{}]", "0".repeat(1074)));
+
+    assert!(continued_fraction(f64::NAN).is_none());
+    assert!(continued_fraction(f64::INFINITY).is_none());
+}
+
+#[cfg(feature = "ecma-methods")]
+#[test]
+fn test_shortest_js_literal() {
+    assert_eq!(shortest_js_literal(1000.0), "1e3");
+    assert_eq!(shortest_js_literal(1234000.0), "1234e3");
+    assert_eq!(shortest_js_literal(0.5), ".5");
+    assert_eq!(shortest_js_literal(-0.25), "-.25");
+    assert_eq!(shortest_js_literal(1.5e-7), "15e-8");
+    assert_eq!(shortest_js_literal(1.23e-18), "123e-20");
+    assert_eq!(shortest_js_literal(255.0), "255");
+    assert_eq!(shortest_js_literal((1u64 << 60) as f64), "1152921504606847e3");
+    assert_eq!(shortest_js_literal((1u64 << 54) as f64), "0x40000000000000");
+    assert_eq!(shortest_js_literal(-0.0), "-0");
+    assert_eq!(shortest_js_literal(f64::NAN), "NaN");
+    assert_eq!(shortest_js_literal(f64::NEG_INFINITY), "-Infinity");
+}
+
+#[cfg(all(feature = "parse", feature = "f32", feature = "f64"))]
+#[test]
+fn test_from_radix_str() {
+    use crate::bignum::Bignum;
+
+    assert_eq!(f64::from_radix_str("0.1f7ced916872b", 16).unwrap(), 0.123);
+    assert_eq!(f64::from_radix_str("-1A.8", 16).unwrap(), -26.5);
+    assert_eq!(f64::from_radix_str("+zz", 36).unwrap(), 1295.0);
+    assert_eq!(f64::from_radix_str("-Infinity", 10).unwrap(), f64::NEG_INFINITY);
+    assert!(f64::from_radix_str("NaN", 10).unwrap().is_nan());
+    assert_eq!(f32::from_radix_str("0.1", 2).unwrap(), 0.5);
+
+    // Correct rounding at the extremes of the range.
+    assert_eq!(f64::from_radix_str(&format!("0.{}1", "0".repeat(1073)), 2).unwrap(), 5e-324);
+    assert_eq!(f64::from_radix_str(&format!("0.{}1", "0".repeat(1074)), 2).unwrap(), 0.0);
+    assert_eq!(f64::from_radix_str(&format!("0.{}11", "0".repeat(1074)), 2).unwrap(), 5e-324);
+    assert_eq!(f64::from_radix_str(&format!("1{}", "0".repeat(1024)), 2).unwrap(), f64::INFINITY);
+    assert_eq!(f64::from_radix_str(&"1".repeat(53), 2).unwrap(), 9007199254740991.0);
+    assert_eq!(f64::from_radix_str(&format!("{}1", "1".repeat(53)), 2).unwrap(), 18014398509481984.0);
+
+    // Long inputs are truncated, and then compared with the halfway value that
+    // could decide their rounding. 3 * 2^-1075, halfway between the two
+    // smallest subnormals, has 752 significant decimal digits.
+    let mut tie = Bignum::pow(5, 1075);
+    tie.mul_small(3);
+    let digits: String = tie.to_digits(10).iter().map(|d| char::from_digit(*d as u32, 10).unwrap()).collect();
+    let tie = format!("0.{digits:0>1075}");
+    let below = format!("{}4{}", &tie[..tie.len() - 1], "9".repeat(100));
+    assert_eq!(f64::from_radix_str(&tie, 10).unwrap(), f64::from_bits(2));
+    assert_eq!(f64::from_radix_str(&format!("{tie}1"), 10).unwrap(), f64::from_bits(2));
+    assert_eq!(f64::from_radix_str(&below, 10).unwrap(), f64::from_bits(1));
+    // Large halfway values are integers, so odd bases have ties too.
+    let digits = Bignum::from_u64(9007199254740993).to_digits(3);
+    let tie: String = digits.iter().map(|d| char::from_digit(*d as u32, 3).unwrap()).collect();
+    let tail = format!(".{}1", "0".repeat(1000));
+    assert_eq!(f64::from_radix_str(&tie, 3).unwrap(), 9007199254740992.0);
+    assert_eq!(f64::from_radix_str(&format!("{tie}{tail}"), 3).unwrap(), 9007199254740994.0);
+
+    assert_eq!(f64::from_radix_str("0.05217266072382676", 10).unwrap(), 0.05217266072382676);
+    assert_eq!(f64::from_radix_str("0.1vm61aaabtc", 36).unwrap(), 0.05217266072382676);
+    assert_eq!(f64::from_radix_str("0.1svvkxr2efv", 35).unwrap(), 0.05217266072382675);
+
+    assert!(matches!(f64::from_radix_str("", 10), Err(ParseRadixError::Empty)));
+    assert!(matches!(f64::from_radix_str("12", 2), Err(ParseRadixError::InvalidDigit(1))));
+    assert!(matches!(f64::from_radix_str("1.", 10), Err(ParseRadixError::InvalidDigit(2))));
+    assert!(matches!(f64::from_radix_str(".5", 10), Err(ParseRadixError::InvalidDigit(0))));
+    assert!(matches!(f64::from_radix_str("-", 10), Err(ParseRadixError::InvalidDigit(1))));
+    assert!(matches!(f64::from_radix_str("1", 37), Err(ParseRadixError::InvalidBase(_))));
+}
+
+#[cfg(all(feature = "parse", feature = "f32", feature = "f64"))]
+#[test]
+fn test_from_radix_str_f32() {
+    // Just above halfway between 1 and the next f32, by less than an f64 can
+    // hold, so rounding through f64 would land on the tie and round down.
+    let above_tie = format!("1.{}1{}1", "0".repeat(23), "0".repeat(35));
+    assert_eq!(f64::from_radix_str(&above_tie, 2).unwrap() as f32, 1.0);
+    assert_eq!(f32::from_radix_str(&above_tie, 2).unwrap(), 1.0 + f32::EPSILON);
+    assert_eq!(f32::from_radix_str(&format!("1.{}1", "0".repeat(23)), 2).unwrap(), 1.0);
+    assert_eq!(f32::from_radix_str("1.000001000000001", 16).unwrap(), 1.0 + f32::EPSILON);
+
+    assert_eq!(f32::from_radix_str(&format!("0.{}1", "0".repeat(148)), 2).unwrap(), f32::from_bits(1));
+    assert_eq!(f32::from_radix_str(&format!("0.{}1", "0".repeat(149)), 2).unwrap(), 0.0);
+    assert_eq!(f32::from_radix_str(&format!("0.{}11", "0".repeat(149)), 2).unwrap(), f32::from_bits(1));
+    let (half, _) = rebase_radix_str(&format!("0.{}1", "0".repeat(149)), 2, 10, usize::MAX).unwrap();
+    assert_eq!(f32::from_radix_str(&half, 10).unwrap(), 0.0);
+    assert_eq!(f32::from_radix_str(&format!("{half}{}1", "0".repeat(400)), 10).unwrap(), f32::from_bits(1));
+    assert_eq!(f32::from_radix_str(&format!("-{}", "1".repeat(24)), 2).unwrap(), -16777215.0);
+    assert_eq!(f32::from_radix_str(&format!("1{}", "0".repeat(128)), 2).unwrap(), f32::INFINITY);
+    assert_eq!(f32::from_radix_str(&"1".repeat(129), 2).unwrap(), f32::INFINITY);
+    assert_eq!(f32::from_radix_str(&"1".repeat(128), 2).unwrap(), f32::INFINITY);
+    assert_eq!(f32::from_radix_str(&format!("{}0", "1".repeat(24)), 2).unwrap(), 33554430.0);
+    assert_eq!(f32::from_radix_str(&f32::MAX.to_radix_str(7).unwrap(), 7).unwrap(), f32::MAX);
+    assert_eq!(f32::from_radix_str("-Infinity", 10).unwrap(), f32::NEG_INFINITY);
+    assert!(f32::from_radix_str("NaN", 10).unwrap().is_nan());
+    assert!(matches!(f32::from_radix_str("1", 37), Err(ParseRadixError::InvalidBase(_))));
+}
+
+#[cfg(feature = "parse")]
+#[test]
+fn test_obfuscator() {
+    let values = [0.5, -1234.75, 0.0, 1e21, f64::NAN, f64::NEG_INFINITY, 0.123];
+    let obfuscator = Obfuscator::new(42);
+    let encoded = obfuscator.encode_all(&values);
+    assert_eq!(encoded, Obfuscator::new(42).encode_all(&values));
+    assert_ne!(encoded, Obfuscator::new(43).encode_all(&values));
+
+    let decoded = obfuscator.decode_all(&encoded).unwrap();
+    for (value, decoded) in values.iter().zip(decoded) {
+        assert_eq!(value.to_bits(), decoded.to_bits());
+    }
+
+    // Decoding with another index uses a different scheme.
+    assert!((0..16).any(|index| obfuscator.decode(index, &encoded[1]).ok() != Some(-1234.75)));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_canonical_key() {
+    let values = [f64::NEG_INFINITY, -1e300, -1.5, -5e-324, 0.0, 5e-324, 0.5, 1.0, 1e300, f64::INFINITY, f64::NAN];
+    let keys: Vec<String> = values.iter().map(|v| canonical_key(*v)).collect();
+    assert!(keys.windows(2).all(|w| w[0] < w[1]));
+    assert!(keys.iter().all(|k| k.len() == 13));
+
+    assert_eq!(canonical_key(-0.0), canonical_key(0.0));
+    assert_eq!(canonical_key(f64::from_bits(0xFFF8_0000_0000_0001)), canonical_key(f64::NAN));
+
+    for value in &values[..10] {
+        assert_eq!(from_canonical_key(&canonical_key(*value)), Some(*value));
+    }
+    assert!(from_canonical_key(&canonical_key(f64::NAN)).unwrap().is_nan());
+    assert_eq!(from_canonical_key("zzz"), None);
+    assert_eq!(from_canonical_key("zzzzzzzzzzzzz"), None);
+}
+
+#[test]
+fn test_double() {
+    use crate::double::*;
+
+    assert_eq!(significand(1.0), HIDDEN_BIT);
+    assert_eq!(exponent(1.0), -52);
+    assert_eq!(significand(1.5), 3 << 51);
+    assert_eq!(exponent(1.5), -52);
+    assert_eq!(exponent(1e21), 17);
+    assert_eq!(exponent(f64::MAX), MAX_EXPONENT - 1);
+
+    assert!(is_denormal(0.0));
+    assert!(is_denormal(5e-324));
+    assert!(!is_denormal(f64::MIN_POSITIVE));
+    assert_eq!(significand(5e-324), 1);
+    assert_eq!(exponent(5e-324), DENORMAL_EXPONENT);
+    assert_eq!(exponent(f64::MIN_POSITIVE), DENORMAL_EXPONENT);
+}
+
+#[test]
+fn test_next_prev_float() {
+    use crate::double::*;
+
+    assert_eq!(next_float(1.0), 1.0 + f64::EPSILON);
+    assert_eq!(prev_float(1.0), 1.0 - f64::EPSILON / 2.0);
+    assert_eq!(next_float(0.0), 5e-324);
+    assert_eq!(next_float(-0.0), 5e-324);
+    assert_eq!(prev_float(0.0), -5e-324);
+    assert_eq!(next_float(-5e-324).to_bits(), (-0.0f64).to_bits());
+    assert_eq!(next_float(f64::MAX), f64::INFINITY);
+    assert_eq!(next_float(f64::INFINITY), f64::INFINITY);
+    assert_eq!(next_float(f64::NEG_INFINITY), f64::MIN);
+    assert_eq!(prev_float(f64::NEG_INFINITY), f64::NEG_INFINITY);
+    assert!(next_float(f64::NAN).is_nan());
+    assert!(prev_float(f64::NAN).is_nan());
+
+    assert_eq!(ulp(1.0), f64::EPSILON);
+    assert_eq!(ulp(-1.0), f64::EPSILON);
+    assert_eq!(ulp(0.0), 5e-324);
+    assert_eq!(ulp(f64::MAX), f64::from_bits((971 + 1023) << 52));
+    assert_eq!(ulp(f64::NEG_INFINITY), f64::INFINITY);
+    assert!(ulp(f64::NAN).is_nan());
+}
+
+#[test]
+fn test_double_fields() {
+    use crate::double::*;
+
+    assert_eq!((sign(-1.5), biased_exponent(-1.5), mantissa(-1.5)), (1, 0x3FF, 1 << 51));
+    assert_eq!((sign(-0.0), biased_exponent(-0.0), mantissa(-0.0)), (1, 0, 0));
+    assert_eq!((sign(5e-324), biased_exponent(5e-324), mantissa(5e-324)), (0, 0, 1));
+    assert_eq!(biased_exponent(f64::INFINITY), 0x7FF);
+
+    for value in [-1.5, 0.123, 5e-324, f64::MAX, f64::NEG_INFINITY] {
+        assert_eq!(from_parts(sign(value), biased_exponent(value), mantissa(value)), Some(value));
+    }
+    assert!(from_parts(0, 0x7FF, 1).unwrap().is_nan());
+    assert_eq!(from_parts(2, 0, 0), None);
+    assert_eq!(from_parts(0, 0x800, 0), None);
+    assert_eq!(from_parts(0, 0, HIDDEN_BIT), None);
+}
+
+#[test]
+fn test_trunc() {
+    use crate::double::*;
+
+    for value in [0.0, -0.0, 0.5, -0.5, 1.0, 1.5, -1.5, 255.99, 4503599627370495.5, 9007199254740992.0, 1e300, 5e-324, f64::MAX] {
+        assert_eq!(trunc(value).to_bits(), value.trunc().to_bits());
+    }
+    assert_eq!(trunc(f64::NEG_INFINITY), f64::NEG_INFINITY);
+    assert!(trunc(f64::NAN).is_nan());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_is_exact_in_base() {
+    assert!(is_exact_in_base(0.1, 10).unwrap());
+    assert!(is_exact_in_base(0.1, 2).unwrap());
+    assert!(is_exact_in_base(0.1, 36).unwrap());
+    assert!(!is_exact_in_base(0.1, 3).unwrap());
+    assert!(!is_exact_in_base(0.5, 35).unwrap());
+    assert!(is_exact_in_base(1e300, 3).unwrap());
+    assert!(is_exact_in_base(-7.0, 7).unwrap());
+    assert!(is_exact_in_base(0.0, 5).unwrap());
+    assert!(!is_exact_in_base(f64::NAN, 2).unwrap());
+    assert!(!is_exact_in_base(f64::INFINITY, 2).unwrap());
+    assert!(is_exact_in_base(0.5, 1).is_err());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_exact_fraction_digit_count() {
+    assert_eq!(exact_fraction_digit_count(0.5, 2).unwrap(), Some(1));
+    assert_eq!(exact_fraction_digit_count(0.1, 2).unwrap(), Some(55));
+    assert_eq!(exact_fraction_digit_count(0.1, 10).unwrap(), Some(55));
+    assert_eq!(exact_fraction_digit_count(0.1, 16).unwrap(), Some(14));
+    assert_eq!(exact_fraction_digit_count(0.1, 3).unwrap(), None);
+    assert_eq!(exact_fraction_digit_count(5e-324, 2).unwrap(), Some(1074));
+    assert_eq!(exact_fraction_digit_count(-3.0, 3).unwrap(), Some(0));
+    assert_eq!(exact_fraction_digit_count(f64::NAN, 2).unwrap(), None);
+    assert!(exact_fraction_digit_count(0.5, 37).is_err());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_quantize() {
+    assert_eq!(quantize(0.123, 16, 2).unwrap(), 0x1f as f64 / 256.0);
+    assert_eq!(quantize(0.123, 10, 2).unwrap(), 0.12);
+    assert_eq!(quantize(2.5, 10, 0).unwrap(), 2.0);
+    assert_eq!(quantize(3.5, 10, 0).unwrap(), 4.0);
+    assert_eq!(quantize(-0.75, 2, 1).unwrap(), -1.0);
+    assert_eq!(quantize(1.0 / 3.0, 3, 1).unwrap(), 1.0 / 3.0);
+    assert_eq!(quantize(0.1, 3, 4).unwrap(), 8.0 / 81.0);
+    assert_eq!(quantize(0.1, 3, 1000).unwrap(), 0.1);
+    assert_eq!(quantize(0.5, 16, 1).unwrap(), 0.5);
+    assert_eq!(quantize(-1e-20, 36, 3).unwrap().to_bits(), (-0.0f64).to_bits());
+    assert!(quantize(f64::NAN, 10, 2).unwrap().is_nan());
+    assert!(quantize(0.5, 0, 1).is_err());
+}
+
+#[cfg(feature = "parse")]
+#[test]
+fn test_radix_str_arithmetic() {
+    assert_eq!(add_radix_str("0.1", "0.2", 10).unwrap(), "0.3");
+    assert_eq!(add_radix_str("ff.f", "0.1", 16).unwrap(), "100");
+    assert_eq!(add_radix_str("-1.5", "1.5", 10).unwrap(), "0");
+    assert_eq!(add_radix_str("-1.5", "0.25", 10).unwrap(), "-1.25");
+    assert_eq!(add_radix_str("007", "-0.01", 10).unwrap(), "6.99");
+    assert_eq!(add_radix_str("ZZ", "1", 36).unwrap(), "100");
+    assert_eq!(sub_radix_str("1", "0.0001", 2).unwrap(), "0.1111");
+    assert_eq!(sub_radix_str("0.1", "0.2", 3).unwrap(), "-0.1");
+    assert_eq!(sub_radix_str("-2", "-2", 10).unwrap(), "0");
+
+    let long = format!("0.{}1", "0".repeat(2000));
+    assert_eq!(add_radix_str(&long, &long, 10).unwrap(), format!("0.{}2", "0".repeat(2000)));
+
+    assert!(matches!(add_radix_str("1", "2", 2), Err(ParseRadixError::InvalidDigit(0))));
+    assert!(add_radix_str("NaN", "1", 10).is_err());
+    assert!(add_radix_str("1", "1", 1).is_err());
+}
+
+#[cfg(feature = "parse")]
+#[test]
+fn test_rebase_radix_str() {
+    assert_eq!(rebase_radix_str("-FF.8", 16, 2, 64).unwrap(), ("-11111111.1".to_string(), true));
+    assert_eq!(rebase_radix_str("0.1", 3, 10, 5).unwrap(), ("0.33333".to_string(), false));
+    assert_eq!(rebase_radix_str("0.1", 10, 4, 0).unwrap(), ("0".to_string(), false));
+    assert_eq!(rebase_radix_str("-0.000", 10, 36, 5).unwrap(), ("0".to_string(), true));
+    assert!(rebase_radix_str("12", 2, 10, 0).is_err());
+    assert!(rebase_radix_str("1", 10, 37, 0).is_err());
+
+    // 7^1500 has 1268 decimal digits and is 10^1500 in base 7.
+    let power = format!("1{}", "0".repeat(1500));
+    let (decimal, exact) = rebase_radix_str(&power, 7, 10, 0).unwrap();
+    assert!(exact);
+    assert_eq!(decimal.len(), 1268);
+    assert!(decimal.starts_with("443669956811") && decimal.ends_with("001"));
+    assert_eq!(rebase_radix_str(&decimal, 10, 7, 0).unwrap().0, power);
+
+    // 2^-1074 is exact in base 10 with 1074 fraction digits.
+    let (min, exact) = rebase_radix_str(&format!("0.{}1", "0".repeat(1073)), 2, 10, usize::MAX).unwrap();
+    assert!(exact);
+    assert_eq!(min, to_exact_decimal(5e-324));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_exact_fraction_digits() {
+    let digits: Vec<u8> = exact_fraction_digits(0.75, 2).unwrap().collect();
+    assert_eq!(digits, [1, 1]);
+
+    let digits: String = exact_fraction_digits(0.1, 10).unwrap()
+        .map(|d| char::from_digit(d as u32, 10).unwrap())
+        .collect();
+    assert_eq!(digits, "1000000000000000055511151231257827021181583404541015625");
+
+    let digits: Vec<u8> = exact_fraction_digits(-2.5, 3).unwrap().take(6).collect();
+    assert_eq!(digits, [1, 1, 1, 1, 1, 1]);
+
+    assert_eq!(exact_fraction_digits(5e-324, 2).unwrap().count(), 1074);
+    assert_eq!(exact_fraction_digits(3.0, 10).unwrap().count(), 0);
+    assert_eq!(exact_fraction_digits(f64::NAN, 10).unwrap().count(), 0);
+    assert!(exact_fraction_digits(0.5, 37).is_err());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_to_radix_interval() {
+    let interval = |value: f64, base: Base, digits: usize| to_radix_interval(value, base, digits).unwrap().unwrap();
+    assert_eq!(interval(0.5, 2, 3), ("0.100".to_string(), "0.100".to_string()));
+    assert_eq!(interval(255.0, 16, 0), ("ff".to_string(), "ff".to_string()));
+    assert_eq!(interval(1.0 / 3.0, 10, 5), ("0.33333".to_string(), "0.33334".to_string()));
+    assert_eq!(interval(0.1, 10, 20), ("0.10000000000000000555".to_string(), "0.10000000000000000556".to_string()));
+    assert_eq!(interval(-0.1, 10, 1), ("-0.2".to_string(), "-0.1".to_string()));
+    assert_eq!(interval(-0.01, 10, 1), ("-0.1".to_string(), "0.0".to_string()));
+    assert_eq!(interval(0.9999, 10, 2), ("0.99".to_string(), "1.00".to_string()));
+    assert_eq!(interval(35.5, 36, 0), ("z".to_string(), "10".to_string()));
+    assert_eq!(interval(5e-324, 7, 3), ("0.000".to_string(), "0.001".to_string()));
+    assert_eq!(interval(-0.0, 3, 2), ("0.00".to_string(), "0.00".to_string()));
+
+    // Integers are exact in every base, and odd bases never end a fraction.
+    let (lower, upper) = interval(f64::MAX, 3, 1000);
+    assert_eq!(lower.len(), 647 + 1 + 1000);
+    assert_eq!(lower, upper);
+    let (lower, upper) = interval(1e21, 36, 4);
+    assert_eq!((lower.as_str(), upper.as_str()), ("5v1j4f4ds79m9s.0000", "5v1j4f4ds79m9s.0000"));
+    let (lower, upper) = interval(0.1, 3, 2000);
+    assert_ne!(lower, upper);
+    assert_eq!(lower[..2001], upper[..2001]);
+
+    assert!(to_radix_interval(f64::NAN, 10, 2).unwrap().is_none());
+    assert!(to_radix_interval(1.0, 37, 2).is_err());
+}
+
+#[cfg(all(feature = "parse", feature = "f64"))]
+#[test]
+fn test_to_scaled_integer() {
+    let scaled = |value: f64, base| to_scaled_integer(value, base).unwrap();
+    assert_eq!(scaled(0.1, 10), Some(("1000000000000000055511151231257827021181583404541015625".to_string(), -55)));
+    assert_eq!(scaled(-0.0, 7), Some(("0".to_string(), 0)));
+    assert_eq!(scaled(1200.0, 10), Some(("12".to_string(), 2)));
+    assert_eq!(scaled(1200.0, 3), Some(("112211".to_string(), 1)));
+    assert_eq!(scaled(0.75, 2), Some(("11".to_string(), -2)));
+    assert_eq!(scaled(0.75, 12), Some(("9".to_string(), -1)));
+    assert_eq!(scaled(2f64.powi(60), 2), Some(("1".to_string(), 60)));
+    assert_eq!(scaled(0.1, 3), None);
+    assert_eq!(scaled(f64::NAN, 10), None);
+    assert!(to_scaled_integer(1.0, 37).is_err());
+
+    // mantissa * base^exponent is the value, exactly.
+    let (mantissa, exponent) = scaled(5e-324, 6).unwrap();
+    assert_eq!((mantissa.len(), exponent), (659, -1074));
+    let zeros = "0".repeat(1074 - 659);
+    assert_eq!(f64::from_radix_str(&format!("0.{}{}", zeros, mantissa), 6).unwrap(), 5e-324);
+
+    // The mantissa can outgrow a Bignum, such as 17^1074 in base 34.
+    for value in [5e-324, f64::MIN_POSITIVE, 1e-300] {
+        for base in MIN_BASE..=MAX_BASE {
+            let Some((mantissa, exponent)) = scaled(value, base) else {
+                assert_eq!(base.trailing_zeros(), 0);
+                continue;
+            };
+            assert!(!mantissa.ends_with('0'));
+            let zeros = "0".repeat(-exponent as usize - mantissa.len());
+            let s = format!("0.{}{}", zeros, mantissa);
+            assert_eq!(exact_fraction_digit_count(value, base).unwrap(), Some(-exponent as usize));
+            assert_eq!(to_radix_interval(value, base, -exponent as usize).unwrap(), Some((s.clone(), s.clone())));
+            assert_eq!(f64::from_radix_str(&s, base).unwrap(), value, "{:e} in base {}", value, base);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_to_exact_decimal() {
+    assert_eq!(to_exact_decimal(0.1), "0.1000000000000000055511151231257827021181583404541015625");
+    assert_eq!(to_exact_decimal(-1.5), "-1.5");
+    assert_eq!(to_exact_decimal(18446744073709551616.0), "18446744073709551616");
+    assert_eq!(to_exact_decimal(1e23), "99999999999999991611392");
+    assert_eq!(to_exact_decimal(-0.0), "-0");
+    assert_eq!(to_exact_decimal(f64::NEG_INFINITY), "-Infinity");
+    assert_eq!(to_exact_decimal(f64::NAN), "NaN");
+
+    // 5e-324 = 4.94...e-324 exactly, with 751 significant digits.
+    let min = to_exact_decimal(5e-324);
+    assert_eq!(min.len(), "0.".len() + 1074);
+    assert!(min.starts_with(&format!("0.{}494065645841246544", "0".repeat(323))));
+    assert!(min.ends_with("419718265533447265625"));
+}
+
+#[cfg(all(feature = "std", feature = "f32", feature = "f64"))]
+#[test]
+#[cfg_attr(miri, ignore = "Miri makes log2 imprecise")]
+fn test_max_len() {
+    let f64_extremes = [-f64::MAX, -5e-324, -f64::MIN_POSITIVE, -(f64::MIN_POSITIVE - 5e-324), -0.999999999999, -9007199254740991.5];
+    let f32_extremes = [-f32::MAX, -f32::from_bits(1), -f32::MIN_POSITIVE, -0.99999994, -8388607.5];
+
+    for base in MIN_BASE..=MAX_BASE {
+        let log2_base = (base as f64).log2();
+        assert_eq!(max_len(base), 1 + usize::max(f64::MAX.log2().div_euclid(log2_base) as usize + 1, 2 + (1074.0 / log2_base).ceil() as usize));
+        assert!(max_len(base) <= MAX_LEN);
+
+        for value in f64_extremes {
+            assert!(value.to_radix_str(base).unwrap().len() <= max_len(base));
+        }
+        for value in f32_extremes {
+            assert!(value.to_radix_str(base).unwrap().len() <= max_len_f32(base));
+        }
+    }
+
+    assert_eq!((-5e-324).to_radix_str(2).unwrap().len(), MAX_LEN);
+    assert_eq!(max_len(MAX_BASE + 1), 0);
+
+    // The conversion buffer is sized for these bounds, so check the values
+    // just below and at each power of two, which maximize carries.
+    let step = if cfg!(miri) { 97 } else { 1 };
+    for biased_exponent in (1..2047u64).step_by(step) {
+        for value in [f64::from_bits((biased_exponent << 52) - 1), f64::from_bits(biased_exponent << 52)] {
+            for base in MIN_BASE..=MAX_BASE {
+                assert!((-value).to_radix_str(base).unwrap().len() <= max_len(base), "{:e} in base {}", value, base);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_try_write() {
+    let mut buf = [0; 16];
+    assert_eq!(try_write(0.123, 16, &mut buf).unwrap(), 15);
+    assert_eq!(&buf[..15], b"0.1f7ced916872b");
+    assert_eq!(try_write(-0.5f32, 2, &mut buf).unwrap(), 4);
+    assert_eq!(&buf[..4], b"-0.1");
+    assert_eq!(try_write(f64::NEG_INFINITY, 10, &mut buf).unwrap(), 9);
+    assert_eq!(&buf[..9], b"-Infinity");
+
+    let mut small = [0; 4];
+    assert!(matches!(try_write(0.123, 16, &mut small), Err(WriteError::BufferTooSmall { required: 15 })));
+    assert_eq!(small, [0; 4]);
+    assert!(matches!(try_write(0.123, 1, &mut buf), Err(WriteError::InvalidBase(_))));
+
+    let mut max = [0; MAX_LEN];
+    assert_eq!(try_write(-5e-324, 2, &mut max).unwrap(), MAX_LEN);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_extend_radix() {
+    let mut bytes = b"[".to_vec();
+    extend_radix(0.123, 16, &mut bytes).unwrap();
+    bytes.push(b',');
+    extend_radix(-0.5f32, 2, &mut bytes).unwrap();
+    assert_eq!(bytes, b"[0.1f7ced916872b,-0.1");
+    assert!(extend_radix(0.123, 37, &mut bytes).is_err());
+    assert_eq!(bytes, b"[0.1f7ced916872b,-0.1");
+}
+
+#[cfg(all(feature = "std", feature = "f32", feature = "f64"))]
+#[test]
+fn test_radix_array_string() {
+    let s = RadixArrayString::<{ max_len(16) }>::new(0.123, 16).unwrap();
+    let copy = s;
+    assert_eq!(copy, "0.1f7ced916872b");
+    assert_eq!(s.len(), 15);
+    assert_eq!(s.capacity(), max_len(16));
+    assert_eq!(s.to_string(), "0.1f7ced916872b");
+
+    assert_eq!(RadixArrayString::<MAX_LEN>::new(-5e-324, 2).unwrap().len(), MAX_LEN);
+    let expected = f32::MIN_POSITIVE.to_radix_str(36).unwrap();
+    assert_eq!(RadixArrayString::<{ max_len_f32(36) }>::new(f32::MIN_POSITIVE, 36).unwrap(), expected.as_str());
+    assert!(matches!(RadixArrayString::<4>::new(0.123, 16), Err(WriteError::BufferTooSmall { required: 15 })));
+    assert!(matches!(RadixArrayString::<4>::new(0.5, 1), Err(WriteError::InvalidBase(_))));
+}
+
+#[cfg(all(feature = "std", feature = "f64"))]
+#[test]
+fn test_write_radix_streaming() {
+    struct Failing;
+    impl std::fmt::Write for Failing {
+        fn write_str(&mut self, _: &str) -> std::fmt::Result {
+            Err(std::fmt::Error)
+        }
+    }
+
+    let mut state = 0u64;
+    // SplitMix64.
+    let mut next = || {
+        state = state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    };
+
+    // Values with carries into the integer part, zero-filled integer digits
+    // and the longest radix strings.
+    let mut values = vec![
+        0.0, -0.0, 0.5, -255.5, 0.1, 1.0 / 3.0, 0.999999999999999, 1e21, 2f64.powi(53) + 2.0, -5e-324, f64::MAX,
+        f64::NAN, f64::NEG_INFINITY,
+    ];
+    let iterations = if cfg!(miri) { 10 } else { 1000 };
+    for _ in 0..iterations {
+        values.push(f64::from_bits(next()));
+        values.push((next() % 1_000_000) as f64 / (1 + next() % 1000) as f64);
+    }
+    for value in values {
+        for base in MIN_BASE..=MAX_BASE {
+            let mut s = String::new();
+            write_radix_streaming(value, base, &mut s).unwrap();
+            assert_eq!(s, value.to_radix_str(base).unwrap(), "{:e} in base {}", value, base);
+        }
+    }
+
+    assert!(matches!(write_radix_streaming(0.5, 37, &mut String::new()), Err(WriteError::InvalidBase(_))));
+    assert!(matches!(write_radix_streaming(0.5, 2, &mut Failing), Err(WriteError::Fmt(_))));
+}
+
+#[cfg(all(feature = "std", feature = "f64"))]
+#[test]
+fn test_explain_radix_str() {
+    let explanation = explain_radix_str(0.75, 2).unwrap();
+    assert_eq!(explanation.steps(), [
+        Step::Split { integer: 0.0, fraction: 0.75, delta: 0.5 * f64::EPSILON / 2.0 },
+        Step::FractionDigit { digit: 1, fraction: 0.5, delta: f64::EPSILON / 2.0 },
+        Step::FractionDigit { digit: 1, fraction: 0.0, delta: f64::EPSILON },
+        Step::Stop { fraction: 0.0, delta: f64::EPSILON },
+        Step::IntegerDigit { digit: 0, integer: 0.0 },
+    ]);
+    assert!(explain_radix_str(f64::NAN, 16).unwrap().steps().is_empty());
+    assert!(explain_radix_str(1.0, 37).is_err());
+
+    // The steps spell out the result.
+    for value in [0.1, -255.5, 1.0 / 3.0, 0.9999999999999999, 1e21, 2f64.powi(60) + 0.0, 5e-324, 123.456] {
+        for base in MIN_BASE..=MAX_BASE {
+            let explanation = explain_radix_str(value, base).unwrap();
+            let (mut integer, mut fraction) = (Vec::new(), Vec::new());
+            for step in explanation.steps() {
+                match *step {
+                    Step::FractionDigit { digit, .. } => fraction.push(digit),
+                    Step::Carry { position, digit } => {
+                        fraction.truncate(position);
+                        fraction.push(digit);
+                    }
+                    Step::CarryIntoInteger { .. } => fraction.clear(),
+                    Step::ZeroFill { .. } => integer.push(0),
+                    Step::IntegerDigit { digit, .. } => integer.push(digit),
+                    _ => {}
+                }
+            }
+            let digit = |d: &u8| char::from_digit(*d as u32, base as u32).unwrap();
+            let mut s: String = integer.iter().rev().map(digit).collect();
+            if !fraction.is_empty() {
+                s.push('.');
+                s.extend(fraction.iter().map(digit));
+            }
+            assert_eq!(explanation.result().trim_start_matches('-'), s, "{:e} in base {}", value, base);
+            assert_eq!(explanation.result(), value.to_radix_str(base).unwrap());
+        }
+    }
+}
+
+#[cfg(all(feature = "std", feature = "f32", feature = "f64"))]
+#[test]
+fn test_to_radix_str_finite() {
+    assert_eq!(to_radix_str_finite(-26.5, 16).unwrap(), "-1a.8");
+    assert_eq!(to_radix_str_finite(-0.0, 2).unwrap(), "0");
+    assert_eq!(to_radix_str_finite(f32::MAX, 36).unwrap(), f32::MAX.to_radix_str(36).unwrap());
+    assert!(matches!(to_radix_str_finite(f64::NAN, 16), Err(FiniteError::NaN)));
+    assert!(matches!(to_radix_str_finite(f64::NEG_INFINITY, 16), Err(FiniteError::Infinite)));
+    assert!(matches!(to_radix_str_finite(f64::NAN, 37), Err(FiniteError::InvalidBase(_))));
+    assert_eq!(FiniteError::Infinite.to_string(), "cannot convert an infinity to a finite radix string");
+}
+
+#[cfg(all(feature = "std", feature = "f64"))]
+#[test]
+fn test_base_ext() {
+    assert_eq!((Base::MIN_RADIX, Base::MAX_RADIX), (MIN_BASE, MAX_BASE));
+    assert_eq!(Base::ALL.collect::<Vec<_>>(), (2..=36).collect::<Vec<_>>());
+    for base in 0..=Base::MAX {
+        assert_eq!(Base::is_valid(base), 1.0.to_radix_str(base).is_ok(), "{}", base);
+    }
+}
+
+#[cfg(all(feature = "std", feature = "f64"))]
+#[test]
+fn test_digit_chars() {
+    for base in MIN_BASE..=MAX_BASE {
+        let s = (base as f64 - 1.0).to_radix_str(base).unwrap();
+        let c = s.chars().next().unwrap();
+        assert_eq!(digit_to_char(base - 1, Case::Lower), Some(c));
+        assert_eq!(digit_to_char(base - 1, Case::Upper), Some(c.to_ascii_uppercase()));
+        assert_eq!(char_to_digit(c), Some(base - 1));
+        assert_eq!(char_to_digit(c.to_ascii_uppercase()), Some(base - 1));
+    }
+    assert_eq!(digit_to_char(36, Case::Upper), None);
+    assert_eq!(char_to_digit('-'), None);
+    assert_eq!(char_to_digit('\u{ff10}'), None);
+
+    let standard = Alphabet::standard(Case::Upper);
+    assert_eq!((standard.base(), standard.char_to_digit('Z'), standard.char_to_digit('z')), (36, Some(35), None));
+    let custom = Alphabet::new("αβγ").unwrap();
+    assert_eq!((custom.base(), custom.digit_to_char(2), custom.char_to_digit('β')), (3, Some('γ'), Some(1)));
+    assert_eq!(custom.digit_to_char(3), None);
+    assert!(Alphabet::new("a").is_none());
+    assert!(Alphabet::new("abca").is_none());
+    assert!(Alphabet::new(&(0..=255u8).map(char::from).collect::<String>()).is_none());
+    assert!(Alphabet::new(&(0..=254u8).map(char::from).collect::<String>()).is_some());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_radix_list() {
+    let list = RadixList::new(10).unwrap();
+    assert_eq!(list.format(&[1.0, 0.5, -0.0, f64::NAN, f64::NEG_INFINITY]), "[1, 0.5, 0, NaN, -Infinity]");
+    assert_eq!(list.format::<f64>(&[]), "[]");
+    assert_eq!(list.format(&[0.5f32]), "[0.5]");
+    assert_eq!(list.separator(",").brackets(Some(("(", ")"))).format(&[1u8, 2, 3]), "(1,2,3)");
+    assert_eq!(RadixList::new(2).unwrap().brackets(None).format(&[3.0, 0.25]), "11, 0.01");
+    assert_eq!(RadixList::new(36).unwrap().base(), 36);
+    assert!(RadixList::new(1).is_err());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_align_radix_column() {
+    let column = align_radix_column([0.5f32, -255.25, 3.0], 2).unwrap();
+    assert_eq!(column.rows(), ["        0.1 ", "-11111111.01", "       11   "]);
+    assert_eq!((column.integer_width(), column.fraction_width(), column.width()), (9, 2, 12));
+
+    let column = align_radix_column([1.0, f64::NEG_INFINITY, 35.0], 36).unwrap();
+    assert_eq!(column.into_rows(), ["        1", "-Infinity", "        z"]);
+
+    let column = align_radix_column(Vec::<f64>::new(), 10).unwrap();
+    assert!(column.rows().is_empty());
+    assert_eq!(column.width(), 0);
+    assert!(align_radix_column([1.0], 37).is_err());
+}
+
+#[cfg(all(feature = "corpus", feature = "f64"))]
+#[test]
+fn test_corpus() {
+    use crate::corpus::{all, golden, DIVERGENCES};
+
+    assert_eq!(golden().count(), 2720);
+    for vector in all() {
+        assert_eq!(vector.value.to_radix_str(vector.base).unwrap(), vector.expected, "{:?}", vector);
+    }
+    for divergence in DIVERGENCES {
+        let (value, base) = (divergence.value, divergence.base);
+        assert_eq!(to_radix_str_compat(value, base, Compat::V8).unwrap(), divergence.v8);
+        assert_eq!(to_radix_str_compat(value, base, Compat::SpiderMonkey).unwrap(), divergence.spidermonkey);
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_to_radix_str_compat() {
+    assert_eq!(to_radix_str_compat(0.123, 16, Compat::V8).unwrap(), "0.1f7ced916872b");
+    assert_eq!(to_radix_str_compat(0.123, 16, Compat::default()).unwrap(), "0.1f7ced916872b");
+
+    assert_eq!(to_radix_str_compat(0.123, 36, Compat::V8).unwrap(), "0.4feornbt361");
+    assert_eq!(to_radix_str_compat(0.123, 36, Compat::V8Legacy).unwrap(), "0.4feornbt361hsh2a16lya7zaor");
+    assert_eq!(to_radix_str_compat(0.1, 10, Compat::V8Legacy).unwrap(), "0.1");
+    assert_eq!(to_radix_str_compat(-255.5, 16, Compat::V8Legacy).unwrap(), "-ff.8");
+    assert_eq!(to_radix_str_compat(0.5, 3, Compat::V8Legacy).unwrap(), format!("0.{}", "1".repeat(1098)));
+    assert_eq!(to_radix_str_compat(1e21, 7, Compat::V8Legacy).unwrap(), "5135235413265003022621156");
+    assert_eq!(to_radix_str_compat(-0.0, 7, Compat::V8Legacy).unwrap(), "0");
+    assert_eq!(to_radix_str_compat(f32::NAN, 7, Compat::V8Legacy).unwrap(), "NaN");
+    assert!(to_radix_str_compat(0.5, 37, Compat::V8Legacy).is_err());
+}
+
+#[cfg(all(feature = "parse", feature = "f64"))]
+#[test]
+fn test_to_radix_str_spidermonkey() {
+    let convert = |value: f64, base| to_radix_str_compat(value, base, Compat::SpiderMonkey).unwrap();
+
+    assert_eq!(convert(0.123, 16), "0.1f7ced916872b");
+    assert_eq!(convert(-255.5, 16), "-ff.8");
+    assert_eq!(convert(3.5, 3), "10.111111111111111111111111111111111");
+    assert_eq!(convert(1e21, 7), "5135235413265003022550266");
+    assert_eq!(convert(5e-324, 2), format!("0.{}1", "0".repeat(1073)));
+    assert_eq!(convert(-0.0, 10), "0");
+    assert_eq!(convert(f64::NEG_INFINITY, 10), "-Infinity");
+
+    // The output is the shortest that rounds back to the value.
+    for base in MIN_BASE..=MAX_BASE {
+        for value in [0.05217266072382676, 1.0 / 3.0, 123.456e-300, 1e300] {
+            assert_eq!(f64::from_radix_str(&convert(value, base), base).unwrap(), value);
+        }
+    }
+}
+
+#[cfg(feature = "format")]
+#[test]
+fn test_format_always_fraction() {
+    let format = RadixFormat::new(16).unwrap();
+    assert_eq!(format.format(26.0), "1a");
+    assert_eq!(format.format(0.123), "0.1f7ced916872b");
+
+    let format = format.always_fraction(true);
+    assert_eq!(format.format(26.0), "1a.0");
+    assert_eq!(format.format(-26.0f32), "-1a.0");
+    assert_eq!(format.format(-0.0), "0.0");
+    assert_eq!(format.format(26.5), "1a.8");
+    assert_eq!(format.format(f64::NAN), "NaN");
+    assert_eq!(format.format(f64::INFINITY), "Infinity");
+
+    assert!(RadixFormat::new(37).is_err());
+}
+
+#[cfg(all(feature = "format", feature = "parse"))]
+#[test]
+fn test_format_leading_zero() {
+    let format = RadixFormat::new(16).unwrap().leading_zero(false);
+    assert_eq!(format.format(0.123), ".1f7ced916872b");
+    assert_eq!(format.format(-0.5), "-.8");
+    assert_eq!(format.format(26.5), "1a.8");
+    assert_eq!(format.format(0.0), "0");
+    assert_eq!(format.always_fraction(true).format(-0.0), ".0");
+
+    assert_eq!(format.parse(".1f7ced916872b").unwrap(), 0.123);
+    assert_eq!(format.parse("-.8").unwrap(), -0.5);
+    assert_eq!(format.parse("0.8").unwrap(), 0.5);
+    assert!(format.parse(".").is_err());
+    assert!(RadixFormat::new(16).unwrap().parse(".8").is_err());
+}
+
+#[cfg(all(feature = "format", feature = "parse"))]
+#[test]
+fn test_format_radix_point() {
+    let format = RadixFormat::new(16).unwrap().radix_point(',');
+    assert_eq!(format.format(26.5), "1a,8");
+    assert_eq!(format.format(-0.5), "-0,8");
+    assert_eq!(format.format(26.0), "1a");
+    assert_eq!(format.leading_zero(false).format(0.5), ",8");
+    assert_eq!(format.parse("1a,8").unwrap(), 26.5);
+    assert_eq!(format.leading_zero(false).parse(",8").unwrap(), 0.5);
+    assert!(format.parse("1a.8").is_err());
+
+    let format = RadixFormat::new(2).unwrap().radix_point('·');
+    assert_eq!(format.format(2.5), "10·1");
+    assert_eq!(format.parse("10·1").unwrap(), 2.5);
+}
+
+#[cfg(all(feature = "intl", feature = "parse"))]
+#[test]
+fn test_group_separators() {
+    let format = RadixFormat::new(10).unwrap().radix_point(',').group_separators(".\u{a0}'");
+    assert_eq!(format.parse("1.234.567,25").unwrap(), 1234567.25);
+    assert_eq!(format.parse("-1\u{a0}234,5").unwrap(), -1234.5);
+    assert_eq!(format.parse("1'000").unwrap(), 1000.0);
+    assert!(matches!(format.parse("1..0"), Err(ParseRadixError::InvalidDigit(1))));
+    assert!(matches!(format.parse(".1"), Err(ParseRadixError::InvalidDigit(0))));
+    assert!(matches!(format.parse("1\u{a0}0,2.5"), Err(ParseRadixError::InvalidDigit(6))));
+    assert!(matches!(format.parse("1.000x"), Err(ParseRadixError::InvalidDigit(5))));
+
+    let format = RadixFormat::new(16).unwrap().group(4, '_').group_separators("_");
+    assert_eq!(format.parse(&format.format(65535.5)).unwrap(), 65535.5);
+}
+
+#[cfg(feature = "format")]
+#[test]
+fn test_format_group() {
+    let format = RadixFormat::new(2).unwrap().group(4, '_');
+    assert_eq!(format.format(170.75), "1010_1010.11");
+    assert_eq!(format.format(-42.0), "-10_1010");
+    assert_eq!(format.format(0.123).split('.').nth(1).unwrap().split('_').next(), Some("0001"));
+
+    let format = RadixFormat::new(16).unwrap().group(4, ' ');
+    assert_eq!(format.format(u32::MAX as f64 + 0.5), "ffff ffff.8");
+    assert_eq!(format.format(f64::NAN), "NaN");
+    assert_eq!(format.group(0, ' ').format(65536.0), "10000");
+}
+
+#[cfg(feature = "format")]
+#[test]
+fn test_format_truncate() {
+    let format = RadixFormat::new(16).unwrap().truncate(8, "…");
+    assert_eq!(format.format_truncated(0.123), ("0.1f7ce…".to_string(), 8));
+    assert_eq!(format.format_truncated(26.5), ("1a.8".to_string(), 0));
+    assert_eq!(format.format_truncated(-0.5), ("-0.8".to_string(), 0));
+    assert_eq!(format.format(f64::NEG_INFINITY), "-Infinity");
+    assert_eq!(format.truncate(3, "...").format(f64::NAN), "NaN");
+
+    // Separators aren't left dangling before the marker.
+    let format = RadixFormat::new(2).unwrap().group(4, '_').truncate(7, "..");
+    assert_eq!(format.format_truncated(170.0), ("1010..".to_string(), 4));
+}
+
+#[cfg(feature = "parse")]
+#[test]
+fn test_scan_radix_literal() {
+    assert_eq!(scan_radix_literal("1a.8)", 16).unwrap(), Some((4, 26.5)));
+    assert_eq!(scan_radix_literal("-101.1 + x", 2).unwrap(), Some((6, -5.5)));
+    assert_eq!(scan_radix_literal("12.", 10).unwrap(), Some((2, 12.0)));
+    assert_eq!(scan_radix_literal("12.x", 10).unwrap(), Some((2, 12.0)));
+    assert_eq!(scan_radix_literal("19", 8).unwrap(), Some((1, 1.0)));
+    assert_eq!(scan_radix_literal("-Infinity;", 10).unwrap(), Some((9, f64::NEG_INFINITY)));
+    assert_eq!(scan_radix_literal("Infinityz", 36).unwrap().map(|(len, _)| len), Some(9));
+    assert!(scan_radix_literal("NaN,", 36).unwrap().unwrap().1.is_nan());
+
+    assert_eq!(scan_radix_literal("", 10).unwrap(), None);
+    assert_eq!(scan_radix_literal("-", 10).unwrap(), None);
+    assert_eq!(scan_radix_literal(".5", 10).unwrap(), None);
+    assert_eq!(scan_radix_literal("+-1", 10).unwrap(), None);
+    assert!(scan_radix_literal("1", 1).is_err());
+}
+
+#[cfg(feature = "parse")]
+#[test]
+fn test_parse_radix_partial() {
+    assert_eq!(parse_radix_partial(b" \t\n-101.1 + x", 2).unwrap(), (-5.5, 9));
+    assert_eq!(parse_radix_partial(b"12.x", 10).unwrap(), (12.0, 3));
+    assert_eq!(parse_radix_partial(b"1a.8\xFF", 16).unwrap(), (26.5, 4));
+    assert_eq!(parse_radix_partial(b"-Infinity.", 10).unwrap(), (f64::NEG_INFINITY, 9));
+    assert_eq!(parse_radix_partial(b"1e400", 10).unwrap(), (1.0, 1));
+    assert_eq!(parse_radix_partial(b"  ", 10).unwrap(), (0.0, 0));
+    assert_eq!(parse_radix_partial(b"\xFF1", 10).unwrap(), (0.0, 0));
+    assert_eq!(parse_radix_partial(b".5", 10).unwrap(), (0.0, 0));
+    assert!(parse_radix_partial(b"1", 37).is_err());
+}
+
+#[cfg(feature = "nom")]
+#[test]
+fn test_combinators() {
+    use crate::combinators::{js_literal, js_numeric_literal, radix_float};
+    type Error<'a> = nom::error::Error<&'a str>;
+
+    assert_eq!(radix_float::<Error>(2)("-101.1 + x"), Ok((" + x", -5.5)));
+    assert!(matches!(radix_float::<Error>(16)("xyz"), Err(nom::Err::Error(_))));
+    assert!(matches!(radix_float::<Error>(37)("1"), Err(nom::Err::Failure(_))));
+
+    assert_eq!(js_numeric_literal::<Error>()("0xff)"), Ok((")", 255.0)));
+    assert!(js_numeric_literal::<Error>()("-1").is_err());
+    assert_eq!(js_literal::<Error>()("0xffn)"), Ok((")", JsLiteral::BigInt(255))));
+}
+
+#[cfg(all(feature = "parse", feature = "ecma-methods"))]
+#[test]
+fn test_scan_js_numeric_literal() {
+    assert_eq!(scan_js_numeric_literal("1.5e-7;"), Some((6, 1.5e-7)));
+    assert_eq!(scan_js_numeric_literal(".5)"), Some((2, 0.5)));
+    assert_eq!(scan_js_numeric_literal("1.toString"), Some((2, 1.0)));
+    assert_eq!(scan_js_numeric_literal("1e+"), Some((1, 1.0)));
+    assert_eq!(scan_js_numeric_literal("0xFF_FF"), Some((7, 65535.0)));
+    assert_eq!(scan_js_numeric_literal("0b101n"), Some((5, 5.0)));
+    assert_eq!(scan_js_numeric_literal("0o17"), Some((4, 15.0)));
+    assert_eq!(scan_js_numeric_literal("1_000_000"), Some((9, 1e6)));
+    assert_eq!(scan_js_numeric_literal("1__0"), Some((1, 1.0)));
+    assert_eq!(scan_js_numeric_literal("017"), Some((1, 0.0)));
+    assert_eq!(scan_js_numeric_literal("1e400"), Some((5, f64::INFINITY)));
+    assert_eq!(scan_js_numeric_literal("0x"), None);
+    assert_eq!(scan_js_numeric_literal("."), None);
+    assert_eq!(scan_js_numeric_literal("-1"), None);
+}
+
+#[cfg(all(feature = "parse", feature = "f64"))]
+#[test]
+fn test_from_repeating_radix_str() {
+    assert_eq!(from_repeating_radix_str("0.(142857)", 10).unwrap(), 1.0 / 7.0);
+    assert_eq!(from_repeating_radix_str("-0.1(6)", 10).unwrap(), -1.0 / 6.0);
+    assert_eq!(from_repeating_radix_str("12.(3)", 10).unwrap(), 37.0 / 3.0);
+    assert_eq!(from_repeating_radix_str("0.(9)", 10).unwrap(), 1.0);
+    assert_eq!(from_repeating_radix_str("0.(1)", 2).unwrap(), 1.0);
+    assert_eq!(from_repeating_radix_str("0.0(0121)", 3).unwrap(), 1.0 / 15.0);
+    assert_eq!(from_repeating_radix_str("0.(z)", 36).unwrap(), 1.0);
+    assert_eq!(from_repeating_radix_str("1a.8(0)", 16).unwrap(), 26.5);
+    assert_eq!(from_repeating_radix_str("1a.8", 16).unwrap(), 26.5);
+    assert!(from_repeating_radix_str("-0.(0)", 10).unwrap().is_sign_negative());
+
+    // 0.(1) is 1/2 in base 3, so these are halfway between two f64s and round
+    // to even.
+    let halfway = format!("{}.(1)", (4503599627370497.0).to_radix_str(3).unwrap());
+    assert_eq!(from_repeating_radix_str(&halfway, 3).unwrap(), 4503599627370498.0);
+    let halfway = format!("{}.(1)", (4503599627370498.0).to_radix_str(3).unwrap());
+    assert_eq!(from_repeating_radix_str(&halfway, 3).unwrap(), 4503599627370498.0);
+
+    // Too long for exact arithmetic.
+    let long = format!("0.{}(1)", "0".repeat(5000));
+    assert_eq!(from_repeating_radix_str(&format!("1{}", &long[1..]), 2).unwrap(), 1.0);
+    assert_eq!(from_repeating_radix_str(&format!("0.{}(9)", "1".repeat(2000)), 10).unwrap(), 1.0 / 9.0);
+    assert_eq!(from_repeating_radix_str(&format!("0.1{}(9)", "0".repeat(2000)), 10).unwrap(), 0.1);
+
+    // A tiny period, which only becomes significant after many leading zeros.
+    // 2^-1001 / (1 - 2^-1001) rounds to 2^-1001.
+    let tiny = format!("0.({}1)", "0".repeat(1000));
+    assert_eq!(from_repeating_radix_str(&tiny, 2).unwrap(), f64::from_bits(22 << 52));
+
+    // Halfway between the two smallest subnormals, as in test_from_radix_str.
+    let (tie, _) = rebase_radix_str(&format!("0.{}11", "0".repeat(1073)), 2, 10, usize::MAX).unwrap();
+    let tie = &tie[..tie.len() - 1];
+    assert_eq!(from_repeating_radix_str(&format!("{tie}4(9)"), 10).unwrap(), f64::from_bits(2));
+    assert_eq!(from_repeating_radix_str(&format!("{tie}4(8)"), 10).unwrap(), f64::from_bits(1));
+    assert_eq!(from_repeating_radix_str(&format!("{tie}5(1)"), 10).unwrap(), f64::from_bits(2));
+
+    assert!(matches!(from_repeating_radix_str("1(3)", 10), Err(ParseRadixError::InvalidDigit(1))));
+    assert!(matches!(from_repeating_radix_str("0.()", 10), Err(ParseRadixError::InvalidDigit(3))));
+    assert!(matches!(from_repeating_radix_str("0.(3", 10), Err(ParseRadixError::InvalidDigit(4))));
+    assert!(matches!(from_repeating_radix_str("0.(3)1", 10), Err(ParseRadixError::InvalidDigit(6))));
+    assert!(matches!(from_repeating_radix_str("0.(2)", 2), Err(ParseRadixError::InvalidDigit(3))));
+    assert!(from_repeating_radix_str("0.(1)", 37).is_err());
+}
+
+#[cfg(all(feature = "parse", feature = "ecma-methods"))]
+#[test]
+fn test_scan_js_literal() {
+    use JsLiteral::{BigInt, Number};
+
+    assert_eq!(scan_js_literal("123n;"), Some((4, BigInt(123))));
+    assert_eq!(scan_js_literal("0n"), Some((2, BigInt(0))));
+    assert_eq!(scan_js_literal("0xFF_FFn"), Some((8, BigInt(65535))));
+    assert_eq!(scan_js_literal("0o17n"), Some((5, BigInt(15))));
+    assert_eq!(scan_js_literal("0b101n"), Some((6, BigInt(5))));
+    assert_eq!(scan_js_literal("9007199254740993n"), Some((17, BigInt(9007199254740993))));
+    assert_eq!(scan_js_literal("170141183460469231731687303715884105727n"), Some((40, BigInt(i128::MAX))));
+    assert_eq!(scan_js_literal("170141183460469231731687303715884105728n"), None);
+    assert_eq!(scan_js_literal("1.5n"), Some((3, Number(1.5))));
+    assert_eq!(scan_js_literal("1.n"), Some((2, Number(1.0))));
+    assert_eq!(scan_js_literal("1e3n"), Some((3, Number(1000.0))));
+    assert_eq!(scan_js_literal("01n"), Some((1, Number(0.0))));
+    assert_eq!(scan_js_literal("1_000"), Some((5, Number(1000.0))));
+    assert_eq!(scan_js_literal("n"), None);
+}
+
+#[cfg(all(feature = "parse", feature = "ecma-methods"))]
+#[test]
+fn test_parse_json5_number() {
+    assert_eq!(parse_json5_number("+.5").unwrap(), 0.5);
+    assert_eq!(parse_json5_number("5.").unwrap(), 5.0);
+    assert_eq!(parse_json5_number("-0x1F").unwrap(), -31.0);
+    assert_eq!(parse_json5_number("1e3").unwrap(), 1000.0);
+    assert_eq!(parse_json5_number("-Infinity").unwrap(), f64::NEG_INFINITY);
+    assert!(parse_json5_number("+NaN").unwrap().is_nan());
+    assert!(parse_json5_number("-0").unwrap().is_sign_negative());
+    assert!(matches!(parse_json5_number("-"), Err(ParseRadixError::Empty)));
+    assert!(matches!(parse_json5_number("1.5x"), Err(ParseRadixError::InvalidDigit(3))));
+    assert!(matches!(parse_json5_number("+-1"), Err(ParseRadixError::InvalidDigit(1))));
+    assert!(matches!(parse_json5_number(" 1"), Err(ParseRadixError::InvalidDigit(0))));
+}
+
+#[cfg(all(feature = "format", feature = "ecma-methods"))]
+#[test]
+fn test_number_printer() {
+    let print = |printer: &dyn NumberPrinter, value: f64| {
+        let mut out = String::new();
+        printer.print_number(value, &mut out).unwrap();
+        out
+    };
+
+    assert_eq!(print(&DefaultToString, 1e21), "1e+21");
+    assert_eq!(print(&DefaultToString, -0.000001), "-0.000001");
+    assert_eq!(print(&DefaultToString, -0.0), "0");
+    assert_eq!(print(&DefaultToString, f64::NEG_INFINITY), "-Infinity");
+    assert_eq!(print(&DefaultToString, f64::NAN), "NaN");
+    assert_eq!(print(&ShortestLiteral, 1000.0), "1e3");
+    assert_eq!(print(&RadixFormat::new(16).unwrap(), 26.5), "1a.8");
+    assert_eq!(print(&|value: f64| format!("{:?}", value), 1.0), "1.0");
+}
+
+#[cfg(all(feature = "num-complex", feature = "f32", feature = "f64"))]
+#[test]
+fn test_complex() {
+    use num_complex::{Complex32, Complex64};
+
+    assert_eq!(Complex64::new(255.5, 0.5).to_radix_str(16).unwrap(), "ff.8+0.8i");
+    assert_eq!(Complex64::new(-1.0, -2.0).to_radix_str(2).unwrap(), "-1-10i");
+    assert_eq!(Complex64::new(0.0, -0.0).to_radix_str(10).unwrap(), "0+0i");
+    assert_eq!(Complex64::new(f64::NAN, f64::NEG_INFINITY).to_radix_str(36).unwrap(), "NaN-Infinityi");
+    assert_eq!(Complex32::new(0.1, 0.1).to_radix_str(10).unwrap(), "0.10000000149011612+0.10000000149011612i");
+    assert_eq!(complex_to_radix_str(Complex64::new(3.0, 4.0), 8, "j").unwrap(), "3+4j");
+    assert_eq!(Hex::from(Complex64::new(10.0, 11.0)).to_string(), "a+bi");
+    assert!(Complex64::new(1.0, 1.0).to_radix_str(37).is_err());
+}
+
+#[cfg(all(feature = "ordered-float", feature = "f32", feature = "f64"))]
+#[test]
+fn test_ordered_float() {
+    use ordered_float::{NotNan, OrderedFloat};
+
+    assert_eq!(OrderedFloat(26.5f64).to_radix_str(16).unwrap(), "1a.8");
+    assert_eq!(OrderedFloat(0.5f32).to_radix_str(2).unwrap(), "0.1");
+    assert_eq!(NotNan::new(-255.0f64).unwrap().to_radix_str(16).unwrap(), "-ff");
+
+    assert_eq!(OrderedFloat::<f64>::from_radix_str("1a.8", 16).unwrap(), OrderedFloat(26.5));
+    assert!(OrderedFloat::<f64>::from_radix_str("NaN", 16).unwrap().is_nan());
+    assert_eq!(NotNan::<f32>::from_radix_str("-0.1", 2).unwrap(), NotNan::new(-0.5).unwrap());
+    assert!(matches!(NotNan::<f64>::from_radix_str("NaN", 16), Err(ParseRadixError::NaN)));
+}
+
+#[cfg(feature = "primitive-types")]
+#[test]
+fn test_u256() {
+    use primitive_types::U256;
+
+    assert_eq!(U256::MAX.to_radix_str(16).unwrap(), "f".repeat(64));
+    assert_eq!(U256::MAX.to_radix_str(2).unwrap(), "1".repeat(256));
+    assert_eq!(U256::zero().to_radix_str(36).unwrap(), "0");
+    assert_eq!(U256::from(1_000_000u64).to_radix_str(36).unwrap(), "lfls");
+    assert_eq!(U256::from_radix_str("LFLS", 36).unwrap(), U256::from(1_000_000u64));
+    for base in MIN_BASE..=MAX_BASE {
+        let n = U256::MAX / U256::from(base as u64 * 7 + 3);
+        assert_eq!(U256::from_radix_str(&n.to_radix_str(base).unwrap(), base).unwrap(), n, "{}", base);
+        assert_eq!(U256::from_radix_str(&U256::MAX.to_radix_str(base).unwrap(), base).unwrap(), U256::MAX);
+    }
+
+    assert!(matches!(U256::from_radix_str(&"f".repeat(65), 16), Err(ParseRadixError::Overflow)));
+    assert!(matches!(U256::from_radix_str("12.5", 10), Err(ParseRadixError::InvalidDigit(2))));
+    assert!(matches!(U256::from_radix_str("", 10), Err(ParseRadixError::Empty)));
+    assert!(matches!(U256::from_radix_str("1", 37), Err(ParseRadixError::InvalidBase(_))));
+    assert!(U256::one().to_radix_str(1).is_err());
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn test_serde_json() {
+    use serde_json::Number;
+
+    assert_eq!(Number::from(255u64).to_radix_str(16).unwrap(), "ff");
+    assert_eq!(Number::from(u64::MAX).to_radix_str(16).unwrap(), "ffffffffffffffff");
+    assert_eq!(Number::from(i64::MIN).to_radix_str(16).unwrap(), "-8000000000000000");
+    assert_eq!(Number::from_f64(-26.5).unwrap().to_radix_str(16).unwrap(), "-1a.8");
+    assert!(Number::from(1u64).to_radix_str(37).is_err());
+
+    let parse = |s: &str, base: Base| Number::from_radix_str(s, base);
+    assert!(parse("ffffffffffffffff", 16).unwrap().is_u64());
+    assert_eq!(parse("-8000000000000000", 16).unwrap().as_i64(), Some(i64::MIN));
+    assert_eq!(parse("+10", 2).unwrap().as_u64(), Some(2));
+    assert!(parse("10000000000000000", 16).unwrap().is_f64());
+    assert!(parse("-8000000000000001", 16).unwrap().is_f64());
+    assert_eq!(parse("-0", 16).unwrap().as_f64().map(f64::is_sign_negative), Some(true));
+    assert_eq!(parse("1a.8", 16).unwrap().as_f64(), Some(26.5));
+    assert!(matches!(parse("NaN", 16), Err(ParseRadixError::NaN)));
+    assert!(matches!(parse("-Infinity", 16), Err(ParseRadixError::Overflow)));
+    assert!(matches!(parse("1g", 16), Err(ParseRadixError::InvalidDigit(1))));
+    assert!(matches!(parse("z", 37), Err(ParseRadixError::InvalidBase(_))));
+}
+
+#[cfg(feature = "ecma-methods")]
+#[test]
+fn test_to_radix_str_js() {
+    assert_eq!(to_radix_str_js(255.5, Some(2.9)).unwrap(), "11111111.1");
+    assert_eq!(to_radix_str_js(255.5, Some(36.99)).unwrap(), "73.i");
+    assert_eq!(to_radix_str_js(255.5f32, None).unwrap(), "255.5");
+    for radix in [f64::NAN, 1.9, -0.5, f64::INFINITY, 37.0, -1e10] {
+        assert!(to_radix_str_js(255.5, Some(radix)).is_err());
+    }
+
+    assert_eq!(coerce_radix(16u8).unwrap(), 16);
+    assert_eq!(coerce_radix(f64::INFINITY).unwrap_err().to_string(), "invalid base: 255");
+    assert_eq!(coerce_radix(-3.5).unwrap_err().to_string(), "invalid base: 0");
+}
+
+#[cfg(feature = "ecma-methods")]
+#[test]
+fn test_to_uint32_radix_str() {
+    assert_eq!(to_uint32_radix_str(255, 16).unwrap(), "ff");
+    assert_eq!(to_uint32_radix_str(-1, 16).unwrap(), "ffffffff");
+    assert_eq!(to_uint32_radix_str(-2, 2).unwrap(), "11111111111111111111111111111110");
+    assert_eq!(to_uint32_radix_str(-255.9, 16).unwrap(), "ffffff01");
+    assert_eq!(to_uint32_radix_str(4294967296.0 + 10.0, 10).unwrap(), "10");
+    assert_eq!(to_uint32_radix_str(-1e20, 10).unwrap(), "2632974336");
+    assert_eq!(to_uint32_radix_str(f64::NAN, 16).unwrap(), "0");
+    assert_eq!(to_uint32_radix_str(f64::NEG_INFINITY, 16).unwrap(), "0");
+    assert_eq!(to_uint32_radix_str(-0.5, 16).unwrap(), "0");
+    assert!(to_uint32_radix_str(1, 37).is_err());
+}
+
+#[cfg(all(feature = "ecma-methods", feature = "f64"))]
+#[test]
+fn test_range_error() {
+    let error = RangeError::from(255.5.to_radix_str(1).unwrap_err());
+    assert_eq!(error.to_string(), "RangeError: toString() radix argument must be between 2 and 36");
+    assert_eq!(error, RangeError::default());
+    assert_eq!(
+        RangeError::new(Compat::SpiderMonkey).message(),
+        "radix must be an integer at least 2 and no greater than 36"
+    );
+}
+
+/// Checks every f32 bit pattern in the bases listed in `RADIX_SWEEP_BASES`
+/// (comma-separated, defaulting to 2, 10, 16 and 36). Takes hours per base, so
+/// run it in release mode:
+/// `cargo test --release --features exhaustive-f32 test_exhaustive_f32`.
+#[cfg(feature = "exhaustive-f32")]
+#[test]
+#[cfg_attr(miri, ignore = "takes years under Miri")]
+fn test_exhaustive_f32() {
+    let bases: Vec<Base> = std::env::var("RADIX_SWEEP_BASES")
+        .unwrap_or_else(|_| "2,10,16,36".into())
+        .split(',')
+        .map(|base| base.trim().parse().unwrap())
+        .collect();
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get()) as u64;
+
+    for base in bases {
+        std::thread::scope(|scope| {
+            for thread in 0..threads {
+                scope.spawn(move || {
+                    let start = (thread << 32) / threads;
+                    let end = ((thread + 1) << 32) / threads;
+                    for bits in start..end {
+                        let value = f32::from_bits(bits as u32);
+                        let s = value.to_radix_str(base).unwrap();
+                        assert_eq!(s, (value as f64).to_radix_str(base).unwrap());
+                        assert!(s.len() <= max_len_f32(base), "{} in base {}", value, base);
+
+                        let parsed = f32::from_radix_str(&s, base).unwrap();
+                        assert!(
+                            parsed.to_bits() == value.to_bits()
+                                || (parsed.is_nan() && value.is_nan())
+                                || (parsed == 0.0 && value == 0.0),
+                            "{} in base {} parsed as {}",
+                            value,
+                            base,
+                            parsed
+                        );
+                    }
+                });
+            }
+        });
+    }
+}
+
+/// Snapshots the output for a corpus of value classes in every base, so output
+/// changes show up as reviewable diffs. Review changes with `cargo insta review`.
+#[cfg(all(feature = "std", feature = "f64"))]
+#[test]
+#[cfg_attr(miri, ignore = "insta runs cargo, which Miri can't")]
+fn test_snapshots() {
+    use std::fmt::Write;
+    use crate::double::{next_float, prev_float};
+
+    let corpus: [(&str, Vec<f64>); 5] = [
+        ("specials", vec![0.0, -0.0, f64::NAN, f64::INFINITY, f64::NEG_INFINITY]),
+        ("subnormals", vec![5e-324, -1e-310, prev_float(f64::MIN_POSITIVE)]),
+        ("powers of two", vec![2f64.powi(-1022), 0.5, 1.0, 2f64.powi(53), 2f64.powi(1023)]),
+        ("near ties", vec![prev_float(0.5), next_float(0.5), 2.5, 0.1, 1.0 / 3.0, 4503599627370495.5]),
+        ("huge magnitudes", vec![1e21, 123.456e30, 1e300, f64::MAX]),
+    ];
+
+    for base in MIN_BASE..=MAX_BASE {
+        let mut snapshot = String::new();
+        for (class, values) in &corpus {
+            writeln!(snapshot, "# {}", class).unwrap();
+            for value in values {
+                writeln!(snapshot, "{:e} => {}", value, value.to_radix_str(base).unwrap()).unwrap();
+            }
+        }
+        insta::assert_snapshot!(format!("to_radix_str_base_{}", base), snapshot);
+    }
+}
+
+/// Cross-checks random (value, base) pairs for internal consistency. The seed
+/// and number of pairs come from `RADIX_CROSS_CHECK_SEED` and
+/// `RADIX_CROSS_CHECK_ITERATIONS` (defaulting to 0 and 1,000,000), so a
+/// nightly job can run
+/// `cargo test --release --features cross-check test_cross_check`.
+#[cfg(feature = "cross-check")]
+#[test]
+#[cfg_attr(miri, ignore = "takes days under Miri")]
+fn test_cross_check() {
+    use crate::double::ulp;
+
+    let var = |name: &str, default: u64| std::env::var(name).map_or(default, |v| v.parse().unwrap());
+    let mut state = var("RADIX_CROSS_CHECK_SEED", 0);
+    let iterations = var("RADIX_CROSS_CHECK_ITERATIONS", 1_000_000);
+
+    // SplitMix64.
+    let mut next = || {
+        state = state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    };
+
+    for _ in 0..iterations {
+        let value = f64::from_bits(next());
+        let base = (next() % (MAX_BASE - MIN_BASE + 1) as u64) as Base + MIN_BASE;
+        if !value.is_finite() {
+            continue;
+        }
+
+        let s = value.to_radix_str(base).unwrap();
+        let parsed = f64::from_radix_str(&s, base).unwrap();
+        if base.is_power_of_two() {
+            // Digits in power-of-two bases are exact, so format -> parse -> format is a fixpoint.
+            assert_eq!(parsed, value, "{:e} in base {}", value, base);
+            assert_eq!(parsed.to_radix_str(base).unwrap(), s, "{:e} in base {}", value, base);
+        } else if (1.0..9007199254740992.0).contains(&value.abs()) {
+            // The fast path is accurate to within an ulp in this range.
+            assert!((parsed - value).abs() <= ulp(value), "{:e} in base {}: {}", value, base, s);
+        }
+
+        // The exact (SpiderMonkey) digits always round-trip.
+        let exact = to_radix_str_compat(value, base, Compat::SpiderMonkey).unwrap();
+        assert_eq!(f64::from_radix_str(&exact, base).unwrap(), value, "{:e} in base {}: {}", value, base, exact);
+    }
+}
+
+#[cfg(all(feature = "buffer-pool", feature = "format", feature = "f32", feature = "f64"))]
+#[test]
+fn test_buffer_pool() {
+    for enabled in [true, false, true] {
+        set_buffer_pool_enabled(enabled);
+        assert_eq!((0.123).to_radix_str(16).unwrap(), "0.1f7ced916872b");
+        assert_eq!((-26.5f32).to_radix_str(16).unwrap(), "-1a.8");
+        assert_eq!(RadixFormat::new(2).unwrap().format(5e-324).len(), 1076);
+    }
+}
+
+#[cfg(all(feature = "format", feature = "parse"))]
+#[test]
+fn test_format_nan_payload() {
+    let format = RadixFormat::new(16).unwrap().nan_payload(true);
+    let signaling = f64::from_bits(0xFFF0_0000_0000_0001);
+    assert_eq!(format.format(f64::NAN), "NaN(0x8000000000000)");
+    assert_eq!(format.format(signaling), "-NaN(0x1)");
+    assert_eq!(format.format(26.5), "1a.8");
+    assert_eq!(RadixFormat::new(16).unwrap().format(signaling), "NaN");
+
+    assert_eq!(format.parse("-NaN(0x1)").unwrap().to_bits(), signaling.to_bits());
+    assert_eq!(format.parse("NaN(0x8000000000000)").unwrap().to_bits(), f64::NAN.to_bits());
+    assert!(format.parse("NaN").unwrap().is_nan());
+    assert!(format.parse("NaN(0x0)").is_err());
+    assert!(format.parse("NaN(0x10000000000000)").is_err());
+}
+
+#[cfg(all(feature = "parse", feature = "f32", feature = "f64"))]
+#[test]
+fn test_bits_radix_str() {
+    assert_eq!(bits_to_radix_str(1.0, 16).unwrap(), "3ff0000000000000");
+    assert_eq!(bits_to_radix_str(0.0, 36).unwrap(), "0");
+    assert_eq!(bits_to_radix_str_f32(-0.0, 2).unwrap(), format!("1{}", "0".repeat(31)));
+
+    let signaling = f64::from_bits(0xFFF0_0000_0000_0001);
+    for value in [0.123, -0.0, 5e-324, f64::MAX, f64::NEG_INFINITY, signaling] {
+        for base in MIN_BASE..=MAX_BASE {
+            let s = bits_to_radix_str(value, base).unwrap();
+            assert_eq!(bits_from_radix_str(&s, base).unwrap().to_bits(), value.to_bits());
+        }
+    }
+    let s = bits_to_radix_str_f32(f32::from_bits(0x7FA0_0001), 36).unwrap();
+    assert_eq!(bits_from_radix_str_f32(&s, 36).unwrap().to_bits(), 0x7FA0_0001);
+
+    assert_eq!(bits_from_radix_str("3FF0000000000000", 16).unwrap(), 1.0);
+    assert!(matches!(bits_from_radix_str("10000000000000000", 16), Err(ParseRadixError::Overflow)));
+    assert!(matches!(bits_from_radix_str_f32("100000000", 16), Err(ParseRadixError::Overflow)));
+    assert!(matches!(bits_from_radix_str("-1", 16), Err(ParseRadixError::InvalidDigit(0))));
+    assert!(matches!(bits_from_radix_str("", 16), Err(ParseRadixError::Empty)));
+    assert!(bits_to_radix_str(1.0, 37).is_err());
+}
+
+#[cfg(all(feature = "quickcheck", feature = "f64"))]
+#[test]
+fn test_arbitrary() {
+    use quickcheck::{quickcheck, TestResult};
+    use crate::arbitrary::{exact_round_trip, RadixStrInput, ValidBase};
+
+    fn prop_valid_base(base: ValidBase) -> bool {
+        (MIN_BASE..=MAX_BASE).contains(&base.0)
+    }
+    fn prop_parses(input: RadixStrInput) -> bool {
+        f64::from_radix_str(&input.text, input.base).is_ok()
+    }
+    fn prop_compat_converts(value: f64, base: ValidBase, compat: Compat) -> bool {
+        to_radix_str_compat(value, base.0, compat).is_ok()
+    }
+    fn prop_format_parses(format: RadixFormat, value: f64) -> TestResult {
+        if format.base().is_power_of_two() && !value.is_nan() {
+            return TestResult::from_bool(format.parse(&format.format(value).replace(['_', ' '], "")).ok() == Some(value));
+        }
+        TestResult::discard()
+    }
+
+    quickcheck(prop_valid_base as fn(ValidBase) -> bool);
+    quickcheck(prop_parses as fn(RadixStrInput) -> bool);
+    quickcheck(prop_compat_converts as fn(f64, ValidBase, Compat) -> bool);
+    quickcheck(prop_format_parses as fn(RadixFormat, f64) -> TestResult);
+    quickcheck(exact_round_trip as fn(f64, ValidBase) -> TestResult);
+}
+
+#[cfg(all(feature = "tracing", feature = "parse", feature = "f64"))]
+#[test]
+fn test_tracing() {
+    use std::sync::{Arc, Mutex};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    /// Records the message of every event.
+    struct Messages(Arc<Mutex<Vec<String>>>);
+
+    impl Subscriber for Messages {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, _: &Attributes<'_>) -> Id {
+            Id::from_u64(1)
+        }
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+        fn event(&self, event: &Event<'_>) {
+            struct Visitor<'a>(&'a mut Vec<String>);
+            impl tracing::field::Visit for Visitor<'_> {
+                fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+                    if field.name() == "message" {
+                        self.0.push(format!("{:?}", value));
+                    }
+                }
+            }
+            event.record(&mut Visitor(&mut self.0.lock().unwrap()));
+        }
+        fn enter(&self, _: &Id) {}
+        fn exit(&self, _: &Id) {}
+    }
+
+    let messages = Arc::new(Mutex::new(Vec::new()));
+    tracing::subscriber::with_default(Messages(messages.clone()), || {
+        (0.2).to_radix_str(3).unwrap();
+        f64::from_radix_str("0.1", 3).unwrap();
+        to_radix_str_compat(0.1, 3, Compat::SpiderMonkey).unwrap();
+    });
+
+    let messages = messages.lock().unwrap();
+    assert!(messages.contains(&"carry back-propagation".to_string()));
+    assert!(messages.contains(&"bignum parse".to_string()));
+    assert!(messages.contains(&"bignum conversion".to_string()));
+}
+
+#[cfg(all(feature = "std", feature = "f64"))]
+#[test]
+fn test_to_radix_str_base() {
+    assert_eq!(to_radix_str_base::<16>(0.123), "0.1f7ced916872b");
+    assert_eq!(to_radix_str_base::<2>(-26.5f32), "-11010.1");
+    assert_eq!(to_radix_str_base::<36>(f64::NAN), "NaN");
+    assert_eq!(to_radix_str_base::<7>(1e21), (1e21).to_radix_str(7).unwrap());
+}
+
+#[cfg(all(feature = "std", feature = "f64"))]
+#[test]
+fn test_digit_counts() {
+    assert_eq!(integer_digit_count(255.5, 16).unwrap(), Some(2));
+    assert_eq!(integer_digit_count(256.0, 16).unwrap(), Some(3));
+    assert_eq!(integer_digit_count(-0.5, 2).unwrap(), Some(1));
+    assert_eq!(integer_digit_count(0.0, 36).unwrap(), Some(1));
+    assert_eq!(integer_digit_count(f64::MAX, 2).unwrap(), Some(1024));
+    assert_eq!(integer_digit_count(1e21, 7).unwrap(), Some((1e21).to_radix_str(7).unwrap().len()));
+    assert_eq!(integer_digit_count(f64::INFINITY, 10).unwrap(), None);
+
+    assert_eq!(ilog_radix(1.0, 10).unwrap(), Some(0));
+    assert_eq!(ilog_radix(-999.0, 10).unwrap(), Some(2));
+    assert_eq!(ilog_radix(1000.0, 10).unwrap(), Some(3));
+    assert_eq!(ilog_radix(0.001, 10).unwrap(), Some(-3));
+    assert_eq!(ilog_radix(5e-324, 2).unwrap(), Some(-1074));
+    assert_eq!(ilog_radix(0.0, 10).unwrap(), None);
+    assert_eq!(ilog_radix(f64::NAN, 10).unwrap(), None);
+    assert!(ilog_radix(1.0, 37).is_err());
+}
+
+#[cfg(all(feature = "parse", feature = "f32", feature = "f64"))]
+#[test]
+fn test_radix_wrappers() {
+    let s = RadixString::new(26.5, 16).unwrap();
+    assert_eq!((s.as_str(), s.base()), ("1a.8", 16));
+    assert_eq!(s.to_f64(), 26.5);
+    assert_eq!(RadixString::from_radix_str("1A.80", 16).unwrap().to_f64(), 26.5);
+    assert!(RadixString::from_radix_str("1g", 16).is_err());
+    assert!(RadixString::new(1.0, 37).is_err());
+
+    assert_eq!(Radix::<f32, 2>(-0.5).to_string(), "-0.1");
+    assert_eq!("ff".parse::<Hex<f64>>().unwrap(), Hex::from(255.0));
+    assert!("ff".parse::<Radix<f64, 10>>().is_err());
+}
+
+#[cfg(all(feature = "sqlx", feature = "f64"))]
+#[test]
+fn test_sqlx() {
+    use sqlx_core::any::{Any, AnyArgumentBuffer, AnyValue, AnyValueKind};
+    use sqlx_core::decode::Decode;
+    use sqlx_core::encode::{Encode, IsNull};
+    use sqlx_core::value::Value;
+
+    let mut buf = AnyArgumentBuffer(Vec::new());
+    assert!(matches!(Hex::from(26.5).encode_by_ref(&mut buf).unwrap(), IsNull::No));
+    assert!(matches!(RadixString::new(0.5, 2).unwrap().encode_by_ref(&mut buf).unwrap(), IsNull::No));
+
+    let values: Vec<_> = buf
+        .0
+        .into_iter()
+        .map(|kind| match kind {
+            AnyValueKind::Text(text) => AnyValue { kind: AnyValueKind::Text(text.into_owned().into()) },
+            kind => panic!("expected text, got {:?}", kind),
+        })
+        .collect();
+    assert_eq!(<Hex<f64> as Decode<Any>>::decode(values[0].as_ref()).unwrap(), Hex::from(26.5));
+    assert_eq!(<String as Decode<Any>>::decode(values[1].as_ref()).unwrap(), "0.1");
+    assert!(<Radix<f64, 2> as Decode<Any>>::decode(values[0].as_ref()).is_err());
+}
+
+#[cfg(all(feature = "parse", feature = "f64"))]
+#[test]
+fn test_ids() {
+    assert_eq!(encode_id(0), "0");
+    assert_eq!(encode_id(1_000_000), (1_000_000.0).to_radix_str(36).unwrap());
+    assert_eq!(encode_id(u64::MAX), "3w5e11264sgsf");
+    assert_eq!(encode_id_radix(u64::MAX, 62).unwrap(), "lYGhA16ahyf");
+    assert_eq!(encode_id_radix(u64::MAX, 2).unwrap().len(), ID_MAX_LEN);
+    assert!(encode_id_radix(1, 63).is_err());
+
+    let mut buf = [0; ID_MAX_LEN];
+    assert_eq!(write_id(255, 16, &mut buf).unwrap(), "ff");
+
+    assert_eq!(decode_id("3W5E11264SGSF").unwrap(), u64::MAX);
+    assert_eq!(decode_id_radix("lYGhA16ahyf", 62).unwrap(), u64::MAX);
+    assert_eq!(decode_id_radix("A", 62).unwrap(), 36);
+    assert!(matches!(decode_id("3w5e11264sgsg"), Err(ParseRadixError::Overflow)));
+    assert!(matches!(decode_id("1-"), Err(ParseRadixError::InvalidDigit(1))));
+    assert!(matches!(decode_id_radix("A", 10), Err(ParseRadixError::InvalidDigit(0))));
+    assert!(matches!(decode_id(""), Err(ParseRadixError::Empty)));
+}
+
+#[cfg(all(feature = "std", feature = "f64"))]
+#[test]
+fn test_to_radix_digits() {
+    let digits = to_radix_digits(255.5, 16).unwrap().unwrap();
+    assert!(!digits.is_negative());
+    assert_eq!((digits.digits(), digits.point()), (&[15, 15, 8][..], 2));
+
+    let digits = to_radix_digits(-0.25f32, 2).unwrap().unwrap();
+    assert!(digits.is_negative());
+    assert_eq!((digits.integer(), digits.fraction()), (&[0][..], &[0, 1][..]));
+
+    let digits = to_radix_digits(1e21, 36).unwrap().unwrap();
+    assert_eq!(digits.point(), digits.digits().len());
+    assert_eq!(digits.into_digits().len(), (1e21).to_radix_str(36).unwrap().len());
+
+    assert!(!to_radix_digits(-0.0, 10).unwrap().unwrap().is_negative());
+    assert_eq!(to_radix_digits(f64::NAN, 10).unwrap(), None);
+    assert!(to_radix_digits(1.0, 1).is_err());
+}
+
+#[cfg(all(feature = "std", feature = "f64"))]
+#[test]
+fn test_for_each_digit() {
+    // Rebuilding the string from the events gives the radix string back.
+    for value in [0.0, -0.0, 255.5, -0.1, 1e21, 5e-324, 1.0 / 3.0] {
+        for base in MIN_BASE..=MAX_BASE {
+            let mut s = String::new();
+            let finite = for_each_digit(value, base, |event| s.push(match event {
+                DigitEvent::Sign => '-',
+                DigitEvent::Point => '.',
+                DigitEvent::IntegerDigit(digit) | DigitEvent::FractionDigit(digit) => {
+                    char::from_digit(digit as u32, base as u32).unwrap()
+                }
+            }))
+            .unwrap();
+            assert!(finite);
+            assert_eq!(s, value.to_radix_str(base).unwrap(), "{:e} in base {}", value, base);
+        }
+    }
+
+    let mut events = Vec::new();
+    assert!(!for_each_digit(f64::NEG_INFINITY, 10, |event| events.push(event)).unwrap());
+    assert!(events.is_empty());
+    assert!(for_each_digit(1.0, 37, |_| {}).is_err());
+}
+
+#[cfg(all(feature = "simd", feature = "f64"))]
+#[test]
+fn test_simd() {
+    // Characters just outside the digit ranges, placed at every position of
+    // the first three chunks. `@`, just below `A`, marks an exponent, so `!`
+    // stands in for it.
+    for base in [2, 10, 11, 16, 36] {
+        let digit = char::from_digit(base as u32 - 1, base as u32).unwrap();
+        let valid = digit.to_string().repeat(48);
+        assert_eq!(f64::from_radix_str(&valid, base).unwrap(), f64::from_radix_str(&valid.to_uppercase(), base).unwrap());
+        for bad in ['/', ':', '!', '[', '`', '{', 'é', char::from_digit(base as u32 % 36, 36).unwrap()] {
+            if bad.is_digit(base as u32) {
+                continue;
+            }
+            for i in 0..48 {
+                let s = format!("{}{}{}", &valid[..i], bad, &valid[i + 1..]);
+                assert!(matches!(f64::from_radix_str(&s, base), Err(ParseRadixError::InvalidDigit(j)) if j == i), "{}", s);
+            }
+        }
+    }
+
+    // Long values in both cases, with the radix point at every offset.
+    for base in [2, 16, 32] {
+        let s = (0.05217266072382676).to_radix_str(base).unwrap();
+        assert_eq!(f64::from_radix_str(&s.to_uppercase(), base).unwrap(), 0.05217266072382676);
+        for point in 1..s.len() - 2 {
+            let digits: String = s.chars().filter(|c| *c != '.').collect();
+            let shifted = format!("{}.{}", &digits[..point], &digits[point..]);
+            let scale = (1..point).fold(1.0, |scale, _| scale * base as f64);
+            assert_eq!(f64::from_radix_str(&shifted, base).unwrap(), 0.05217266072382676 * scale, "{}", shifted);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_bignum() {
+    use crate::bignum::{ratio_to_f64, Bignum};
+
+    let mut n = Bignum::from_u64(u64::MAX);
+    n.mul_small(u32::MAX);
+    n.add_small(1);
+    assert_eq!(n.to_string(), "79228162495817593515539431426");
+    assert_eq!(format!("{:>32}", n), format!("   {}", n));
+    assert_eq!(Bignum::default(), Bignum::zero());
+    assert_eq!(Bignum::zero().to_string(), "0");
+
+    let mut power = Bignum::pow(2, 4000);
+    assert_eq!(power.bit_len(), 4001);
+    let quotient = power.div_rem(&Bignum::pow(2, 3990));
+    assert!(power.is_zero());
+    assert_eq!(quotient.to_u64(), Some(1024));
+    assert_eq!(Bignum::pow(36, 2).to_digits(36), [1, 0, 0]);
+    assert_eq!(ratio_to_f64(&Bignum::from_u64(1), &Bignum::pow(2, 1074)), 5e-324);
+}
+
+#[cfg(feature = "parse")]
+#[test]
+fn test_bijective() {
+    let columns: Vec<String> = [1, 26, 27, 52, 702, 703, 16384].iter().map(|n| to_bijective(*n, SPREADSHEET_ALPHABET)).collect();
+    assert_eq!(columns, ["A", "Z", "AA", "AZ", "ZZ", "AAA", "XFD"]);
+    assert_eq!(to_bijective(0, SPREADSHEET_ALPHABET), "");
+    assert_eq!(to_bijective(10, "123456789A"), "A");
+    assert_eq!(to_bijective(u64::MAX, "12"), "1".repeat(64));
+
+    for n in [0, 1, 26, 27, 16384, u64::MAX] {
+        assert_eq!(from_bijective(&to_bijective(n, SPREADSHEET_ALPHABET), SPREADSHEET_ALPHABET).unwrap(), n);
+    }
+    assert_eq!(from_bijective("αβ", "αβγ").unwrap(), 5);
+    assert!(matches!(from_bijective("Aa", SPREADSHEET_ALPHABET), Err(ParseRadixError::InvalidDigit(1))));
+    assert!(matches!(from_bijective(&"2".repeat(64), "12"), Err(ParseRadixError::Overflow)));
+}
+
+#[cfg(feature = "parse")]
+#[test]
+fn test_factoradic() {
+    assert_eq!(to_factoradic(0), "0");
+    assert_eq!(to_factoradic(1), "10");
+    assert_eq!(to_factoradic(463), "341010");
+    assert_eq!(to_factoradic(3628800), "10000000000");
+    assert_eq!(factoradic_digits(u64::MAX).len(), 21);
+
+    for n in [0, 1, 5, 463, 3628800, u64::MAX] {
+        assert_eq!(from_factoradic(&to_factoradic(n)).unwrap(), n);
+    }
+    assert_eq!(from_factoradic("A0000000000").unwrap(), 36288000);
+    assert!(matches!(from_factoradic("201"), Err(ParseRadixError::InvalidDigit(2))));
+    assert!(matches!(from_factoradic("300"), Err(ParseRadixError::InvalidDigit(0))));
+    assert!(matches!(from_factoradic(""), Err(ParseRadixError::Empty)));
+    assert!(matches!(from_factoradic("k00000000000000000000"), Err(ParseRadixError::Overflow)));
+}
+
+#[cfg(all(feature = "std", feature = "f64"))]
+#[test]
+fn test_to_radix_str_deterministic() {
+    use crate::softfloat::Soft;
+
+    let mut state = 0u64;
+    // SplitMix64.
+    let mut next = || {
+        state = state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    };
+
+    // The emulated operations round like the FPU, including subnormal and
+    // overflowing results.
+    let iterations = if cfg!(miri) { 100 } else { 20_000 };
+    for _ in 0..iterations {
+        let (a, b) = (f64::from_bits(next() >> 1), f64::from_bits(next() >> (1 + next() % 12)));
+        if !a.is_finite() || !b.is_finite() {
+            continue;
+        }
+        let (x, y) = (Soft::from_f64_abs(a), Soft::from_f64_abs(b));
+        let check = |soft: Soft, hard: f64| assert_eq!(soft, Soft::from_f64_abs(hard), "{:e} {:e}", a, b);
+        check(x.add(y), a + b);
+        check(x.max(y).sub(x.min(y)), a.max(b) - a.min(b));
+        let base = (next() % 36 + 1) as u32;
+        check(x.rem_small(base), a % base as f64);
+        check(x.mul_small(base), a * base as f64);
+        check(x.div_small(base), a / base as f64);
+    }
+
+    let mut values = vec![
+        0.0, -0.0, 0.5, 1.0, -255.5, 0.1, 1.0 / 3.0, 1e21, 2f64.powi(53) + 2.0, 5e-324, f64::MIN_POSITIVE, f64::MAX,
+        f64::EPSILON, f64::NAN, f64::INFINITY, f64::NEG_INFINITY, 1.0 - f64::EPSILON / 2.0, -0.999,
+    ];
+    for _ in 0..iterations / 50 {
+        values.push(f64::from_bits(next()));
+        values.push((next() % 1_000_000) as f64 / (1 + next() % 1000) as f64);
+        // Values below one take a shorter path for the integer digit.
+        values.push((next() >> 11) as f64 / 2f64.powi(53));
+    }
+    for value in values {
+        for base in MIN_BASE..=MAX_BASE {
+            assert_eq!(
+                to_radix_str_deterministic(value, base).unwrap(),
+                value.to_radix_str(base).unwrap(),
+                "{:e} in base {}", value, base
+            );
+        }
+    }
+    assert!(to_radix_str_deterministic(1.0, 37).is_err());
+}
+
+#[cfg(all(feature = "std", feature = "f64"))]
+#[test]
+fn test_consts() {
+    use crate::consts::*;
+
+    assert_eq!(MAX_SAFE_INTEGER, 2f64.powi(53) - 1.0);
+    assert_eq!(MIN_VALUE, f64::from_bits(1));
+    assert_eq!(MIN_VALUE.to_radix_str(2).unwrap(), format!("0.{}1", "0".repeat(1073)));
+    assert_eq!(1.0 + EPSILON, double::next_float(1.0));
+    assert_eq!(MAX_VALUE.to_radix_str(2).unwrap(), format!("{}{}", "1".repeat(53), "0".repeat(971)));
+
+    assert!(is_integer(-0.0));
+    assert!(is_integer(1e300));
+    assert!(!is_integer(0.5));
+    assert!(!is_integer(f64::INFINITY));
+    assert!(!is_integer(f64::NAN));
+
+    assert!(is_safe_integer(MAX_SAFE_INTEGER));
+    assert!(is_safe_integer(MIN_SAFE_INTEGER));
+    assert!(!is_safe_integer(MAX_SAFE_INTEGER + 1.0));
+    assert!(!is_safe_integer(1.5));
+}
+
+#[cfg(all(feature = "std", feature = "f32", feature = "f64"))]
+#[test]
+fn test_float_format() {
+    for value in [0.1, -255.5, 1.0 / 3.0, 5e-324, f64::MIN_POSITIVE, f64::MAX, f64::NAN, f64::NEG_INFINITY] {
+        for base in MIN_BASE..=MAX_BASE {
+            assert_eq!(to_radix_str_format(value, base).unwrap(), value.to_radix_str(base).unwrap());
+        }
+    }
+
+    // f32 gets its own, shorter digits.
+    assert_eq!(to_radix_str_format(0.1f32, 10).unwrap(), "0.1");
+    assert_eq!(to_radix_str_format(f32::from_bits(1), 2).unwrap(), format!("0.{}1", "0".repeat(148)));
+    assert_eq!(to_radix_str_format(-f32::MAX, 16).unwrap(), format!("-ffffff{}", "0".repeat(26)));
+
+    assert_eq!(Binary16::from_bits(0x3555).to_radix_str(2).unwrap(), "0.010101010101");
+    assert_eq!(Binary16::from_bits(0x7BFF).to_radix_str(10).unwrap(), "65504");
+    assert_eq!(Fp8E5M2::from_bits(0x7B).to_radix_str(10).unwrap(), "57344");
+    assert_eq!(Fp8E5M2::from_bits(0x01).to_radix_str(2).unwrap(), "0.0000000000000001");
+    assert_eq!(Fp8E5M2::from_bits(0x80).to_radix_str(2).unwrap(), "0");
+    assert_eq!(Fp8E5M2::from_bits(0xFC).to_radix_str(2).unwrap(), "-Infinity");
+    assert_eq!(Fp8E5M2::from_bits(0x7D).to_radix_str(2).unwrap(), "NaN");
+    assert_eq!(Fp8E5M2::from_bits(0x13C), Fp8E5M2::from_bits(0x3C));
+    assert!(Bfloat16::from_bits(0x3DCD).to_radix_str(37).is_err());
+
+    // Every value of a format reads back closer to itself than to its neighbours.
+    #[cfg(feature = "parse")]
+    for bits in (1..0x7C00).step_by(7) {
+        let value = Binary16::from_bits(bits);
+        let exact = crate::float_format::to_f64(value);
+        for base in MIN_BASE..=MAX_BASE {
+            let parsed = f64::from_radix_str(&value.to_radix_str(base).unwrap(), base).unwrap();
+            assert!((parsed - exact).abs() <= crate::float_format::delta(value), "{:#x} in base {}", bits, base);
+        }
+    }
+}
+
+#[cfg(all(feature = "softposit", feature = "f64"))]
+#[test]
+fn test_posit() {
+    use softposit::{PxE2, P16E1, P32E2, P8E0};
+
+    assert_eq!(P8E0::from_bits(0x40).to_radix_str(10).unwrap(), "1");
+    assert_eq!(P8E0::from_bits(0x01).to_radix_str(2).unwrap(), "0.000001");
+    assert_eq!(P8E0::from_bits(0x7F).to_radix_str(16).unwrap(), "40");
+    assert_eq!(P8E0::from_bits(0xC0).to_radix_str(10).unwrap(), "-1");
+    assert_eq!(P8E0::from_bits(0).to_radix_str(10).unwrap(), "0");
+    assert_eq!(P8E0::NAR.to_radix_str(10).unwrap(), "NaR");
+    assert_eq!(P32E2::MAX.to_radix_str(2).unwrap(), format!("1{}", "0".repeat(120)));
+    assert_eq!(P32E2::from_f64(0.1).to_radix_str(10).unwrap(), "0.1");
+    assert_eq!(PxE2::<16>::from_f64(0.1).to_radix_str(10).unwrap(), "0.1");
+    assert!(P16E1::ONE.to_radix_str(37).is_err());
+
+    // Positive posits read back closer to themselves than to the next posit.
+    #[cfg(feature = "parse")]
+    for bits in (1..0x7FFFu16).step_by(7) {
+        let (value, next) = (P16E1::from_bits(bits).to_f64(), P16E1::from_bits(bits + 1).to_f64());
+        for base in MIN_BASE..=MAX_BASE {
+            let s = P16E1::from_bits(bits).to_radix_str(base).unwrap();
+            assert!((f64::from_radix_str(&s, base).unwrap() - value).abs() <= (next - value) / 2.0, "{:#x} in base {}", bits, base);
+            assert_eq!(P16E1::from_bits(bits.wrapping_neg()).to_radix_str(base).unwrap(), format!("-{}", s));
+        }
+    }
+}
+
+#[cfg(all(feature = "std", feature = "heapless", feature = "f64"))]
+#[test]
+fn test_to_radix_heapless() {
+    let s = to_radix_heapless::<{ MAX_LEN }>(-5e-324, 2).unwrap();
+    assert_eq!(s.as_str(), (-5e-324).to_radix_str(2).unwrap());
+    assert_eq!(to_radix_heapless::<4>(255.5, 16).unwrap(), "ff.8");
+    assert!(matches!(to_radix_heapless::<3>(255.5, 16), Err(WriteError::BufferTooSmall { required: 4 })));
+    assert!(matches!(to_radix_heapless::<8>(1.0, 37), Err(WriteError::InvalidBase(_))));
+}
+
+#[cfg(feature = "bumpalo")]
+#[test]
+fn test_to_radix_str_bump() {
+    let bump = bumpalo::Bump::new();
+    let strings: Vec<&str> = [0.5, -255.5, f64::NAN].iter().map(|v| to_radix_str_bump(*v, 16, &bump).unwrap()).collect();
+    assert_eq!(strings, ["0.8", "-ff.8", "NaN"]);
+    assert!(to_radix_str_bump(1.0, 1, &bump).is_err());
+}
+
+#[cfg(all(feature = "futures", feature = "f64"))]
+#[test]
+fn test_radix_stream() {
+    use std::task::Poll;
+    use futures::executor::block_on;
+    use futures::stream::{self, FusedStream, Stream, StreamExt};
+
+    let values: Vec<f64> = (0..200).map(|i| i as f64 / 8.0).collect();
+    let expected: Vec<String> = values.iter().map(|v| v.to_radix_str(36).unwrap()).collect();
+
+    let strings: Vec<String> = block_on(RadixStream::new(stream::iter(values.clone()), 36).unwrap().collect());
+    assert_eq!(strings, expected);
+
+    // An inner stream that's only ready every other poll.
+    let mut iter = values.clone().into_iter();
+    let mut pending = false;
+    let inner = stream::poll_fn(move |cx| {
+        pending = !pending;
+        if pending {
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        } else {
+            Poll::Ready(iter.next())
+        }
+    });
+    let mut radix = RadixStream::new(inner, 36).unwrap().batch_size(3);
+    assert_eq!(block_on(radix.by_ref().collect::<Vec<_>>()), expected);
+    assert!(radix.is_terminated());
+
+    let radix = RadixStream::new(stream::iter([1f32, 2.5]), 2).unwrap();
+    assert_eq!(radix.size_hint(), (2, Some(2)));
+    assert_eq!(block_on(radix.collect::<Vec<_>>()), ["1", "10.1"]);
+    assert!(RadixStream::new(stream::iter([1.0]), 37).is_err());
+}
+
+#[cfg(feature = "rkyv")]
+#[test]
+fn test_rkyv() {
+    use rkyv::rancor::Error;
+
+    let s = RadixString::new(-255.5, 16).unwrap();
+    let mut bytes = rkyv::to_bytes::<Error>(&s).unwrap();
+    let archived = rkyv::access::<ArchivedRadixString, Error>(&bytes).unwrap();
+    assert_eq!(archived.as_str(), "-ff.8");
+    assert_eq!(archived.base(), 16);
+    assert_eq!(archived.to_f64().unwrap(), -255.5);
+    assert_eq!(rkyv::deserialize::<RadixString, Error>(archived).unwrap(), s);
+
+    // Deserializing checks the syntax.
+    let dot = bytes.iter().position(|b| *b == b'.').unwrap();
+    bytes[dot] = b'!';
+    let archived = rkyv::access::<ArchivedRadixString, Error>(&bytes).unwrap();
+    assert!(archived.to_f64().is_err());
+    assert!(rkyv::deserialize::<RadixString, Error>(archived).is_err());
+}
+
+#[cfg(all(feature = "std", feature = "f64"))]
+#[test]
+fn test_utf16() {
+    for value in [0.5, -255.5, 1e21, 5e-324, f64::NAN, f64::NEG_INFINITY] {
+        for base in MIN_BASE..=MAX_BASE {
+            let expected: Vec<u16> = value.to_radix_str(base).unwrap().encode_utf16().collect();
+            assert_eq!(to_radix_utf16(value, base).unwrap(), expected);
+
+            let mut buf = [0; MAX_LEN];
+            let len = try_write_utf16(value, base, &mut buf).unwrap();
+            assert_eq!(&buf[..len], expected);
+        }
+    }
+
+    let mut buf = [0; 3];
+    assert!(matches!(try_write_utf16(255.5, 16, &mut buf), Err(WriteError::BufferTooSmall { required: 4 })));
+    assert_eq!(buf, [0; 3]);
+    assert!(matches!(try_write_utf16(1.0, 37, &mut buf), Err(WriteError::InvalidBase(_))));
+    assert!(to_radix_utf16(1.0, 1).is_err());
+}
+
+#[cfg(all(feature = "metrics", feature = "f64"))]
+#[test]
+fn test_metrics() {
+    // Other tests convert concurrently, so only increases are checked.
+    let before = metrics::snapshot();
+    (0.1).to_radix_str(3).unwrap();
+    (1.0 / 3.0).to_radix_str(3).unwrap();
+    // Rounds the last digit up: 0.04620462046204620463.
+    (0.1).to_radix_str(7).unwrap();
+    to_radix_str_compat(0.1, 3, Compat::SpiderMonkey).unwrap();
+    f64::from_radix_str(&format!("0.{}", "1".repeat(100)), 3).unwrap();
+    let after = metrics::snapshot();
+
+    assert!(after.conversions(3) >= before.conversions(3) + 3);
+    assert!(after.total_conversions() >= before.total_conversions() + 3);
+    assert!(after.round_ups > before.round_ups);
+    assert!(after.bignum_conversions > before.bignum_conversions);
+    assert!(after.bignum_parses > before.bignum_parses);
+    assert_eq!(after.conversions(37), 0);
+}
+
+#[cfg(feature = "parse")]
+#[test]
+fn test_radix_string_parts() {
+    let s = RadixString::new(-255.5, 16).unwrap();
+    assert!(s.is_negative() && s.is_finite());
+    assert_eq!((s.integer_part(), s.fraction_part(), s.num_fraction_digits()), ("ff", "8", 1));
+    assert_eq!((0..4).map(|i| s.digit_at(i)).collect::<Vec<_>>(), [Some(15), Some(15), Some(8), None]);
+
+    let s = RadixString::new(1e21, 36).unwrap();
+    assert!(!s.is_negative());
+    assert_eq!((s.integer_part(), s.fraction_part(), s.num_fraction_digits()), ("5v1j4f4ds7c000", "", 0));
+
+    let s = RadixString::from_radix_str("+1A.80", 16).unwrap();
+    assert_eq!((s.integer_part(), s.fraction_part()), ("1A", "80"));
+    assert_eq!(s.digit_at(1), Some(10));
+
+    let s = RadixString::new(f64::NEG_INFINITY, 2).unwrap();
+    assert!(s.is_negative() && !s.is_finite());
+    assert_eq!((s.integer_part(), s.fraction_part(), s.digit_at(0)), ("Infinity", "", None));
+    assert!(!RadixString::new(f64::NAN, 2).unwrap().is_finite());
+}
+
+#[cfg(feature = "parse")]
+#[test]
+fn test_radix_string_round_to() {
+    let round = |value: f64, base, digits| RadixString::new(value, base).unwrap().round_to(digits).as_str().to_owned();
+    assert_eq!(round(0.1, 16, 3), "0.19a");
+    assert_eq!(round(0.1, 16, 20), "0.1999999999999a");
+    assert_eq!(round(255.96875, 16, 1), "100.0");
+    assert_eq!(round(-255.96875, 16, 0), "-100");
+    // Ties go to even.
+    assert_eq!(round(2.5, 10, 0), "2");
+    assert_eq!(round(3.5, 10, 0), "4");
+    assert_eq!(round(0.75, 2, 1), "1.0");
+    // Odd bases can't write one half exactly.
+    let third = |s: &str, digits| RadixString::from_radix_str(s, 3).unwrap().round_to(digits).as_str().to_owned();
+    assert_eq!(third("0.111", 0), "0");
+    assert_eq!(third("0.112", 0), "1");
+    assert_eq!(third("2.21", 1), "2.2");
+    assert_eq!(third("2.22", 1), "10.0");
+    assert_eq!(round(-0.1, 10, 0), "0");
+    assert_eq!(RadixString::from_radix_str("+1F.F8", 16).unwrap().round_to(1).as_str(), "+20.0");
+    assert_eq!(RadixString::from_radix_str("9.9F", 16).unwrap().round_to(1).as_str(), "9.A");
+    assert_eq!(round(f64::NEG_INFINITY, 10, 0), "-Infinity");
+}
+
+#[cfg(all(feature = "parse", feature = "f64"))]
+#[test]
+fn test_normalize() {
+    let normalize = |s: &str| normalize_radix_str(s, NegativeZero::Collapse);
+    assert_eq!(normalize("+00FF.80"), "ff.8");
+    assert_eq!(normalize("0.5"), "0.5");
+    assert_eq!(normalize("000.500"), "0.5");
+    assert_eq!(normalize("-010.000"), "-10");
+    assert_eq!(normalize("-0"), "0");
+    assert_eq!(normalize("+Infinity"), "Infinity");
+    assert_eq!(normalize("-Infinity"), "-Infinity");
+    assert_eq!(normalize("NaN"), "NaN");
+    assert_eq!(normalize_radix_str("-00.0", NegativeZero::Keep), "-0");
+    assert_eq!(normalize_radix_str("-00.01", NegativeZero::Keep), "-0.01");
+
+    // Canonical strings are left alone.
+    for value in [0.1, -255.5, 1e21, 5e-324] {
+        let s = value.to_radix_str(36).unwrap();
+        assert_eq!(normalize(&s), s);
+    }
+
+    let s = RadixString::from_radix_str("-0A.B0", 16).unwrap().normalize(NegativeZero::Collapse);
+    assert_eq!((s.as_str(), s.base()), ("-a.b", 16));
+}
+
+#[cfg(all(feature = "parse", feature = "f32", feature = "f64"))]
+#[test]
+fn test_parse_exponent() {
+    assert_eq!(f64::from_radix_str("1.8@2", 16).unwrap(), 384.0);
+    assert_eq!(f64::from_radix_str("1.8p8", 16).unwrap(), 384.0);
+    assert_eq!(f64::from_radix_str("-1.1P+3", 2).unwrap(), -12.0);
+    assert_eq!(f64::from_radix_str("1p-1074", 2).unwrap(), 5e-324);
+    assert_eq!(f64::from_radix_str("1@-2", 10).unwrap(), 0.01);
+    assert_eq!(f64::from_radix_str("1@400", 10).unwrap(), f64::INFINITY);
+    assert_eq!(f64::from_radix_str("1@-400", 10).unwrap(), 0.0);
+    assert_eq!(f32::from_radix_str("3p-2", 8).unwrap(), 0.75);
+    // `p` is a digit in bases above 25.
+    assert_eq!(f64::from_radix_str("1p", 36).unwrap(), 61.0);
+
+    assert!(matches!(f64::from_radix_str("@5", 10), Err(ParseRadixError::InvalidDigit(0))));
+    assert!(matches!(f64::from_radix_str("1@", 10), Err(ParseRadixError::InvalidDigit(2))));
+    assert!(matches!(f64::from_radix_str("1@x", 10), Err(ParseRadixError::InvalidDigit(2))));
+    assert!(matches!(f64::from_radix_str("Infinity@1", 10), Err(ParseRadixError::InvalidDigit(8))));
+    assert!(matches!(RadixString::from_radix_str("1@2", 10), Err(ParseRadixError::InvalidDigit(1))));
+}
+
+#[cfg(feature = "parse")]
+#[test]
+fn test_radix_string_hash() {
+    use std::collections::HashSet;
+
+    let mut set = HashSet::new();
+    assert!(set.insert(RadixString::from_radix_str("ff.8", 16).unwrap()));
+    assert!(!set.insert(RadixString::from_radix_str("+00FF.80", 16).unwrap()));
+    assert!(set.insert(RadixString::from_radix_str("ff.8", 17).unwrap()));
+    assert!(set.insert(RadixString::from_radix_str("-ff.8", 16).unwrap()));
+    assert!(set.insert(RadixString::new(0.0, 10).unwrap()));
+    assert!(!set.insert(RadixString::from_radix_str("-0.00", 10).unwrap()));
+    assert_eq!(set.len(), 4);
+
+    assert_eq!(RadixString::from_radix_str("1A.80", 16).unwrap(), RadixString::new(26.5, 16).unwrap());
+    assert_ne!(RadixString::from_radix_str("10", 10).unwrap(), RadixString::from_radix_str("1", 10).unwrap());
+    assert_ne!(RadixString::from_radix_str("0.1", 10).unwrap(), RadixString::from_radix_str("0.01", 10).unwrap());
+
+    // In base 36, `nan` and `infinity` are numbers, unlike `NaN` and `Infinity`.
+    let mut set = HashSet::new();
+    for s in ["NaN", "nan", "Infinity", "infinity", "-Infinity", "-infinity"] {
+        assert!(set.insert(RadixString::from_radix_str(s, 36).unwrap()), "{}", s);
+    }
+    assert!(!set.insert(RadixString::from_radix_str("+Infinity", 36).unwrap()));
+    assert!(!set.insert(RadixString::from_radix_str("NAN", 36).unwrap()));
+    assert!(!set.insert(RadixString::from_radix_str("0Infinity", 36).unwrap()));
+    assert_ne!(RadixString::from_radix_str("NaN", 36).unwrap(), RadixString::from_radix_str("nan", 36).unwrap());
+    assert_ne!(RadixString::from_radix_str("Infinity", 36).unwrap(), RadixString::from_radix_str("infinity", 36).unwrap());
+}
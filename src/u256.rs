@@ -0,0 +1,101 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! [ToRadixStr] and [FromRadixStr] implementations for
+//! [primitive_types::U256](https://docs.rs/primitive-types), enabled by the
+//! `primitive-types` feature.
+
+use primitive_types::U256;
+use crate::{Base, FromRadixStr, InvalidBaseError, ParseRadixError, ToRadixStr, MAX_BASE, MIN_BASE};
+use crate::lut::digit_value;
+
+/// The maximum length of a U256 radix string, which is that of [U256::MAX] in
+/// base 2.
+const U256_MAX_LEN: usize = 256;
+
+/// Integers are written exactly, with every digit rather than the zeros
+/// `toString` fills in beyond 2^53.
+impl ToRadixStr for U256 {
+    fn to_radix_str(self, base: Base) -> Result<String, InvalidBaseError> {
+        if !(MIN_BASE..=MAX_BASE).contains(&base) {
+            return Err(InvalidBaseError(base));
+        }
+
+        let mut buf = [0; U256_MAX_LEN];
+        let mut start = U256_MAX_LEN;
+        let mut n = self;
+        loop {
+            let (quotient, remainder) = n.div_mod(U256::from(base));
+            start -= 1;
+            buf[start] = char::from_digit(remainder.low_u32(), base as u32).unwrap() as u8;
+            n = quotient;
+            if n.is_zero() {
+                break;
+            }
+        }
+        Ok(std::str::from_utf8(&buf[start..]).unwrap().to_owned())
+    }
+}
+
+/// Parses the digits of an unsigned integer, without a sign or radix point.
+/// Digits are case-insensitive.
+///
+/// Returns [ParseRadixError::Overflow] if the value doesn't fit in 256 bits.
+impl FromRadixStr for U256 {
+    fn from_radix_str(s: &str, base: Base) -> Result<Self, ParseRadixError> {
+        if !(MIN_BASE..=MAX_BASE).contains(&base) {
+            return Err(InvalidBaseError(base).into());
+        }
+        if s.is_empty() {
+            return Err(ParseRadixError::Empty);
+        }
+
+        let mut n = U256::zero();
+        for (i, c) in s.bytes().enumerate() {
+            let digit = digit_value(c, base).ok_or(ParseRadixError::InvalidDigit(i))?;
+            n = n
+                .checked_mul(U256::from(base))
+                .and_then(|n| n.checked_add(U256::from(digit)))
+                .ok_or(ParseRadixError::Overflow)?;
+        }
+        Ok(n)
+    }
+}
@@ -0,0 +1,211 @@
+/*
+ * Copyright (c) 2023 Levi-Michael Taylor. All rights reserved.
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files (the “Software”), to deal in the Software without restriction, including without limitation the rights to use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ * This library implements logic found in Google's open-source V8 library, specifically:
+ *  double.h (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/double.h)
+ *  DoubleToRadixCString (https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/src/numbers/conversions.cc#L1269)
+ * Copyright 2014, the V8 project authors. All rights reserved.
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *  Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ *  Redistributions in binary form must reproduce the above
+ *    copyright notice, this list of conditions and the following
+ *    disclaimer in the documentation and/or other materials provided
+ *    with the distribution.
+ *  Neither the name of Google Inc. nor the names of its
+ *    contributors may be used to endorse or promote products derived
+ *    from this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+ * "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+ * LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+ * A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+ * OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+ * SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+ * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * This license can also be found at: https://github.com/v8/v8/blob/f83601408c3207211bc8eb82a8802b01fd82c775/LICENSE
+ */
+
+//! Writing radix strings into caller-provided buffers.
+
+use core::fmt::{Display, Formatter};
+#[cfg(feature = "std")]
+use crate::pool::with_buffer;
+use crate::{Base, InvalidBaseError};
+#[cfg(any(feature = "std", not(feature = "small-stack")))]
+use crate::{write_radix, MAX_BASE, MIN_BASE};
+#[cfg(not(feature = "small-stack"))]
+use crate::BUFFER_LEN;
+#[cfg(feature = "small-stack")]
+use crate::write_radix_streaming;
+
+/// An error returned when writing a radix string into a buffer fails.
+#[derive(Debug)]
+pub enum WriteError {
+    /// The given [Base] is out of range of [MIN_BASE] and [MAX_BASE].
+    InvalidBase(InvalidBaseError),
+    /// The buffer is too small; the radix string needs `required` bytes.
+    BufferTooSmall {
+        /// The length of the radix string.
+        required: usize,
+    },
+    /// The writer returned an error.
+    Fmt(core::fmt::Error),
+}
+
+impl Display for WriteError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            WriteError::InvalidBase(e) => e.fmt(f),
+            WriteError::BufferTooSmall { required } => write!(f, "buffer too small: {} bytes required", required),
+            WriteError::Fmt(e) => e.fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for WriteError {}
+
+impl From<InvalidBaseError> for WriteError {
+    fn from(e: InvalidBaseError) -> Self {
+        WriteError::InvalidBase(e)
+    }
+}
+
+impl From<core::fmt::Error> for WriteError {
+    fn from(e: core::fmt::Error) -> Self {
+        WriteError::Fmt(e)
+    }
+}
+
+/// Writes the radix string representation of the given value into the start
+/// of buf as ASCII, returning the number of bytes written.
+///
+/// Nothing is written if the buffer is too small, in which case
+/// [WriteError::BufferTooSmall] reports the required length. Buffers of
+/// [max_len](crate::max_len) bytes are always large enough.
+///
+/// This never panics or allocates, so it's safe in signal handlers and across
+/// FFI boundaries. With the `no-panic` feature, optimized builds with
+/// `codegen-units = 1` fail to link if the compiler can't prove it. The check
+/// is skipped with `soft-float`, whose emulated arithmetic it can't see
+/// through, with `tracing`, whose subscribers may panic, and with
+/// `small-stack`.
+///
+/// The conversion needs a scratch buffer of about [MAX_LEN](crate::MAX_LEN)
+/// bytes on the stack. With the `small-stack` feature, for targets such as
+/// AVR and MSP430 whose stacks are smaller than that, it goes through
+/// [write_radix_streaming](crate::write_radix_streaming) instead, which needs
+/// about a hundred bytes but generates the digits twice.
+///
+/// Returns [WriteError::InvalidBase] if the given [Base] is out of range of
+/// [MIN_BASE] and [MAX_BASE] (inclusive).
+pub fn try_write(value: impl Into<f64>, base: Base, buf: &mut [u8]) -> Result<usize, WriteError> {
+    try_write_f64(value.into(), base, buf)
+}
+
+/// Appends the radix string representation of the given value in the given
+/// [Base] to the given byte vector as ASCII, for building byte payloads
+/// without a `String` in between.
+///
+/// Returns [InvalidBaseError] if the given [Base] is out of range of
+/// [MIN_BASE] and [MAX_BASE] (inclusive), in which case nothing is appended.
+///
+/// Example:
+/// ```rust
+/// use radix_ecmascript::extend_radix;
+///
+/// let mut bytes = b"x=".to_vec();
+/// extend_radix(255.5, 16, &mut bytes).unwrap();
+/// assert_eq!(bytes, b"x=ff.8");
+/// ```
+#[cfg(feature = "std")]
+pub fn extend_radix(value: impl Into<f64>, base: Base, bytes: &mut Vec<u8>) -> Result<(), InvalidBaseError> {
+    if !(MIN_BASE..=MAX_BASE).contains(&base) {
+        return Err(InvalidBaseError(base));
+    }
+
+    let value = value.into();
+    with_buffer(|buf| {
+        let range = write_radix(value, base, buf);
+        bytes.extend_from_slice(&buf[range]);
+    });
+    Ok(())
+}
+
+/// [try_write] for f64, where the `no-panic` check applies. It's kept out of
+/// line so the check is made where the crate is built, rather than in callers
+/// that inline it.
+#[cfg(not(feature = "small-stack"))]
+#[inline(never)]
+#[cfg_attr(
+    all(feature = "no-panic", not(feature = "soft-float"), not(feature = "tracing")),
+    no_panic::no_panic
+)]
+fn try_write_f64(value: f64, base: Base, buf: &mut [u8]) -> Result<usize, WriteError> {
+    if !(MIN_BASE..=MAX_BASE).contains(&base) {
+        return Err(InvalidBaseError(base).into());
+    }
+
+    // A stack buffer rather than the pool, whose thread-local storage can
+    // panic once the thread is being torn down.
+    let mut scratch = [0; BUFFER_LEN];
+    let range = write_radix(value, base, &mut scratch);
+    let written = scratch.get(range).unwrap_or_default();
+    match buf.get_mut(..written.len()) {
+        Some(dest) => {
+            dest.copy_from_slice(written);
+            Ok(written.len())
+        }
+        None => Err(WriteError::BufferTooSmall { required: written.len() }),
+    }
+}
+
+/// [try_write] for f64 with the `small-stack` feature, which streams the radix
+/// string rather than converting into a scratch buffer.
+#[cfg(feature = "small-stack")]
+#[inline(never)]
+fn try_write_f64(value: f64, base: Base, buf: &mut [u8]) -> Result<usize, WriteError> {
+    // Measure first, so nothing is written if the buffer is too small.
+    let mut measure = SliceWriter { buf: &mut [], len: 0 };
+    write_radix_streaming(value, base, &mut measure)?;
+    if measure.len > buf.len() {
+        return Err(WriteError::BufferTooSmall { required: measure.len });
+    }
+
+    let mut writer = SliceWriter { buf, len: 0 };
+    write_radix_streaming(value, base, &mut writer)?;
+    Ok(writer.len)
+}
+
+/// A writer into the start of a byte slice, counting the bytes written.
+/// Bytes past the end of the slice are counted but dropped.
+#[cfg(feature = "small-stack")]
+struct SliceWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+#[cfg(feature = "small-stack")]
+impl core::fmt::Write for SliceWriter<'_> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let end = self.len + s.len();
+        if let Some(dest) = self.buf.get_mut(self.len..end) {
+            dest.copy_from_slice(s.as_bytes());
+        }
+        self.len = end;
+        Ok(())
+    }
+}